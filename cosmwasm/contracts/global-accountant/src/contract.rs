@@ -1,11 +1,11 @@
 use std::marker::PhantomData;
 
 use accountant::{
-    query_balance, query_modification,
-    state::{account, transfer, Kind, Modification, TokenAddress, Transfer},
+    query_balance, query_balance_at, query_modification, query_reversion,
+    state::{account, transfer, Kind, Modification, Reversion, TokenAddress, Transfer},
     validate_transfer,
 };
-use anyhow::{ensure, Context};
+use anyhow::{anyhow, ensure, Context};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
@@ -29,19 +29,35 @@ use crate::{
     bail,
     error::{AnyError, ContractError},
     msg::{
-        AllAccountsResponse, AllModificationsResponse, AllPendingTransfersResponse,
-        AllTransfersResponse, BatchTransferStatusResponse, ChainRegistrationResponse, ExecuteMsg,
-        MigrateMsg, MissingObservation, MissingObservationsResponse, Observation, ObservationError,
-        ObservationStatus, QueryMsg, SubmitObservationResponse, TransferDetails, TransferStatus,
+        AccountOutflow, AllAccountsResponse, AllModificationsResponse, AllOutflowsResponse,
+        AllPendingTransfersResponse, AllReversionsResponse, AllTransfersResponse,
+        BackfillInvariantStateResponse, BatchTransferStatusResponse, ChainRegistrationResponse,
+        CheckInvariantsResponse, ExecuteMsg, MigrateMsg, MissingObservation,
+        MissingObservationsResponse, Observation, ObservationError, ObservationStatus,
+        OutflowLimitUpdate, PrunePendingTransfersResponse, PrunedPendingTransfer, QueryMsg,
+        RateLimitedTransfer, SubmitObservationResponse, TransferDetails, TransferStatus,
         SUBMITTED_OBSERVATIONS_PREFIX,
     },
-    state::{Data, PendingTransfer, CHAIN_REGISTRATIONS, DIGESTS, PENDING_TRANSFERS},
+    state::{
+        Data, OutflowLimit, PendingTransfer, RateLimited, CHAIN_REGISTRATIONS, DIGESTS, OUTFLOWS,
+        OUTFLOW_LIMITS, PENDING_TRANSFERS, RATE_LIMITED_TRANSFERS,
+    },
 };
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:global-accountant";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// The maximum number of pending transfers examined by a single `PrunePendingTransfers` message.
+const MAX_PRUNE_LIMIT: u32 = 100;
+
+// The maximum number of accounts or modifications examined by a single `BackfillInvariantState`
+// message.
+const MAX_BACKFILL_LIMIT: u32 = 100;
+
+// The maximum number of tokens examined by a single `CheckInvariants` query.
+const MAX_INVARIANT_LIMIT: u32 = 100;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut<WormholeQuery>,
@@ -51,6 +67,8 @@ pub fn instantiate(
 ) -> Result<Response, AnyError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)
         .context("failed to set contract version")?;
+    accountant::init_invariant_state(deps.storage)
+        .context("failed to initialize invariant state")?;
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
@@ -59,14 +77,18 @@ pub fn instantiate(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut<WormholeQuery>, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(deps: DepsMut<WormholeQuery>, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    // The per-token invariant state is rebuilt in batches by `BackfillInvariantState` messages
+    // because there may be too many accounts and modifications to do it here.
+    accountant::start_invariant_backfill(deps.storage)?;
+
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut<WormholeQuery>,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, AnyError> {
@@ -75,14 +97,28 @@ pub fn execute(
             observations,
             guardian_set_index,
             signature,
-        } => submit_observations(deps, info, observations, guardian_set_index, signature),
+        } => submit_observations(
+            deps,
+            &env,
+            info,
+            observations,
+            guardian_set_index,
+            signature,
+        ),
+
+        ExecuteMsg::SubmitVaas { vaas } => submit_vaas(deps, &env, info, vaas),
+
+        ExecuteMsg::PrunePendingTransfers { start_after, limit } => {
+            prune_pending_transfers(deps, &env, info, start_after, limit)
+        }
 
-        ExecuteMsg::SubmitVaas { vaas } => submit_vaas(deps, info, vaas),
+        ExecuteMsg::BackfillInvariantState { limit } => backfill_invariant_state(deps, info, limit),
     }
 }
 
 fn submit_observations(
     mut deps: DepsMut<WormholeQuery>,
+    env: &Env,
     info: MessageInfo,
     observations: Binary,
     guardian_set_index: u32,
@@ -116,7 +152,7 @@ fn submit_observations(
     let mut events = Vec::with_capacity(observations.len());
     for o in observations {
         let key = transfer::Key::new(o.emitter_chain, o.emitter_address.into(), o.sequence);
-        match handle_observation(deps.branch(), o, guardian_set_index, quorum, signature) {
+        match handle_observation(deps.branch(), env, o, guardian_set_index, quorum, signature) {
             Ok((status, event)) => {
                 responses.push(SubmitObservationResponse { key, status });
                 if let Some(evt) = event {
@@ -150,6 +186,7 @@ fn submit_observations(
 
 fn handle_observation(
     mut deps: DepsMut<WormholeQuery>,
+    env: &Env,
     o: Observation,
     guardian_set_index: u32,
     quorum: u32,
@@ -198,6 +235,7 @@ fn handle_observation(
                 o.tx_hash.clone(),
                 o.emitter_chain,
                 guardian_set_index,
+                env.block.time.seconds(),
             ));
             let back = pending.len() - 1;
             &mut pending[back]
@@ -239,15 +277,53 @@ fn handle_observation(
         _ => bail!("Unknown tokenbridge payload"),
     };
 
+    let now = env.block.time.seconds();
+    let src = account::Key::new(
+        tx_key.emitter_chain(),
+        tx_data.token_chain,
+        tx_data.token_address,
+    );
+    if let Some((limit, usage)) =
+        query_outflow_usage(deps.as_ref(), src.clone(), now).context("failed to load outflows")?
+    {
+        if limit.is_exceeded_by(usage, tx_data.amount) {
+            // Keep the signatures around so that the transfer can be committed once enough of the
+            // window has elapsed and the observation is submitted again.
+            key.save(deps.storage, &pending)
+                .context("failed to save pending transfers")?;
+            let rate_limited = RateLimited {
+                data: tx_data.clone(),
+                digest,
+            };
+            RATE_LIMITED_TRANSFERS
+                .save(deps.storage, tx_key.clone(), &rate_limited)
+                .context("failed to save rate limited transfer")?;
+
+            let event = cw_transcode::to_event(&RateLimitedTransfer {
+                key: tx_key,
+                data: tx_data,
+                usage,
+            })
+            .map(Some)
+            .context("failed to transcode `RateLimitedTransfer` to `Event`")?;
+
+            return Ok((ObservationStatus::RateLimited, event));
+        }
+    }
+
+    let amount = tx_data.amount;
     accountant::commit_transfer(
         deps.branch(),
+        env,
         Transfer {
-            key: tx_key,
+            key: tx_key.clone(),
             data: tx_data,
         },
     )
     .context("failed to commit transfer")?;
 
+    record_outflow(deps.branch(), src, now, amount)?;
+
     // Save the digest of the observation so that we can check for duplicate transfer keys with
     // mismatched data.
     digest_key
@@ -256,6 +332,7 @@ fn handle_observation(
 
     // Now that the transfer has been committed, we don't need to keep it in the pending list.
     key.remove(deps.storage);
+    RATE_LIMITED_TRANSFERS.remove(deps.storage, tx_key);
 
     let event = cw_transcode::to_event(&o)
         .map(Some)
@@ -266,10 +343,11 @@ fn handle_observation(
 
 fn modify_balance(
     deps: DepsMut<WormholeQuery>,
+    env: &Env,
     info: &MessageInfo,
     modification: Modification,
 ) -> Result<Event, AnyError> {
-    let mut event = accountant::modify_balance(deps, modification)
+    let mut event = accountant::modify_balance(deps, env, modification)
         .context("failed to modify account balance")?;
 
     event = event
@@ -279,14 +357,230 @@ fn modify_balance(
     Ok(event)
 }
 
+fn revert_transfer(
+    deps: DepsMut<WormholeQuery>,
+    env: &Env,
+    info: &MessageInfo,
+    reversion: Reversion,
+) -> Result<Event, AnyError> {
+    let mut event =
+        accountant::revert_transfer(deps, env, reversion).context("failed to revert transfer")?;
+
+    event = event
+        .add_attribute("action", "revert_transfer")
+        .add_attribute("owner", info.sender.clone());
+
+    Ok(event)
+}
+
+fn set_outflow_limit(
+    deps: DepsMut<WormholeQuery>,
+    info: &MessageInfo,
+    key: account::Key,
+    limit: OutflowLimit,
+) -> anyhow::Result<Event> {
+    if limit.window == 0 {
+        OUTFLOW_LIMITS.remove(deps.storage, key.clone());
+        OUTFLOWS.remove(deps.storage, key.clone());
+    } else {
+        OUTFLOW_LIMITS
+            .save(deps.storage, key.clone(), &limit)
+            .context("failed to save outflow limit")?;
+    }
+
+    let event = cw_transcode::to_event(&OutflowLimitUpdate {
+        key,
+        limit: limit.limit,
+        window: limit.window,
+    })
+    .context("failed to transcode `OutflowLimitUpdate` to `Event`")?;
+
+    Ok(event
+        .add_attribute("action", "set_outflow_limit")
+        .add_attribute("owner", info.sender.clone()))
+}
+
+// Commits a rate limited transfer without checking the outflow limit of its source account.  The
+// transfer still counts towards that limit.
+fn release_transfer(
+    mut deps: DepsMut<WormholeQuery>,
+    env: &Env,
+    info: &MessageInfo,
+    key: transfer::Key,
+) -> anyhow::Result<Event> {
+    let RateLimited { data, digest } = RATE_LIMITED_TRANSFERS
+        .may_load(deps.storage, key.clone())
+        .context("failed to load rate limited transfer")?
+        .ok_or_else(|| anyhow!("no rate limited transfer for key {key}"))?;
+
+    let src = account::Key::new(key.emitter_chain(), data.token_chain, data.token_address);
+    let amount = data.amount;
+    let tx = Transfer {
+        key: key.clone(),
+        data,
+    };
+    let event = accountant::commit_transfer(deps.branch(), env, tx)
+        .with_context(|| format!("failed to commit transfer for key {key}"))?;
+
+    record_outflow(deps.branch(), src, env.block.time.seconds(), amount)?;
+
+    DIGESTS
+        .save(
+            deps.storage,
+            (
+                key.emitter_chain(),
+                key.emitter_address().to_vec(),
+                key.sequence(),
+            ),
+            &digest,
+        )
+        .context("failed to save transfer digest")?;
+
+    PENDING_TRANSFERS.remove(deps.storage, key.clone());
+    RATE_LIMITED_TRANSFERS.remove(deps.storage, key);
+
+    Ok(event
+        .add_attribute("action", "release_transfer")
+        .add_attribute("owner", info.sender.clone()))
+}
+
+// Adds an outflow of `amount` to the current window of the account for `key`.  Outflows are only
+// tracked for accounts that have a limit.
+fn record_outflow(
+    deps: DepsMut<WormholeQuery>,
+    key: account::Key,
+    now: u64,
+    amount: Uint256,
+) -> anyhow::Result<()> {
+    let limit = match OUTFLOW_LIMITS
+        .may_load(deps.storage, key.clone())
+        .context("failed to load outflow limit")?
+    {
+        Some(l) => l,
+        None => return Ok(()),
+    };
+
+    let mut outflows = OUTFLOWS
+        .may_load(deps.storage, key.clone())
+        .map(Option::unwrap_or_default)
+        .context("failed to load outflows")?;
+    limit.prune(&mut outflows, now);
+    limit.record(&mut outflows, now, amount);
+
+    OUTFLOWS
+        .save(deps.storage, key, &outflows)
+        .context("failed to save outflows")
+}
+
+fn prune_pending_transfers(
+    deps: DepsMut<WormholeQuery>,
+    env: &Env,
+    info: MessageInfo,
+    start_after: Option<transfer::Key>,
+    limit: Option<u32>,
+) -> Result<Response, AnyError> {
+    let now = env.block.time.seconds();
+    let limit = limit.map_or(MAX_PRUNE_LIMIT, |l| l.min(MAX_PRUNE_LIMIT)) as usize;
+    let start = start_after.map(|key| Bound::Exclusive((key, PhantomData)));
+
+    let keys = PENDING_TRANSFERS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()
+        .context("failed to load pending transfer keys")?;
+    let last_key = if keys.len() < limit {
+        None
+    } else {
+        keys.last().cloned()
+    };
+
+    let mut events = Vec::new();
+    for key in keys {
+        if RATE_LIMITED_TRANSFERS.has(deps.storage, key.clone()) {
+            // This transfer already reached quorum and is only waiting on the outflow limit.
+            continue;
+        }
+
+        let pending_key = PENDING_TRANSFERS.key(key.clone());
+        let mut pending = pending_key
+            .load(deps.storage)
+            .context("failed to load `PendingTransfer`")?;
+
+        // Data saved before `observed_at` was added has no record of when it was first observed
+        // so its expiry period starts the first time it is examined here.
+        let stamped = pending
+            .iter_mut()
+            .fold(false, |acc, d| d.stamp_observed_at(now) || acc);
+
+        let mut pruned = Vec::new();
+        pending.retain(|d| {
+            if d.is_expired(now) {
+                pruned.push(d.clone());
+                false
+            } else {
+                true
+            }
+        });
+
+        if pruned.is_empty() {
+            if stamped {
+                pending_key
+                    .save(deps.storage, &pending)
+                    .context("failed to save pending transfers")?;
+            }
+            continue;
+        }
+
+        if pending.is_empty() {
+            pending_key.remove(deps.storage);
+        } else {
+            pending_key
+                .save(deps.storage, &pending)
+                .context("failed to save pending transfers")?;
+        }
+
+        let evt = cw_transcode::to_event(&PrunedPendingTransfer { key, data: pruned })
+            .context("failed to transcode `PrunedPendingTransfer` to `Event`")?;
+        events.push(evt);
+    }
+
+    let data = to_binary(&PrunePendingTransfersResponse { last_key })
+        .context("failed to serialize prune response")?;
+
+    Ok(Response::new()
+        .add_attribute("action", "prune_pending_transfers")
+        .add_attribute("owner", info.sender)
+        .set_data(data)
+        .add_events(events))
+}
+
+fn backfill_invariant_state(
+    deps: DepsMut<WormholeQuery>,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, AnyError> {
+    let limit = limit.map_or(MAX_BACKFILL_LIMIT, |l| l.clamp(1, MAX_BACKFILL_LIMIT)) as usize;
+    let complete = accountant::backfill_invariant_state(deps, limit)
+        .context("failed to backfill invariant state")?;
+
+    let data = to_binary(&BackfillInvariantStateResponse { complete })
+        .context("failed to serialize backfill response")?;
+
+    Ok(Response::new()
+        .add_attribute("action", "backfill_invariant_state")
+        .add_attribute("owner", info.sender)
+        .set_data(data))
+}
+
 fn submit_vaas(
     mut deps: DepsMut<WormholeQuery>,
+    env: &Env,
     info: MessageInfo,
     vaas: Vec<Binary>,
 ) -> Result<Response, AnyError> {
     let evts = vaas
         .into_iter()
-        .map(|v| handle_vaa(deps.branch(), &info, v))
+        .map(|v| handle_vaa(deps.branch(), env, &info, v))
         .collect::<anyhow::Result<Vec<_>>>()?;
     Ok(Response::new()
         .add_attribute("action", "submit_vaas")
@@ -296,6 +590,7 @@ fn submit_vaas(
 
 fn handle_vaa(
     mut deps: DepsMut<WormholeQuery>,
+    env: &Env,
     info: &MessageInfo,
     vaa: Binary,
 ) -> anyhow::Result<Event> {
@@ -348,14 +643,19 @@ fn handle_vaa(
         } else if module == accountant_module::MODULE {
             let govpacket = serde_wormhole::from_slice(body.payload)
                 .context("failed to parse accountant governance packet")?;
-            handle_accountant_governance_vaa(deps.branch(), info, body.with_payload(govpacket))?
+            handle_accountant_governance_vaa(
+                deps.branch(),
+                env,
+                info,
+                body.with_payload(govpacket),
+            )?
         } else {
             bail!("unknown governance module")
         }
     } else {
         let msg = serde_wormhole::from_slice(body.payload)
             .context("failed to parse tokenbridge message")?;
-        handle_tokenbridge_vaa(deps.branch(), body.with_payload(msg))?
+        handle_tokenbridge_vaa(deps.branch(), env, body.with_payload(msg))?
     };
 
     digest_key
@@ -398,6 +698,7 @@ fn handle_token_governance_vaa(
 
 fn handle_accountant_governance_vaa(
     deps: DepsMut<WormholeQuery>,
+    env: &Env,
     info: &MessageInfo,
     body: Body<accountant_module::GovernancePacket>,
 ) -> anyhow::Result<Event> {
@@ -434,13 +735,56 @@ fn handle_accountant_governance_vaa(
                 amount,
                 reason: reason.to_string(),
             };
-            modify_balance(deps, info, modification).map_err(|e| e.into())
+            modify_balance(deps, env, info, modification).map_err(|e| e.into())
+        }
+        accountant_module::Action::RevertTransfer {
+            emitter_chain,
+            emitter_address,
+            sequence,
+            reason,
+        } => {
+            let reversion = Reversion {
+                key: transfer::Key::new(
+                    emitter_chain,
+                    TokenAddress::new(emitter_address.0),
+                    sequence,
+                ),
+                reason: reason.to_string(),
+            };
+            revert_transfer(deps, env, info, reversion).map_err(|e| e.into())
+        }
+        accountant_module::Action::SetOutflowLimit {
+            chain_id,
+            token_chain,
+            token_address,
+            limit,
+            window,
+        } => {
+            let key = account::Key::new(chain_id, token_chain, TokenAddress::new(token_address.0));
+            let limit = OutflowLimit {
+                limit: Uint256::from_be_bytes(limit.0),
+                window,
+            };
+            set_outflow_limit(deps, info, key, limit)
+        }
+        accountant_module::Action::ReleaseTransfer {
+            emitter_chain,
+            emitter_address,
+            sequence,
+        } => {
+            let key = transfer::Key::new(
+                emitter_chain,
+                TokenAddress::new(emitter_address.0),
+                sequence,
+            );
+            release_transfer(deps, env, info, key)
         }
     }
 }
 
 fn handle_tokenbridge_vaa(
     mut deps: DepsMut<WormholeQuery>,
+    env: &Env,
     body: Body<token::Message<&RawMessage>>,
 ) -> anyhow::Result<Event> {
     let registered_emitter = CHAIN_REGISTRATIONS
@@ -482,25 +826,41 @@ fn handle_tokenbridge_vaa(
         body.sequence,
     );
 
+    // VAAs have already been signed by a quorum of guardians so they are never rate limited but
+    // they still count towards the outflow limit of the source account.
+    let src = account::Key::new(key.emitter_chain(), data.token_chain, data.token_address);
+    let amount = data.amount;
     let tx = Transfer {
         key: key.clone(),
         data,
     };
-    let evt = accountant::commit_transfer(deps.branch(), tx)
+    let evt = accountant::commit_transfer(deps.branch(), env, tx)
         .with_context(|| format!("failed to commit transfer for key {key}"))?;
 
-    PENDING_TRANSFERS.remove(deps.storage, key);
+    record_outflow(deps.branch(), src, env.block.time.seconds(), amount)?;
+
+    PENDING_TRANSFERS.remove(deps.storage, key.clone());
+    RATE_LIMITED_TRANSFERS.remove(deps.storage, key);
 
     Ok(evt)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps<WormholeQuery>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps<WormholeQuery>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Balance(key) => query_balance(deps, key).and_then(|resp| to_binary(&resp)),
         QueryMsg::AllAccounts { start_after, limit } => {
             query_all_accounts(deps, start_after, limit).and_then(|resp| to_binary(&resp))
         }
+        QueryMsg::BalanceAt { key, height } => {
+            query_balance_at(deps, key, height).and_then(|resp| to_binary(&resp))
+        }
+        QueryMsg::AllAccountsAt {
+            height,
+            start_after,
+            limit,
+        } => query_all_accounts_at(deps, height, start_after, limit)
+            .and_then(|resp| to_binary(&resp)),
         QueryMsg::AllTransfers { start_after, limit } => {
             query_all_transfers(deps, start_after, limit).and_then(|resp| to_binary(&resp))
         }
@@ -535,6 +895,20 @@ pub fn query(deps: Deps<WormholeQuery>, _env: Env, msg: QueryMsg) -> StdResult<B
         QueryMsg::BatchTransferStatus(keys) => {
             query_batch_transfer_status(deps, keys).and_then(|resp| to_binary(&resp))
         }
+        QueryMsg::Reversion(key) => query_reversion(deps, key).and_then(|resp| to_binary(&resp)),
+        QueryMsg::AllReversions { start_after, limit } => {
+            query_all_reversions(deps, start_after, limit).and_then(|resp| to_binary(&resp))
+        }
+        QueryMsg::Outflow(key) => {
+            query_outflow(deps, key, env.block.time.seconds()).and_then(|resp| to_binary(&resp))
+        }
+        QueryMsg::AllOutflows { start_after, limit } => {
+            query_all_outflows(deps, start_after, limit, env.block.time.seconds())
+                .and_then(|resp| to_binary(&resp))
+        }
+        QueryMsg::CheckInvariants { start_after, limit } => {
+            check_invariants(deps, start_after, limit).and_then(|resp| to_binary(&resp))
+        }
     }
 }
 
@@ -558,6 +932,40 @@ fn query_all_accounts(
     }
 }
 
+fn query_all_accounts_at(
+    deps: Deps<WormholeQuery>,
+    height: u64,
+    start_after: Option<account::Key>,
+    limit: Option<u32>,
+) -> StdResult<AllAccountsResponse> {
+    if let Some(lim) = limit {
+        let l = lim
+            .try_into()
+            .map_err(|_| ConversionOverflowError::new("u32", "usize", lim.to_string()))?;
+        accountant::query_all_accounts_at(deps, start_after, height)
+            .take(l)
+            .collect::<StdResult<Vec<_>>>()
+            .map(|accounts| AllAccountsResponse { accounts })
+    } else {
+        accountant::query_all_accounts_at(deps, start_after, height)
+            .collect::<StdResult<Vec<_>>>()
+            .map(|accounts| AllAccountsResponse { accounts })
+    }
+}
+
+fn check_invariants(
+    deps: Deps<WormholeQuery>,
+    start_after: Option<(u16, TokenAddress)>,
+    limit: Option<u32>,
+) -> StdResult<CheckInvariantsResponse> {
+    let limit = limit.map_or(MAX_INVARIANT_LIMIT, |l| l.clamp(1, MAX_INVARIANT_LIMIT)) as usize;
+    let (violations, last_key) = accountant::check_invariants(deps, start_after, limit)?;
+    Ok(CheckInvariantsResponse {
+        violations,
+        last_key,
+    })
+}
+
 fn query_all_transfers(
     deps: Deps<WormholeQuery>,
     start_after: Option<transfer::Key>,
@@ -663,6 +1071,91 @@ fn query_all_modifications(
     }
 }
 
+fn query_all_reversions(
+    deps: Deps<WormholeQuery>,
+    start_after: Option<transfer::Key>,
+    limit: Option<u32>,
+) -> StdResult<AllReversionsResponse> {
+    if let Some(lim) = limit {
+        let l = lim
+            .try_into()
+            .map_err(|_| ConversionOverflowError::new("u32", "usize", lim.to_string()))?;
+        accountant::query_all_reversions(deps, start_after)
+            .take(l)
+            .collect::<StdResult<Vec<_>>>()
+            .map(|reversions| AllReversionsResponse { reversions })
+    } else {
+        accountant::query_all_reversions(deps, start_after)
+            .collect::<StdResult<Vec<_>>>()
+            .map(|reversions| AllReversionsResponse { reversions })
+    }
+}
+
+// Returns the outflow limit for the account with `key`, if it has one, along with the amount that
+// has flowed out of the account in the window ending at `now`.
+fn query_outflow_usage(
+    deps: Deps<WormholeQuery>,
+    key: account::Key,
+    now: u64,
+) -> StdResult<Option<(OutflowLimit, Uint256)>> {
+    let limit = match OUTFLOW_LIMITS.may_load(deps.storage, key.clone())? {
+        Some(l) => l,
+        None => return Ok(None),
+    };
+
+    let mut outflows = OUTFLOWS.may_load(deps.storage, key)?.unwrap_or_default();
+    let usage = limit.prune(&mut outflows, now);
+
+    Ok(Some((limit, usage)))
+}
+
+fn query_outflow(
+    deps: Deps<WormholeQuery>,
+    key: account::Key,
+    now: u64,
+) -> StdResult<AccountOutflow> {
+    query_outflow_usage(deps, key.clone(), now)?
+        .map(|(limit, usage)| AccountOutflow {
+            key: key.clone(),
+            limit,
+            usage,
+        })
+        .ok_or_else(|| StdError::not_found(format!("outflow limit for account {key:?}")))
+}
+
+fn query_all_outflows(
+    deps: Deps<WormholeQuery>,
+    start_after: Option<account::Key>,
+    limit: Option<u32>,
+    now: u64,
+) -> StdResult<AllOutflowsResponse> {
+    let start = start_after.map(|key| Bound::Exclusive((key, PhantomData)));
+
+    let iter = OUTFLOW_LIMITS
+        .range(deps.storage, start, None, Order::Ascending)
+        .map(|item| {
+            item.and_then(|(key, limit)| {
+                let mut outflows = OUTFLOWS
+                    .may_load(deps.storage, key.clone())?
+                    .unwrap_or_default();
+                let usage = limit.prune(&mut outflows, now);
+                Ok(AccountOutflow { key, limit, usage })
+            })
+        });
+
+    if let Some(lim) = limit {
+        let l = lim
+            .try_into()
+            .map_err(|_| ConversionOverflowError::new("u32", "usize", lim.to_string()))?;
+        iter.take(l)
+            .collect::<StdResult<Vec<_>>>()
+            .map(|outflows| AllOutflowsResponse { outflows })
+    } else {
+        iter.collect::<StdResult<Vec<_>>>()
+            .map(|outflows| AllOutflowsResponse { outflows })
+    }
+}
+
 fn query_chain_registration(
     deps: Deps<WormholeQuery>,
     chain: u16,
@@ -707,8 +1200,15 @@ fn query_transfer_status(
     )? {
         let data = accountant::query_transfer(deps, key.clone())?;
         Ok(TransferStatus::Committed { data, digest })
-    } else if let Some(data) = PENDING_TRANSFERS.may_load(deps.storage, key.clone())? {
-        Ok(TransferStatus::Pending(tinyvec_to_vec(data)))
+    } else if let Some(pending) = PENDING_TRANSFERS.may_load(deps.storage, key.clone())? {
+        if let Some(r) = RATE_LIMITED_TRANSFERS.may_load(deps.storage, key.clone())? {
+            Ok(TransferStatus::RateLimited {
+                data: r.data,
+                pending: tinyvec_to_vec(pending),
+            })
+        } else {
+            Ok(TransferStatus::Pending(tinyvec_to_vec(pending)))
+        }
     } else {
         Err(StdError::not_found(format!("transfer with key {key}")))
     }
@@ -1,13 +1,15 @@
-use accountant::state::{account, transfer, Account, Modification, Transfer};
+use accountant::state::{
+    account, transfer, Account, InvariantViolation, Modification, Reversion, TokenAddress, Transfer,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Binary;
+use cosmwasm_std::{Binary, Uint256};
 use serde_wormhole::RawMessage;
 use wormhole_sdk::{
     vaa::{Body, Signature},
     Address,
 };
 
-use crate::state::{self, PendingTransfer};
+use crate::state::{self, OutflowLimit, PendingTransfer};
 
 pub const SUBMITTED_OBSERVATIONS_PREFIX: &[u8; 35] = b"acct_sub_obsfig_000000000000000000|";
 
@@ -71,6 +73,10 @@ impl Observation {
 pub enum ObservationStatus {
     Pending,
     Committed,
+    // The observation reached quorum but committing it would exceed the outflow limit of the
+    // source account.  It remains pending until the limit allows it, a VAA is submitted for it, or
+    // governance releases it.
+    RateLimited,
     Error(String),
 }
 
@@ -86,6 +92,43 @@ pub struct ObservationError {
     pub error: String,
 }
 
+#[cw_serde]
+pub struct PrunedPendingTransfer {
+    pub key: transfer::Key,
+    // The expired observation data that was removed.
+    pub data: Vec<state::Data>,
+}
+
+#[cw_serde]
+pub struct PrunePendingTransfersResponse {
+    // The last pending transfer key that was examined.  Pass this as `start_after` to continue
+    // pruning from where this call stopped.  `None` if there are no more pending transfers.
+    pub last_key: Option<transfer::Key>,
+}
+
+#[cw_serde]
+pub struct RateLimitedTransfer {
+    pub key: transfer::Key,
+    pub data: transfer::Data,
+    // The amount that has already flowed out of the source account in the current window.
+    pub usage: Uint256,
+}
+
+#[cw_serde]
+pub struct OutflowLimitUpdate {
+    pub key: account::Key,
+    pub limit: Uint256,
+    // A window of 0 indicates that the limit was removed.
+    pub window: u64,
+}
+
+#[cw_serde]
+pub struct BackfillInvariantStateResponse {
+    // True once the per-token invariant state has been completely rebuilt and no more
+    // `BackfillInvariantState` messages are needed.
+    pub complete: bool,
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Submit a series of observations.  Once the contract has received a quorum of signatures
@@ -108,6 +151,24 @@ pub enum ExecuteMsg {
         /// wire format.
         vaas: Vec<Binary>,
     },
+
+    /// Remove pending observation data that has not reached quorum within
+    /// `PENDING_TRANSFER_EXPIRY` seconds of first being observed, for example because the
+    /// guardians observed a transaction that was later reorged out.  Anyone may call this.  At
+    /// most `limit` pending transfers, starting after `start_after`, are examined in a single call.
+    /// Data saved before the contract recorded when it was first observed is not pruned until
+    /// `PENDING_TRANSFER_EXPIRY` seconds after it is first examined.
+    /// Transfers that are waiting for room under an outflow limit are never pruned.
+    PrunePendingTransfers {
+        start_after: Option<transfer::Key>,
+        limit: Option<u32>,
+    },
+
+    /// Continue rebuilding the per-token state used by `CheckInvariants` after the contract was
+    /// migrated from a version that did not have it.  Anyone may call this.  At most `limit`
+    /// accounts or modifications are examined in a single call and `CheckInvariants` returns an
+    /// error until the response reports that the state is complete.
+    BackfillInvariantState { limit: Option<u32> },
 }
 
 #[cw_serde]
@@ -123,6 +184,16 @@ pub enum QueryMsg {
         start_after: Option<account::Key>,
         limit: Option<u32>,
     },
+    // The balance of an account at the start of block `height`, before any changes made in that
+    // block.
+    #[returns(account::Balance)]
+    BalanceAt { key: account::Key, height: u64 },
+    #[returns(AllAccountsResponse)]
+    AllAccountsAt {
+        height: u64,
+        start_after: Option<account::Key>,
+        limit: Option<u32>,
+    },
     #[returns(AllTransfersResponse)]
     AllTransfers {
         start_after: Option<transfer::Key>,
@@ -150,6 +221,27 @@ pub enum QueryMsg {
     TransferStatus(transfer::Key),
     #[returns(BatchTransferStatusResponse)]
     BatchTransferStatus(Vec<transfer::Key>),
+    #[returns(Reversion)]
+    Reversion(transfer::Key),
+    #[returns(AllReversionsResponse)]
+    AllReversions {
+        start_after: Option<transfer::Key>,
+        limit: Option<u32>,
+    },
+    #[returns(AccountOutflow)]
+    Outflow(account::Key),
+    #[returns(AllOutflowsResponse)]
+    AllOutflows {
+        start_after: Option<account::Key>,
+        limit: Option<u32>,
+    },
+    // Tokens whose balances violate the accountant's core invariant.  At most `limit` tokens are
+    // checked by each query, from 1 up to a maximum of 100.
+    #[returns(CheckInvariantsResponse)]
+    CheckInvariants {
+        start_after: Option<(u16, TokenAddress)>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -173,6 +265,24 @@ pub struct AllModificationsResponse {
     pub modifications: Vec<Modification>,
 }
 
+#[cw_serde]
+pub struct AllReversionsResponse {
+    pub reversions: Vec<Reversion>,
+}
+
+#[cw_serde]
+pub struct AccountOutflow {
+    pub key: account::Key,
+    pub limit: OutflowLimit,
+    // The amount that has flowed out of the account in the window ending at the current block.
+    pub usage: Uint256,
+}
+
+#[cw_serde]
+pub struct AllOutflowsResponse {
+    pub outflows: Vec<AccountOutflow>,
+}
+
 #[cw_serde]
 pub struct ChainRegistrationResponse {
     pub address: Binary,
@@ -196,6 +306,10 @@ pub enum TransferStatus {
         data: transfer::Data,
         digest: Binary,
     },
+    RateLimited {
+        data: transfer::Data,
+        pending: Vec<state::Data>,
+    },
 }
 
 #[cw_serde]
@@ -211,3 +325,11 @@ pub struct TransferDetails {
 pub struct BatchTransferStatusResponse {
     pub details: Vec<TransferDetails>,
 }
+
+#[cw_serde]
+pub struct CheckInvariantsResponse {
+    pub violations: Vec<InvariantViolation>,
+    // The last token that was checked.  Pass this as `start_after` to continue checking from where
+    // this query stopped.  `None` if there are no more tokens.
+    pub last_key: Option<(u16, TokenAddress)>,
+}
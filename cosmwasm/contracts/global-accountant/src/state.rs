@@ -1,12 +1,16 @@
-use accountant::state::transfer;
+use accountant::state::{account, transfer};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Binary;
+use cosmwasm_std::{Binary, Uint256};
 use cw_storage_plus::Map;
 use tinyvec::TinyVec;
 
 pub const PENDING_TRANSFERS: Map<transfer::Key, TinyVec<[Data; 2]>> = Map::new("pending_transfers");
 pub const CHAIN_REGISTRATIONS: Map<u16, Binary> = Map::new("chain_registrations");
 pub const DIGESTS: Map<(u16, Vec<u8>, u64), Binary> = Map::new("digests");
+pub const OUTFLOW_LIMITS: Map<account::Key, OutflowLimit> = Map::new("outflow_limits");
+pub const OUTFLOWS: Map<account::Key, Vec<Outflow>> = Map::new("outflows");
+pub const RATE_LIMITED_TRANSFERS: Map<transfer::Key, RateLimited> =
+    Map::new("rate_limited_transfers");
 
 #[cw_serde]
 pub struct PendingTransfer {
@@ -14,6 +18,19 @@ pub struct PendingTransfer {
     pub data: Vec<Data>,
 }
 
+/// A transfer that reached quorum but is being held back by the outflow limit of its source
+/// account.
+#[cw_serde]
+pub struct RateLimited {
+    pub data: transfer::Data,
+    // The digest of the observation that reached quorum.
+    pub digest: Binary,
+}
+
+/// The number of seconds after which pending observation data that has not reached quorum is
+/// considered stale and may be pruned.
+pub const PENDING_TRANSFER_EXPIRY: u64 = 7 * 24 * 60 * 60;
+
 #[cw_serde]
 #[derive(Default)]
 pub struct Data {
@@ -22,6 +39,11 @@ pub struct Data {
     signatures: u128,
     guardian_set_index: u32,
     emitter_chain: u16,
+    // Seconds since UNIX epoch at which the first signature for this data was received.  Data
+    // saved before this field was added has a value of 0 until it is stamped by
+    // `PrunePendingTransfers`.
+    #[serde(default)]
+    observed_at: u64,
 }
 
 impl Data {
@@ -30,6 +52,7 @@ impl Data {
         tx_hash: Binary,
         emitter_chain: u16,
         guardian_set_index: u32,
+        observed_at: u64,
     ) -> Self {
         Self {
             digest,
@@ -37,6 +60,7 @@ impl Data {
             signatures: 0,
             guardian_set_index,
             emitter_chain,
+            observed_at,
         }
     }
 
@@ -60,6 +84,27 @@ impl Data {
         self.signatures
     }
 
+    pub fn observed_at(&self) -> u64 {
+        self.observed_at
+    }
+
+    /// Sets the time at which this `Data` was first observed to `now` if it was saved before that
+    /// time was recorded.  Returns true if the time was updated.
+    pub fn stamp_observed_at(&mut self, now: u64) -> bool {
+        if self.observed_at == 0 {
+            self.observed_at = now;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns true if at least `PENDING_TRANSFER_EXPIRY` seconds have passed between the time
+    /// this `Data` was first observed and `now`.
+    pub fn is_expired(&self, now: u64) -> bool {
+        now.saturating_sub(self.observed_at) >= PENDING_TRANSFER_EXPIRY
+    }
+
     /// Returns the number of signatures for this `Data`.
     pub fn num_signatures(&self) -> u32 {
         self.signatures.count_ones()
@@ -78,6 +123,62 @@ impl Data {
     }
 }
 
+/// The maximum amount that may flow out of an account within a rolling window.
+#[cw_serde]
+pub struct OutflowLimit {
+    pub limit: Uint256,
+    // The length of the window in seconds.
+    pub window: u64,
+}
+
+/// The number of buckets that the outflows of an account are grouped into for each window.  This
+/// bounds the number of `Outflow` entries stored for an account regardless of how many transfers
+/// it sends.
+pub const OUTFLOW_BUCKETS: u64 = 60;
+
+/// The total amount that flowed out of an account during a single bucket.
+#[cw_serde]
+pub struct Outflow {
+    // The start of the bucket in seconds since UNIX epoch.
+    pub timestamp: u64,
+    pub amount: Uint256,
+}
+
+impl OutflowLimit {
+    /// Returns the length in seconds of each bucket in the window.
+    pub fn bucket_width(&self) -> u64 {
+        (self.window / OUTFLOW_BUCKETS).max(1)
+    }
+
+    /// Adds an outflow of `amount` at `now` to the bucket in `outflows` that covers `now`.
+    pub fn record(&self, outflows: &mut Vec<Outflow>, now: u64, amount: Uint256) {
+        let timestamp = now - now % self.bucket_width();
+        match outflows.last_mut() {
+            Some(o) if o.timestamp == timestamp => o.amount = o.amount.saturating_add(amount),
+            _ => outflows.push(Outflow { timestamp, amount }),
+        }
+    }
+
+    /// Removes every bucket in `outflows` that is outside the window ending at `now` and returns
+    /// the total amount of the remaining buckets.  A bucket stays in the window until its last
+    /// second has left it so the limit may be enforced for up to one bucket longer than `window`
+    /// but never for less.
+    pub fn prune(&self, outflows: &mut Vec<Outflow>, now: u64) -> Uint256 {
+        let age = self.window.saturating_add(self.bucket_width() - 1);
+        outflows.retain(|o| now.saturating_sub(o.timestamp) < age);
+        outflows
+            .iter()
+            .fold(Uint256::zero(), |acc, o| acc.saturating_add(o.amount))
+    }
+
+    /// Returns true if an outflow of `amount` on top of `usage` would exceed this limit.
+    pub fn is_exceeded_by(&self, usage: Uint256, amount: Uint256) -> bool {
+        usage
+            .checked_add(amount)
+            .map_or(true, |total| total > self.limit)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -121,4 +222,97 @@ mod test {
                 .expect_err("successfully added out-of-bounds signature");
         }
     }
+
+    #[test]
+    fn expiry() {
+        let data = Data::new(Binary::default(), Binary::default(), 2, 0, 1000);
+        assert!(!data.is_expired(0));
+        assert!(!data.is_expired(1000));
+        assert!(!data.is_expired(1000 + PENDING_TRANSFER_EXPIRY - 1));
+        assert!(data.is_expired(1000 + PENDING_TRANSFER_EXPIRY));
+    }
+
+    #[test]
+    fn stamp_legacy_data() {
+        let legacy = br#"{
+            "digest": "",
+            "tx_hash": "",
+            "signatures": "3",
+            "guardian_set_index": 0,
+            "emitter_chain": 2
+        }"#;
+
+        let mut data: Data = serde_json_wasm::from_slice(legacy).unwrap();
+        assert_eq!(0, data.observed_at());
+        assert_eq!(2, data.num_signatures());
+
+        assert!(data.stamp_observed_at(1000));
+        assert_eq!(1000, data.observed_at());
+        assert!(!data.is_expired(PENDING_TRANSFER_EXPIRY));
+
+        // Data that already has a time is left alone.
+        assert!(!data.stamp_observed_at(2000));
+        assert_eq!(1000, data.observed_at());
+    }
+
+    #[test]
+    fn prune_outflows() {
+        let limit = OutflowLimit {
+            limit: Uint256::from(1000u128),
+            window: 100,
+        };
+        let mut outflows = (0..5)
+            .map(|i| Outflow {
+                timestamp: i * 50,
+                amount: Uint256::from(100u128),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(Uint256::from(200u128), limit.prune(&mut outflows, 201));
+        assert_eq!(
+            vec![150, 200],
+            outflows.iter().map(|o| o.timestamp).collect::<Vec<_>>()
+        );
+
+        assert_eq!(Uint256::zero(), limit.prune(&mut outflows, 300));
+        assert!(outflows.is_empty());
+    }
+
+    #[test]
+    fn bucket_outflows() {
+        let limit = OutflowLimit {
+            limit: Uint256::from(1000u128),
+            window: 600,
+        };
+        assert_eq!(10, limit.bucket_width());
+
+        let mut outflows = Vec::new();
+        for now in 1000..2000 {
+            limit.prune(&mut outflows, now);
+            limit.record(&mut outflows, now, Uint256::from(1u128));
+            assert!(outflows.len() as u64 <= OUTFLOW_BUCKETS + 1);
+        }
+
+        // Outflows in the same bucket are merged.
+        assert!(outflows.iter().all(|o| o.timestamp % 10 == 0));
+        assert!(outflows.iter().all(|o| o.amount == Uint256::from(10u128)));
+
+        // The bucket starting at 1400 holds outflows up to 1409 and so stays in the window until
+        // 2009.
+        assert_eq!(1400, outflows[0].timestamp);
+        assert_eq!(Uint256::from(600u128), limit.prune(&mut outflows, 2008));
+        assert_eq!(Uint256::from(590u128), limit.prune(&mut outflows, 2009));
+    }
+
+    #[test]
+    fn exceed_limit() {
+        let limit = OutflowLimit {
+            limit: Uint256::from(1000u128),
+            window: 100,
+        };
+
+        assert!(!limit.is_exceeded_by(Uint256::from(400u128), Uint256::from(600u128)));
+        assert!(limit.is_exceeded_by(Uint256::from(400u128), Uint256::from(601u128)));
+        assert!(limit.is_exceeded_by(Uint256::MAX, Uint256::from(1u128)));
+    }
 }
@@ -1,19 +1,21 @@
 #![allow(dead_code)]
 
-use accountant::state::{account, transfer, Kind, Modification};
+use accountant::state::{account, transfer, Kind, Modification, Reversion, TokenAddress};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
     testing::{MockApi, MockStorage},
-    Addr, Binary, Coin, Empty, StdError, StdResult, Uint128,
+    Addr, Binary, Coin, Empty, StdError, StdResult, Uint128, Uint256,
 };
 use cw_multi_test::{
     App, AppBuilder, AppResponse, BankKeeper, ContractWrapper, Executor, WasmKeeper,
 };
 use global_accountant::{
     msg::{
-        AllAccountsResponse, AllModificationsResponse, AllPendingTransfersResponse,
-        AllTransfersResponse, BatchTransferStatusResponse, ChainRegistrationResponse, ExecuteMsg,
-        MissingObservationsResponse, QueryMsg, TransferStatus, SUBMITTED_OBSERVATIONS_PREFIX,
+        AccountOutflow, AllAccountsResponse, AllModificationsResponse, AllOutflowsResponse,
+        AllPendingTransfersResponse, AllReversionsResponse, AllTransfersResponse,
+        BatchTransferStatusResponse, ChainRegistrationResponse, CheckInvariantsResponse,
+        ExecuteMsg, MissingObservationsResponse, QueryMsg, TransferStatus,
+        SUBMITTED_OBSERVATIONS_PREFIX,
     },
     state,
 };
@@ -135,6 +137,114 @@ impl Contract {
         })
     }
 
+    pub fn revert_transfer(
+        &mut self,
+        reversion: Reversion,
+        wh: &fake::WormholeKeeper,
+    ) -> anyhow::Result<AppResponse> {
+        let Reversion { key, reason } = reversion;
+        let body = Body {
+            timestamp: self.sequence as u32,
+            nonce: self.sequence as u32,
+            emitter_chain: Chain::Solana,
+            emitter_address: wormhole_sdk::GOVERNANCE_EMITTER,
+            sequence: self.sequence,
+            consistency_level: 0,
+            payload: accountant_module::GovernancePacket {
+                chain: Chain::Wormchain,
+                action: accountant_module::Action::RevertTransfer {
+                    emitter_chain: key.emitter_chain(),
+                    emitter_address: Address(**key.emitter_address()),
+                    sequence: key.sequence(),
+                    reason: reason.into(),
+                },
+            },
+        };
+
+        self.sequence += 1;
+
+        let (_, data) = sign_vaa_body(wh, body);
+
+        self.app.execute_contract(
+            Addr::unchecked(USER),
+            self.addr(),
+            &ExecuteMsg::SubmitVaas { vaas: vec![data] },
+            &[],
+        )
+    }
+
+    pub fn set_outflow_limit(
+        &mut self,
+        key: account::Key,
+        limit: Uint256,
+        window: u64,
+        wh: &fake::WormholeKeeper,
+    ) -> anyhow::Result<AppResponse> {
+        let body = Body {
+            timestamp: self.sequence as u32,
+            nonce: self.sequence as u32,
+            emitter_chain: Chain::Solana,
+            emitter_address: wormhole_sdk::GOVERNANCE_EMITTER,
+            sequence: self.sequence,
+            consistency_level: 0,
+            payload: accountant_module::GovernancePacket {
+                chain: Chain::Wormchain,
+                action: accountant_module::Action::SetOutflowLimit {
+                    chain_id: key.chain_id(),
+                    token_chain: key.token_chain(),
+                    token_address: Address(**key.token_address()),
+                    limit: Amount(limit.to_be_bytes()),
+                    window,
+                },
+            },
+        };
+
+        self.sequence += 1;
+
+        let (_, data) = sign_vaa_body(wh, body);
+
+        self.app.execute_contract(
+            Addr::unchecked(USER),
+            self.addr(),
+            &ExecuteMsg::SubmitVaas { vaas: vec![data] },
+            &[],
+        )
+    }
+
+    pub fn release_transfer(
+        &mut self,
+        key: transfer::Key,
+        wh: &fake::WormholeKeeper,
+    ) -> anyhow::Result<AppResponse> {
+        let body = Body {
+            timestamp: self.sequence as u32,
+            nonce: self.sequence as u32,
+            emitter_chain: Chain::Solana,
+            emitter_address: wormhole_sdk::GOVERNANCE_EMITTER,
+            sequence: self.sequence,
+            consistency_level: 0,
+            payload: accountant_module::GovernancePacket {
+                chain: Chain::Wormchain,
+                action: accountant_module::Action::ReleaseTransfer {
+                    emitter_chain: key.emitter_chain(),
+                    emitter_address: Address(**key.emitter_address()),
+                    sequence: key.sequence(),
+                },
+            },
+        };
+
+        self.sequence += 1;
+
+        let (_, data) = sign_vaa_body(wh, body);
+
+        self.app.execute_contract(
+            Addr::unchecked(USER),
+            self.addr(),
+            &ExecuteMsg::SubmitVaas { vaas: vec![data] },
+            &[],
+        )
+    }
+
     pub fn submit_vaas(&mut self, vaas: Vec<Binary>) -> anyhow::Result<AppResponse> {
         self.app.execute_contract(
             Addr::unchecked(ADMIN),
@@ -144,6 +254,19 @@ impl Contract {
         )
     }
 
+    pub fn prune_pending_transfers(
+        &mut self,
+        start_after: Option<transfer::Key>,
+        limit: Option<u32>,
+    ) -> anyhow::Result<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(USER),
+            self.addr(),
+            &ExecuteMsg::PrunePendingTransfers { start_after, limit },
+            &[],
+        )
+    }
+
     pub fn query_balance(&self, key: account::Key) -> StdResult<account::Balance> {
         self.app
             .wrap()
@@ -160,6 +283,39 @@ impl Contract {
             .query_wasm_smart(self.addr(), &QueryMsg::AllAccounts { start_after, limit })
     }
 
+    pub fn query_balance_at(&self, key: account::Key, height: u64) -> StdResult<account::Balance> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::BalanceAt { key, height })
+    }
+
+    pub fn query_all_accounts_at(
+        &self,
+        height: u64,
+        start_after: Option<account::Key>,
+        limit: Option<u32>,
+    ) -> StdResult<AllAccountsResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::AllAccountsAt {
+                height,
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    pub fn check_invariants(
+        &self,
+        start_after: Option<(u16, TokenAddress)>,
+        limit: Option<u32>,
+    ) -> StdResult<CheckInvariantsResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::CheckInvariants { start_after, limit },
+        )
+    }
+
     pub fn query_transfer_status(&self, key: transfer::Key) -> StdResult<TransferStatus> {
         self.app
             .wrap()
@@ -235,6 +391,38 @@ impl Contract {
         )
     }
 
+    pub fn query_reversion(&self, key: transfer::Key) -> StdResult<Reversion> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::Reversion(key))
+    }
+
+    pub fn query_all_reversions(
+        &self,
+        start_after: Option<transfer::Key>,
+        limit: Option<u32>,
+    ) -> StdResult<AllReversionsResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::AllReversions { start_after, limit })
+    }
+
+    pub fn query_outflow(&self, key: account::Key) -> StdResult<AccountOutflow> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::Outflow(key))
+    }
+
+    pub fn query_all_outflows(
+        &self,
+        start_after: Option<account::Key>,
+        limit: Option<u32>,
+    ) -> StdResult<AllOutflowsResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::AllOutflows { start_after, limit })
+    }
+
     pub fn query_chain_registration(&self, chain: u16) -> StdResult<ChainRegistrationResponse> {
         self.app
             .wrap()
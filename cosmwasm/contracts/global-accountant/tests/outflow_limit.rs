@@ -0,0 +1,355 @@
+mod helpers;
+
+use accountant::state::{account, transfer, TokenAddress};
+use cosmwasm_std::{from_binary, to_binary, Binary, Event, Uint256};
+use cw_multi_test::AppResponse;
+use global_accountant::{
+    msg::{
+        Observation, ObservationStatus, RateLimitedTransfer, SubmitObservationResponse,
+        TransferStatus,
+    },
+    state::{OUTFLOW_BUCKETS, PENDING_TRANSFER_EXPIRY},
+};
+use helpers::*;
+use wormhole_bindings::fake;
+use wormhole_sdk::{token::Message, vaa::Body, Address, Amount};
+
+const EMITTER_CHAIN: u16 = 2;
+const WINDOW: u64 = 3600;
+
+fn source_account() -> account::Key {
+    account::Key::new(EMITTER_CHAIN, EMITTER_CHAIN, [0x7c; 32].into())
+}
+
+fn transfer(amount: u128) -> Message {
+    Message::Transfer {
+        amount: Amount(Uint256::from(amount).to_be_bytes()),
+        token_address: Address([0x7c; 32]),
+        token_chain: EMITTER_CHAIN.into(),
+        recipient: Address([0x3a; 32]),
+        recipient_chain: 3.into(),
+        fee: Amount([0; 32]),
+    }
+}
+
+fn observation(sequence: u64, amount: u128) -> Observation {
+    Observation {
+        tx_hash: vec![sequence as u8; 20].into(),
+        timestamp: sequence as u32,
+        nonce: sequence as u32,
+        emitter_chain: EMITTER_CHAIN,
+        emitter_address: [EMITTER_CHAIN as u8; 32],
+        sequence,
+        consistency_level: 0,
+        payload: serde_wormhole::to_vec(&transfer(amount))
+            .map(Binary::from)
+            .unwrap(),
+    }
+}
+
+fn key(o: &Observation) -> transfer::Key {
+    transfer::Key::new(o.emitter_chain, o.emitter_address.into(), o.sequence)
+}
+
+// Submits signatures for `o` from a quorum of guardians and returns the final status.
+fn submit_with_quorum(
+    wh: &fake::WormholeKeeper,
+    contract: &mut Contract,
+    o: &Observation,
+) -> (ObservationStatus, AppResponse) {
+    let index = wh.guardian_set_index();
+    let obs = to_binary(&vec![o.clone()]).unwrap();
+    let quorum = wh
+        .calculate_quorum(index, contract.app().block_info().height)
+        .unwrap() as usize;
+
+    let mut resp = None;
+    for s in sign_observations(wh, &obs).into_iter().take(quorum) {
+        resp = Some(contract.submit_observations(obs.clone(), index, s).unwrap());
+    }
+
+    let resp = resp.unwrap();
+    let status = from_binary::<Vec<SubmitObservationResponse>>(resp.data.as_ref().unwrap())
+        .unwrap()
+        .remove(0)
+        .status;
+
+    (status, resp)
+}
+
+fn set_up() -> (fake::WormholeKeeper, Contract) {
+    let (wh, mut contract) = proper_instantiate();
+    register_emitters(&wh, &mut contract, 4);
+    contract
+        .set_outflow_limit(source_account(), Uint256::from(1000u128), WINDOW, &wh)
+        .unwrap();
+
+    (wh, contract)
+}
+
+#[test]
+fn within_limit() {
+    let (wh, mut contract) = set_up();
+
+    let o = observation(1, 600);
+    let (status, _) = submit_with_quorum(&wh, &mut contract, &o);
+    assert!(matches!(status, ObservationStatus::Committed));
+    contract.query_transfer(key(&o)).unwrap();
+
+    let outflow = contract.query_outflow(source_account()).unwrap();
+    assert_eq!(Uint256::from(1000u128), outflow.limit.limit);
+    assert_eq!(WINDOW, outflow.limit.window);
+    assert_eq!(Uint256::from(600u128), outflow.usage);
+
+    let o = observation(2, 400);
+    let (status, _) = submit_with_quorum(&wh, &mut contract, &o);
+    assert!(matches!(status, ObservationStatus::Committed));
+
+    let outflow = contract.query_outflow(source_account()).unwrap();
+    assert_eq!(Uint256::from(1000u128), outflow.usage);
+}
+
+#[test]
+fn exceed_limit() {
+    let (wh, mut contract) = set_up();
+
+    let (status, _) = submit_with_quorum(&wh, &mut contract, &observation(1, 600));
+    assert!(matches!(status, ObservationStatus::Committed));
+
+    let o = observation(2, 600);
+    let (status, resp) = submit_with_quorum(&wh, &mut contract, &o);
+    assert!(matches!(status, ObservationStatus::RateLimited));
+
+    let data = transfer::Data {
+        amount: Uint256::from(600u128),
+        token_chain: EMITTER_CHAIN,
+        token_address: TokenAddress::new([0x7c; 32]),
+        recipient_chain: 3,
+    };
+    let expected = RateLimitedTransfer {
+        key: key(&o),
+        data: data.clone(),
+        usage: Uint256::from(600u128),
+    };
+    resp.assert_event(
+        &Event::new("wasm-RateLimitedTransfer")
+            .add_attribute("key", serde_json_wasm::to_string(&expected.key).unwrap())
+            .add_attribute("data", serde_json_wasm::to_string(&expected.data).unwrap())
+            .add_attribute(
+                "usage",
+                serde_json_wasm::to_string(&expected.usage).unwrap(),
+            ),
+    );
+
+    // The transfer should not have been committed and the signatures should still be around.
+    contract
+        .query_transfer(key(&o))
+        .expect_err("committed transfer that exceeds the outflow limit");
+    match contract.query_transfer_status(key(&o)).unwrap() {
+        TransferStatus::RateLimited { data: d, pending } => {
+            assert_eq!(data, d);
+            assert_eq!(&o.digest().unwrap(), pending[0].digest());
+        }
+        s => panic!("unexpected transfer status: {s:?}"),
+    }
+
+    let outflow = contract.query_outflow(source_account()).unwrap();
+    assert_eq!(Uint256::from(600u128), outflow.usage);
+}
+
+#[test]
+fn commit_after_window() {
+    let (wh, mut contract) = set_up();
+
+    submit_with_quorum(&wh, &mut contract, &observation(1, 600));
+
+    let o = observation(2, 600);
+    let (status, _) = submit_with_quorum(&wh, &mut contract, &o);
+    assert!(matches!(status, ObservationStatus::RateLimited));
+
+    // Outflows are grouped into buckets and a bucket only leaves the window once every outflow in
+    // it has.
+    contract
+        .app_mut()
+        .update_block(|b| b.time = b.time.plus_seconds(WINDOW + WINDOW / OUTFLOW_BUCKETS));
+
+    // Re-submitting the observation should commit it now that the earlier outflow has left the
+    // window.
+    let (status, _) = submit_with_quorum(&wh, &mut contract, &o);
+    assert!(matches!(status, ObservationStatus::Committed));
+    contract.query_transfer(key(&o)).unwrap();
+    contract
+        .query_pending_transfer(key(&o))
+        .expect_err("found pending transfer for committed observation");
+    assert!(matches!(
+        contract.query_transfer_status(key(&o)).unwrap(),
+        TransferStatus::Committed { .. }
+    ));
+
+    let outflow = contract.query_outflow(source_account()).unwrap();
+    assert_eq!(Uint256::from(600u128), outflow.usage);
+}
+
+#[test]
+fn rate_limited_not_pruned() {
+    let (wh, mut contract) = set_up();
+
+    let o = observation(1, 1500);
+    let (status, _) = submit_with_quorum(&wh, &mut contract, &o);
+    assert!(matches!(status, ObservationStatus::RateLimited));
+
+    contract
+        .app_mut()
+        .update_block(|b| b.time = b.time.plus_seconds(PENDING_TRANSFER_EXPIRY));
+    contract.prune_pending_transfers(None, None).unwrap();
+
+    assert!(matches!(
+        contract.query_transfer_status(key(&o)).unwrap(),
+        TransferStatus::RateLimited { .. }
+    ));
+}
+
+#[test]
+fn release_oversized_transfer() {
+    let (wh, mut contract) = set_up();
+
+    // A transfer larger than the limit can never fit in the window so it must be released by
+    // governance.
+    let o = observation(1, 1500);
+    let (status, _) = submit_with_quorum(&wh, &mut contract, &o);
+    assert!(matches!(status, ObservationStatus::RateLimited));
+
+    contract
+        .app_mut()
+        .update_block(|b| b.time = b.time.plus_seconds(2 * WINDOW));
+    let (status, _) = submit_with_quorum(&wh, &mut contract, &o);
+    assert!(matches!(status, ObservationStatus::RateLimited));
+
+    let resp = contract.release_transfer(key(&o), &wh).unwrap();
+    resp.assert_event(
+        &Event::new("wasm-Transfer")
+            .add_attribute("key", serde_json_wasm::to_string(&key(&o)).unwrap())
+            .add_attribute("action", "release_transfer"),
+    );
+
+    let tx = contract.query_transfer(key(&o)).unwrap();
+    assert_eq!(Uint256::from(1500u128), tx.data.amount);
+    assert_eq!(o.digest().unwrap(), tx.digest);
+    contract
+        .query_pending_transfer(key(&o))
+        .expect_err("found pending transfer for released observation");
+
+    let outflow = contract.query_outflow(source_account()).unwrap();
+    assert_eq!(Uint256::from(1500u128), outflow.usage);
+
+    // Late signatures for the released observation are treated like any other committed one.
+    let (status, _) = submit_with_quorum(&wh, &mut contract, &o);
+    assert!(matches!(status, ObservationStatus::Committed));
+}
+
+#[test]
+fn release_unknown_transfer() {
+    let (wh, mut contract) = set_up();
+
+    let o = observation(1, 600);
+    contract
+        .release_transfer(key(&o), &wh)
+        .expect_err("released a transfer that was never observed");
+
+    let (status, _) = submit_with_quorum(&wh, &mut contract, &o);
+    assert!(matches!(status, ObservationStatus::Committed));
+    contract
+        .release_transfer(key(&o), &wh)
+        .expect_err("released a transfer that was not rate limited");
+}
+
+#[test]
+fn vaas_are_not_limited() {
+    let (wh, mut contract) = set_up();
+
+    let body: Body<Message> = Body {
+        timestamp: 1,
+        nonce: 1,
+        emitter_chain: EMITTER_CHAIN.into(),
+        emitter_address: Address([EMITTER_CHAIN as u8; 32]),
+        sequence: 1,
+        consistency_level: 0,
+        payload: transfer(1500),
+    };
+    let (_, data) = sign_vaa_body(&wh, body);
+    contract.submit_vaas(vec![data]).unwrap();
+
+    // The VAA still counts towards the limit.
+    let outflow = contract.query_outflow(source_account()).unwrap();
+    assert_eq!(Uint256::from(1500u128), outflow.usage);
+
+    let (status, _) = submit_with_quorum(&wh, &mut contract, &observation(2, 1));
+    assert!(matches!(status, ObservationStatus::RateLimited));
+}
+
+#[test]
+fn remove_limit() {
+    let (wh, mut contract) = set_up();
+
+    let o = observation(1, 1500);
+    let (status, _) = submit_with_quorum(&wh, &mut contract, &o);
+    assert!(matches!(status, ObservationStatus::RateLimited));
+
+    let resp = contract
+        .set_outflow_limit(source_account(), Uint256::zero(), 0, &wh)
+        .unwrap();
+    resp.assert_event(
+        &Event::new("wasm-OutflowLimitUpdate")
+            .add_attribute(
+                "key",
+                serde_json_wasm::to_string(&source_account()).unwrap(),
+            )
+            .add_attribute(
+                "limit",
+                serde_json_wasm::to_string(&Uint256::zero()).unwrap(),
+            )
+            .add_attribute("window", "0"),
+    );
+
+    contract
+        .query_outflow(source_account())
+        .expect_err("found outflow limit after removing it");
+    assert!(contract
+        .query_all_outflows(None, None)
+        .unwrap()
+        .outflows
+        .is_empty());
+
+    let (status, _) = submit_with_quorum(&wh, &mut contract, &o);
+    assert!(matches!(status, ObservationStatus::Committed));
+}
+
+#[test]
+fn query_all_outflows() {
+    let (wh, mut contract) = proper_instantiate();
+
+    let keys = (0..4)
+        .map(|i| account::Key::new(i, i, [i as u8; 32].into()))
+        .collect::<Vec<_>>();
+    for (i, k) in keys.iter().enumerate() {
+        contract
+            .set_outflow_limit(k.clone(), Uint256::from(i as u128 + 1), WINDOW, &wh)
+            .unwrap();
+    }
+
+    let resp = contract.query_all_outflows(None, None).unwrap();
+    assert_eq!(
+        keys,
+        resp.outflows
+            .iter()
+            .map(|o| o.key.clone())
+            .collect::<Vec<_>>()
+    );
+    assert!(resp.outflows.iter().all(|o| o.usage.is_zero()));
+
+    let resp = contract
+        .query_all_outflows(Some(keys[1].clone()), Some(1))
+        .unwrap();
+    assert_eq!(1, resp.outflows.len());
+    assert_eq!(keys[2], resp.outflows[0].key);
+}
@@ -0,0 +1,184 @@
+mod helpers;
+
+use accountant::state::transfer;
+use cosmwasm_std::{from_binary, to_binary, Binary, Event, Uint256};
+use cw_multi_test::AppResponse;
+use global_accountant::{
+    msg::{
+        Observation, ObservationStatus, PrunePendingTransfersResponse, PrunedPendingTransfer,
+        SubmitObservationResponse,
+    },
+    state::PENDING_TRANSFER_EXPIRY,
+};
+use helpers::*;
+use wormhole_bindings::fake;
+use wormhole_sdk::{token::Message, Address, Amount};
+
+const EMITTER_CHAIN: u16 = 2;
+
+fn observation(sequence: u64) -> Observation {
+    let tx = Message::Transfer {
+        amount: Amount(Uint256::from(500u128).to_be_bytes()),
+        token_address: Address([0x7c; 32]),
+        token_chain: EMITTER_CHAIN.into(),
+        recipient: Address([0x3a; 32]),
+        recipient_chain: 3.into(),
+        fee: Amount([0; 32]),
+    };
+
+    Observation {
+        tx_hash: vec![sequence as u8; 20].into(),
+        timestamp: sequence as u32,
+        nonce: sequence as u32,
+        emitter_chain: EMITTER_CHAIN,
+        emitter_address: [EMITTER_CHAIN as u8; 32],
+        sequence,
+        consistency_level: 0,
+        payload: serde_wormhole::to_vec(&tx).map(Binary::from).unwrap(),
+    }
+}
+
+fn key(o: &Observation) -> transfer::Key {
+    transfer::Key::new(o.emitter_chain, o.emitter_address.into(), o.sequence)
+}
+
+// Submits a single signature for each observation so that they all remain pending.
+fn submit_pending(
+    wh: &fake::WormholeKeeper,
+    contract: &mut Contract,
+    observations: &[Observation],
+) {
+    let index = wh.guardian_set_index();
+    let obs = to_binary(&observations).unwrap();
+    let s = sign_observations(wh, &obs).remove(0);
+    contract.submit_observations(obs, index, s).unwrap();
+}
+
+fn advance_time(contract: &mut Contract, seconds: u64) {
+    contract.app_mut().update_block(|b| {
+        b.height += 1;
+        b.time = b.time.plus_seconds(seconds);
+    });
+}
+
+fn pruned(resp: &AppResponse) -> Vec<PrunedPendingTransfer> {
+    resp.events
+        .iter()
+        .filter(|e| e.ty == "wasm-PrunedPendingTransfer")
+        .map(|e| cw_transcode::from_event(e).unwrap())
+        .collect()
+}
+
+fn last_key(resp: &AppResponse) -> Option<transfer::Key> {
+    from_binary::<PrunePendingTransfersResponse>(resp.data.as_ref().unwrap())
+        .unwrap()
+        .last_key
+}
+
+#[test]
+fn prune_expired() {
+    let (wh, mut contract) = proper_instantiate();
+    register_emitters(&wh, &mut contract, 4);
+
+    let old = (1..=3).map(observation).collect::<Vec<_>>();
+    submit_pending(&wh, &mut contract, &old);
+
+    advance_time(&mut contract, PENDING_TRANSFER_EXPIRY / 2);
+    let new = observation(4);
+    submit_pending(&wh, &mut contract, &[new.clone()]);
+
+    // Nothing has expired yet.
+    let resp = contract.prune_pending_transfers(None, None).unwrap();
+    assert!(pruned(&resp).is_empty());
+    assert_eq!(None, last_key(&resp));
+    assert_eq!(
+        4,
+        contract
+            .query_all_pending_transfers(None, None)
+            .unwrap()
+            .pending
+            .len()
+    );
+
+    advance_time(&mut contract, PENDING_TRANSFER_EXPIRY / 2);
+    let resp = contract.prune_pending_transfers(None, None).unwrap();
+
+    let events = pruned(&resp);
+    assert_eq!(old.len(), events.len());
+    for (o, evt) in old.iter().zip(&events) {
+        assert_eq!(key(o), evt.key);
+        assert_eq!(1, evt.data.len());
+        assert_eq!(&o.digest().unwrap(), evt.data[0].digest());
+
+        contract
+            .query_pending_transfer(key(o))
+            .expect_err("found pending transfer after it was pruned");
+    }
+    resp.assert_event(
+        &Event::new("wasm-PrunedPendingTransfer")
+            .add_attribute("key", serde_json_wasm::to_string(&key(&old[0])).unwrap()),
+    );
+
+    assert_eq!(1, contract.query_pending_transfer(key(&new)).unwrap().len());
+}
+
+#[test]
+fn prune_in_batches() {
+    let (wh, mut contract) = proper_instantiate();
+    register_emitters(&wh, &mut contract, 4);
+
+    let observations = (1..=5).map(observation).collect::<Vec<_>>();
+    submit_pending(&wh, &mut contract, &observations);
+    advance_time(&mut contract, PENDING_TRANSFER_EXPIRY);
+
+    let resp = contract.prune_pending_transfers(None, Some(2)).unwrap();
+    assert_eq!(2, pruned(&resp).len());
+    assert_eq!(Some(key(&observations[1])), last_key(&resp));
+
+    let resp = contract
+        .prune_pending_transfers(last_key(&resp), Some(2))
+        .unwrap();
+    assert_eq!(2, pruned(&resp).len());
+    assert_eq!(Some(key(&observations[3])), last_key(&resp));
+
+    let resp = contract
+        .prune_pending_transfers(last_key(&resp), Some(2))
+        .unwrap();
+    assert_eq!(1, pruned(&resp).len());
+    assert_eq!(None, last_key(&resp));
+
+    assert!(contract
+        .query_all_pending_transfers(None, None)
+        .unwrap()
+        .pending
+        .is_empty());
+}
+
+#[test]
+fn resubmit_after_prune() {
+    let (wh, mut contract) = proper_instantiate();
+    register_emitters(&wh, &mut contract, 4);
+
+    let o = observation(1);
+    submit_pending(&wh, &mut contract, &[o.clone()]);
+    advance_time(&mut contract, PENDING_TRANSFER_EXPIRY);
+    contract.prune_pending_transfers(None, None).unwrap();
+
+    // The guardians can still commit the transfer if they observe it again.
+    let index = wh.guardian_set_index();
+    let obs = to_binary(&vec![o.clone()]).unwrap();
+    let quorum = wh
+        .calculate_quorum(index, contract.app().block_info().height)
+        .unwrap() as usize;
+    let mut status = None;
+    for s in sign_observations(&wh, &obs).into_iter().take(quorum) {
+        let resp = contract.submit_observations(obs.clone(), index, s).unwrap();
+        status = from_binary::<Vec<SubmitObservationResponse>>(resp.data.as_ref().unwrap())
+            .unwrap()
+            .pop()
+            .map(|r| r.status);
+    }
+
+    assert!(matches!(status, Some(ObservationStatus::Committed)));
+    contract.query_transfer(key(&o)).unwrap();
+}
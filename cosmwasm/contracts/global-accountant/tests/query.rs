@@ -209,6 +209,111 @@ fn all_balances_sub_range() {
     }
 }
 
+#[test]
+fn balances_at_height() {
+    let count = 2;
+    let (wh, mut contract) = proper_instantiate();
+    let created = contract.app().block_info().height;
+    create_accounts(&wh, &mut contract, count);
+
+    contract.app_mut().update_block(|b| b.height += 1);
+    let modified = contract.app().block_info().height;
+
+    let key = account::Key::new(0, 1, [0; 32].into());
+    let m = Modification {
+        sequence: (count * count + 1) as u64,
+        chain_id: key.chain_id(),
+        token_chain: key.token_chain(),
+        token_address: *key.token_address(),
+        kind: Kind::Add,
+        amount: Uint256::from(5u128),
+        reason: "balances_at_height".into(),
+    };
+    contract.modify_balance(m, &wh).unwrap();
+
+    contract.app_mut().update_block(|b| b.height += 1);
+    let latest = contract.app().block_info().height;
+
+    // Balances are recorded as of the start of the block so the accounts did not exist yet.
+    let err = contract
+        .query_balance_at(key.clone(), created)
+        .expect_err("successfully queried account before it was created");
+    assert!(err.to_string().to_lowercase().contains("not found"));
+    assert!(contract
+        .query_all_accounts_at(created, None, None)
+        .unwrap()
+        .accounts
+        .is_empty());
+
+    assert_eq!(
+        Balance::new(Uint256::from(1u128)),
+        contract.query_balance_at(key.clone(), modified).unwrap()
+    );
+    assert_eq!(
+        Balance::new(Uint256::from(6u128)),
+        contract.query_balance_at(key.clone(), latest).unwrap()
+    );
+    assert_eq!(
+        contract.query_balance(key.clone()).unwrap(),
+        contract.query_balance_at(key.clone(), latest).unwrap()
+    );
+
+    let resp = contract
+        .query_all_accounts_at(modified, None, None)
+        .unwrap();
+    assert_eq!(count * count, resp.accounts.len());
+    for acc in resp.accounts {
+        assert_eq!(Uint256::from(acc.key.token_chain() as u128), *acc.balance);
+    }
+
+    let resp = contract
+        .query_all_accounts_at(latest, Some(key.clone()), Some(1))
+        .unwrap();
+    assert_eq!(1, resp.accounts.len());
+    assert_eq!(
+        account::Key::new(1, 0, [1; 32].into()),
+        resp.accounts[0].key
+    );
+}
+
+#[test]
+fn invariants() {
+    let count = 3;
+    let (wh, mut contract) = proper_instantiate();
+    register_emitters(&wh, &mut contract, count);
+    create_transfers(&wh, &mut contract, count);
+
+    let resp = contract.check_invariants(None, None).unwrap();
+    assert!(resp.violations.is_empty());
+    assert_eq!(None, resp.last_key);
+
+    // Modifications are taken into account so they don't violate the invariant either.
+    create_accounts(&wh, &mut contract, count);
+    let resp = contract.check_invariants(None, None).unwrap();
+    assert!(resp.violations.is_empty());
+
+    // Each query only checks up to `limit` tokens and returns the last one that it checked.
+    let first = contract
+        .check_invariants(None, Some(1))
+        .unwrap()
+        .last_key
+        .unwrap();
+    let second = contract
+        .check_invariants(Some(first), Some(1))
+        .unwrap()
+        .last_key
+        .unwrap();
+    assert!(first < second);
+
+    // A limit of 0 still checks one token instead of reporting that there are none left.
+    let resp = contract.check_invariants(None, Some(0)).unwrap();
+    assert_eq!(Some(first), resp.last_key);
+
+    let resp = contract.check_invariants(Some(second), None).unwrap();
+    assert!(resp.violations.is_empty());
+    assert_eq!(None, resp.last_key);
+}
+
 #[test]
 fn transfer_data() {
     let count = 2;
@@ -0,0 +1,173 @@
+mod helpers;
+
+use accountant::state::{account, transfer, Kind, Modification, Reversion, TokenAddress};
+use cosmwasm_std::{Event, Uint256};
+use helpers::*;
+use wormhole_bindings::fake;
+use wormhole_sdk::{token::Message, vaa::Body, Address, Amount};
+
+const EMITTER_CHAIN: u16 = 2;
+const RECIPIENT_CHAIN: u16 = 3;
+
+fn submit_transfer(
+    wh: &fake::WormholeKeeper,
+    contract: &mut Contract,
+    sequence: u64,
+    amount: Uint256,
+) -> transfer::Key {
+    let emitter_address = [EMITTER_CHAIN as u8; 32];
+    let body: Body<Message> = Body {
+        timestamp: sequence as u32,
+        nonce: sequence as u32,
+        emitter_chain: EMITTER_CHAIN.into(),
+        emitter_address: Address(emitter_address),
+        sequence,
+        consistency_level: 0,
+        payload: Message::Transfer {
+            amount: Amount(amount.to_be_bytes()),
+            token_address: Address([0x7c; 32]),
+            token_chain: EMITTER_CHAIN.into(),
+            recipient: Address([0x3a; 32]),
+            recipient_chain: RECIPIENT_CHAIN.into(),
+            fee: Amount([0; 32]),
+        },
+    };
+
+    let (_, data) = sign_vaa_body(wh, body);
+    contract.submit_vaas(vec![data]).unwrap();
+
+    transfer::Key::new(EMITTER_CHAIN, TokenAddress::new(emitter_address), sequence)
+}
+
+fn balance(contract: &Contract, chain_id: u16) -> Uint256 {
+    let key = account::Key::new(chain_id, EMITTER_CHAIN, [0x7c; 32].into());
+    contract
+        .query_balance(key)
+        .map(|b| *b)
+        .unwrap_or_else(|_| Uint256::zero())
+}
+
+#[test]
+fn simple_revert() {
+    let (wh, mut contract) = proper_instantiate();
+    register_emitters(&wh, &mut contract, 4);
+
+    let amount = Uint256::from(500u128);
+    let key = submit_transfer(&wh, &mut contract, 7, amount);
+    assert_eq!(amount, balance(&contract, EMITTER_CHAIN));
+    assert_eq!(amount, balance(&contract, RECIPIENT_CHAIN));
+
+    let r = Reversion {
+        key: key.clone(),
+        reason: "reorg".into(),
+    };
+    let resp = contract.revert_transfer(r.clone(), &wh).unwrap();
+
+    let evt = Event::new("wasm-Reversion")
+        .add_attribute("key", serde_json_wasm::to_string(&r.key).unwrap())
+        .add_attribute("reason", serde_json_wasm::to_string(&r.reason).unwrap());
+    resp.assert_event(&evt);
+
+    let evt = resp
+        .events
+        .iter()
+        .find(|e| e.ty == "wasm-Reversion")
+        .unwrap();
+    assert_eq!(r, cw_transcode::from_event(evt).unwrap());
+
+    assert_eq!(Uint256::zero(), balance(&contract, EMITTER_CHAIN));
+    assert_eq!(Uint256::zero(), balance(&contract, RECIPIENT_CHAIN));
+
+    assert_eq!(r, contract.query_reversion(key).unwrap());
+    assert_eq!(
+        vec![r],
+        contract
+            .query_all_reversions(None, None)
+            .unwrap()
+            .reversions
+    );
+}
+
+#[test]
+fn duplicate_revert() {
+    let (wh, mut contract) = proper_instantiate();
+    register_emitters(&wh, &mut contract, 4);
+
+    let key = submit_transfer(&wh, &mut contract, 7, Uint256::from(500u128));
+
+    let r = Reversion {
+        key,
+        reason: "reorg".into(),
+    };
+    contract.revert_transfer(r.clone(), &wh).unwrap();
+
+    let err = contract
+        .revert_transfer(r, &wh)
+        .expect_err("successfully reverted the same transfer twice");
+    assert_eq!(
+        "transfer already reverted",
+        err.root_cause().to_string().to_lowercase()
+    );
+}
+
+#[test]
+fn revert_missing_transfer() {
+    let (wh, mut contract) = proper_instantiate();
+    register_emitters(&wh, &mut contract, 4);
+
+    let r = Reversion {
+        key: transfer::Key::new(EMITTER_CHAIN, [EMITTER_CHAIN as u8; 32].into(), 7),
+        reason: "reorg".into(),
+    };
+
+    let err = contract
+        .revert_transfer(r.clone(), &wh)
+        .expect_err("successfully reverted a missing transfer");
+    assert_eq!(
+        "cannot revert a transfer that was never committed",
+        err.root_cause().to_string().to_lowercase()
+    );
+    contract
+        .query_reversion(r.key)
+        .expect_err("found reversion for a failed revert");
+}
+
+#[test]
+fn revert_after_outflow() {
+    let (wh, mut contract) = proper_instantiate();
+    register_emitters(&wh, &mut contract, 4);
+
+    let amount = Uint256::from(500u128);
+    let key = submit_transfer(&wh, &mut contract, 7, amount);
+
+    // Burn some of the wrapped tokens so that the destination can no longer cover the reversion.
+    let m = Modification {
+        sequence: 0,
+        chain_id: RECIPIENT_CHAIN,
+        token_chain: EMITTER_CHAIN,
+        token_address: [0x7c; 32].into(),
+        kind: Kind::Sub,
+        amount: Uint256::from(1u128),
+        reason: "burn".into(),
+    };
+    contract.modify_balance(m, &wh).unwrap();
+
+    let r = Reversion {
+        key,
+        reason: "reorg".into(),
+    };
+    let err = contract
+        .revert_transfer(r, &wh)
+        .expect_err("successfully reverted transfer with insufficient destination balance");
+    assert_eq!(
+        "insufficient balance in destination account",
+        err.root_cause().to_string().to_lowercase()
+    );
+
+    assert_eq!(amount, balance(&contract, EMITTER_CHAIN));
+    assert!(contract
+        .query_all_reversions(None, None)
+        .unwrap()
+        .reversions
+        .is_empty());
+}
@@ -0,0 +1,200 @@
+mod helpers;
+
+use accountant::state::{account, transfer, Kind, Modification, Reversion, TokenAddress, Transfer};
+use cosmwasm_std::{to_binary, Binary, Uint256};
+use cw_multi_test::AppResponse;
+use global_accountant::{
+    msg::{Observation, OutflowLimitUpdate, PrunedPendingTransfer, RateLimitedTransfer},
+    state::PENDING_TRANSFER_EXPIRY,
+};
+use helpers::*;
+use serde::de::DeserializeOwned;
+use wormhole_bindings::fake;
+use wormhole_sdk::{token::Message, vaa::Body, Address, Amount, Chain};
+
+fn decode_event<T: DeserializeOwned>(resp: &AppResponse, ty: &str) -> T {
+    let evt = resp
+        .events
+        .iter()
+        .find(|e| e.ty == ty)
+        .unwrap_or_else(|| panic!("missing `{ty}` event"));
+    cw_transcode::from_event(evt).unwrap()
+}
+
+#[test]
+fn modification() {
+    let (wh, mut contract) = proper_instantiate();
+
+    let m = Modification {
+        sequence: 0,
+        chain_id: 1,
+        token_chain: 1,
+        token_address: [0x7c; 32].into(),
+        kind: Kind::Add,
+        amount: Uint256::from(300u128),
+        reason: "test".into(),
+    };
+
+    let resp = contract.modify_balance(m.clone(), &wh).unwrap();
+    assert_eq!(m, decode_event(&resp, "wasm-Modification"));
+}
+
+#[test]
+fn transfer() {
+    let (wh, mut contract) = proper_instantiate();
+    register_emitters(&wh, &mut contract, 3);
+
+    let body = Body {
+        timestamp: 2,
+        nonce: 2,
+        emitter_chain: Chain::Ethereum,
+        emitter_address: Address([2u8; 32]),
+        sequence: 2,
+        consistency_level: 32,
+        payload: Message::Transfer {
+            amount: Amount(Uint256::from(2u128).to_be_bytes()),
+            token_address: Address([3u8; 32]),
+            token_chain: Chain::Ethereum,
+            recipient: Address([2u8; 32]),
+            recipient_chain: Chain::Bsc,
+            fee: Amount([0u8; 32]),
+        },
+    };
+    let (v, data) = sign_vaa_body(&wh, body);
+    let resp = contract.submit_vaas(vec![data]).unwrap();
+
+    let key = transfer::Key::new(
+        v.emitter_chain.into(),
+        TokenAddress::new(v.emitter_address.0),
+        v.sequence,
+    );
+    let data = contract.query_transfer(key.clone()).unwrap().data;
+    assert_eq!(Transfer { key, data }, decode_event(&resp, "wasm-Transfer"));
+}
+
+fn transfer_observation(sequence: u64, amount: u128) -> Observation {
+    let msg = Message::Transfer {
+        amount: Amount(Uint256::from(amount).to_be_bytes()),
+        token_address: Address([0xccu8; 32]),
+        token_chain: Chain::Ethereum,
+        recipient: Address([0xb9u8; 32]),
+        recipient_chain: Chain::Bsc,
+        fee: Amount([0u8; 32]),
+    };
+    Observation {
+        tx_hash: vec![0xd8u8; 20].into(),
+        timestamp: 0xec8d03d6,
+        nonce: 0x4343b191,
+        emitter_chain: 2,
+        emitter_address: [2u8; 32],
+        sequence,
+        consistency_level: 0,
+        payload: serde_wormhole::to_vec(&msg).map(Binary::from).unwrap(),
+    }
+}
+
+// Submits signatures for `o` from the first `count` guardians and returns the last response.
+fn submit_signatures(
+    wh: &fake::WormholeKeeper,
+    contract: &mut Contract,
+    o: &Observation,
+    count: usize,
+) -> AppResponse {
+    let index = wh.guardian_set_index();
+    let obs = to_binary(&vec![o.clone()]).unwrap();
+    sign_observations(wh, &obs)
+        .into_iter()
+        .take(count)
+        .map(|s| contract.submit_observations(obs.clone(), index, s).unwrap())
+        .last()
+        .unwrap()
+}
+
+fn quorum(wh: &fake::WormholeKeeper, contract: &Contract) -> usize {
+    wh.calculate_quorum(wh.guardian_set_index(), contract.app().block_info().height)
+        .unwrap() as usize
+}
+
+#[test]
+fn observation() {
+    let (wh, mut contract) = proper_instantiate();
+    register_emitters(&wh, &mut contract, 3);
+
+    let o = transfer_observation(37, 500);
+    let resp = submit_signatures(&wh, &mut contract, &o, quorum(&wh, &contract));
+    assert_eq!(o, decode_event(&resp, "wasm-Observation"));
+}
+
+#[test]
+fn reversion() {
+    let (wh, mut contract) = proper_instantiate();
+    register_emitters(&wh, &mut contract, 3);
+
+    let o = transfer_observation(37, 500);
+    submit_signatures(&wh, &mut contract, &o, quorum(&wh, &contract));
+
+    let r = Reversion {
+        key: transfer::Key::new(o.emitter_chain, o.emitter_address.into(), o.sequence),
+        reason: "test".into(),
+    };
+    let resp = contract.revert_transfer(r.clone(), &wh).unwrap();
+    assert_eq!(r, decode_event(&resp, "wasm-Reversion"));
+}
+
+#[test]
+fn rate_limited_transfer() {
+    let (wh, mut contract) = proper_instantiate();
+    register_emitters(&wh, &mut contract, 3);
+
+    let source = account::Key::new(2, 2, [0xcc; 32].into());
+    contract
+        .set_outflow_limit(source, Uint256::from(100u128), 3600, &wh)
+        .unwrap();
+
+    let o = transfer_observation(37, 500);
+    let resp = submit_signatures(&wh, &mut contract, &o, quorum(&wh, &contract));
+
+    let evt: RateLimitedTransfer = decode_event(&resp, "wasm-RateLimitedTransfer");
+    assert_eq!(
+        transfer::Key::new(o.emitter_chain, o.emitter_address.into(), o.sequence),
+        evt.key
+    );
+    assert_eq!(Uint256::from(500u128), evt.data.amount);
+    assert!(evt.usage.is_zero());
+}
+
+#[test]
+fn outflow_limit_update() {
+    let (wh, mut contract) = proper_instantiate();
+
+    let expected = OutflowLimitUpdate {
+        key: account::Key::new(2, 2, [0xcc; 32].into()),
+        limit: Uint256::from(100u128),
+        window: 3600,
+    };
+    let resp = contract
+        .set_outflow_limit(expected.key.clone(), expected.limit, expected.window, &wh)
+        .unwrap();
+    assert_eq!(expected, decode_event(&resp, "wasm-OutflowLimitUpdate"));
+}
+
+#[test]
+fn pruned_pending_transfer() {
+    let (wh, mut contract) = proper_instantiate();
+    register_emitters(&wh, &mut contract, 3);
+
+    let o = transfer_observation(37, 500);
+    submit_signatures(&wh, &mut contract, &o, 1);
+
+    let key = transfer::Key::new(o.emitter_chain, o.emitter_address.into(), o.sequence);
+    let data = contract.query_pending_transfer(key.clone()).unwrap();
+
+    contract
+        .app_mut()
+        .update_block(|b| b.time = b.time.plus_seconds(PENDING_TRANSFER_EXPIRY));
+    let resp = contract.prune_pending_transfers(None, None).unwrap();
+    assert_eq!(
+        PrunedPendingTransfer { key, data },
+        decode_event(&resp, "wasm-PrunedPendingTransfer")
+    );
+}
@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 
 use accountant::{
-    query_balance, query_modification,
+    query_balance, query_balance_at, query_modification,
     state::{account, transfer, Kind, Modification, TokenAddress, Transfer},
     validate_transfer,
 };
@@ -31,7 +31,7 @@ use wormhole_sdk::{
     accountant_modification::ModificationKind,
     ntt_accountant as ntt_accountant_module, relayer,
     vaa::{self, Body, Header, Signature},
-    Chain,
+    Amount, Chain,
 };
 
 use crate::{
@@ -40,22 +40,32 @@ use crate::{
     msg::{
         AllAccountsResponse, AllModificationsResponse, AllPendingTransfersResponse,
         AllTransceiverHubsResponse, AllTransceiverPeersResponse, AllTransfersResponse,
-        BatchTransferStatusResponse, ExecuteMsg, MigrateMsg, MissingObservation,
-        MissingObservationsResponse, Observation, ObservationError, ObservationStatus, QueryMsg,
-        RelayerChainRegistrationResponse, SubmitObservationResponse, TransferDetails,
+        BackfillInvariantStateResponse, BatchTransferStatusResponse, CheckInvariantsResponse,
+        ExecuteMsg, MigrateMsg, MissingObservation, MissingObservationsResponse, Observation,
+        ObservationError, ObservationStatus, PrunePendingTransfersResponse, PrunedPendingTransfer,
+        QueryMsg, RelayerChainRegistrationResponse, SubmitObservationResponse, TransferDetails,
         TransferStatus, SUBMITTED_OBSERVATIONS_PREFIX,
     },
     state::{
         Data, PendingTransfer, TransceiverHub, TransceiverPeer, DIGESTS, PENDING_TRANSFERS,
         RELAYER_CHAIN_REGISTRATIONS, TRANSCEIVER_PEER, TRANSCEIVER_TO_HUB,
     },
-    structs::DeliveryInstruction,
 };
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:ntt-global-accountant";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// The maximum number of pending transfers examined by a single `PrunePendingTransfers` message.
+const MAX_PRUNE_LIMIT: u32 = 100;
+
+// The maximum number of accounts or modifications examined by a single `BackfillInvariantState`
+// message.
+const MAX_BACKFILL_LIMIT: u32 = 100;
+
+// The maximum number of tokens examined by a single `CheckInvariants` query.
+const MAX_INVARIANT_LIMIT: u32 = 100;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut<WormholeQuery>,
@@ -65,6 +75,8 @@ pub fn instantiate(
 ) -> Result<Response, AnyError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)
         .context("failed to set contract version")?;
+    accountant::init_invariant_state(deps.storage)
+        .context("failed to initialize invariant state")?;
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
@@ -73,14 +85,18 @@ pub fn instantiate(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut<WormholeQuery>, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+pub fn migrate(deps: DepsMut<WormholeQuery>, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    // The per-token invariant state is rebuilt in batches by `BackfillInvariantState` messages
+    // because there may be too many accounts and modifications to do it here.
+    accountant::start_invariant_backfill(deps.storage)?;
+
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut<WormholeQuery>,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, AnyError> {
@@ -89,14 +105,28 @@ pub fn execute(
             observations,
             guardian_set_index,
             signature,
-        } => submit_observations(deps, info, observations, guardian_set_index, signature),
+        } => submit_observations(
+            deps,
+            &env,
+            info,
+            observations,
+            guardian_set_index,
+            signature,
+        ),
+
+        ExecuteMsg::SubmitVaas { vaas } => submit_vaas(deps, &env, info, vaas),
+
+        ExecuteMsg::PrunePendingTransfers { start_after, limit } => {
+            prune_pending_transfers(deps, &env, info, start_after, limit)
+        }
 
-        ExecuteMsg::SubmitVaas { vaas } => submit_vaas(deps, info, vaas),
+        ExecuteMsg::BackfillInvariantState { limit } => backfill_invariant_state(deps, info, limit),
     }
 }
 
 fn submit_observations(
     mut deps: DepsMut<WormholeQuery>,
+    env: &Env,
     info: MessageInfo,
     observations: Binary,
     guardian_set_index: u32,
@@ -131,7 +161,7 @@ fn submit_observations(
     for o in observations {
         // this key is for the VAA, which is how the guardian is tracking messages
         let key = transfer::Key::new(o.emitter_chain, o.emitter_address.into(), o.sequence);
-        match handle_observation(deps.branch(), o, guardian_set_index, quorum, signature) {
+        match handle_observation(deps.branch(), env, o, guardian_set_index, quorum, signature) {
             Ok((status, event)) => {
                 responses.push(SubmitObservationResponse { key, status });
                 if let Some(evt) = event {
@@ -165,6 +195,7 @@ fn submit_observations(
 
 fn handle_observation(
     mut deps: DepsMut<WormholeQuery>,
+    env: &Env,
     o: Observation,
     guardian_set_index: u32,
     quorum: u32,
@@ -180,11 +211,8 @@ fn handle_observation(
     let (sender, payload) =
         if relayer_emitter.is_some_and(|relayer_address| relayer_address == o.emitter_address) {
             // if the emitter is a known standard relayer, parse the sender and payload from the delivery instruction
-            let delivery_instruction = DeliveryInstruction::deserialize(&o.payload.0)?;
-            (
-                delivery_instruction.sender_address.into(),
-                delivery_instruction.payload,
-            )
+            let (sender, payload) = parse_delivery_instruction(&o.payload.0)?;
+            (sender.into(), payload)
         } else {
             // otherwise, the sender and payload is the same as the VAA
             (o.emitter_address.into(), o.payload.0)
@@ -246,6 +274,7 @@ fn handle_observation(
                 o.tx_hash.clone(),
                 o.emitter_chain,
                 guardian_set_index,
+                env.block.time.seconds(),
             ));
             let back = pending.len() - 1;
             &mut pending[back]
@@ -274,6 +303,7 @@ fn handle_observation(
 
     accountant::commit_transfer(
         deps.branch(),
+        env,
         Transfer {
             key: tx_key,
             data: tx_data,
@@ -295,10 +325,11 @@ fn handle_observation(
 
 fn modify_balance(
     deps: DepsMut<WormholeQuery>,
+    env: &Env,
     info: &MessageInfo,
     modification: Modification,
 ) -> Result<Event, AnyError> {
-    let mut event = accountant::modify_balance(deps, modification)
+    let mut event = accountant::modify_balance(deps, env, modification)
         .context("failed to modify account balance")?;
 
     event = event
@@ -308,14 +339,110 @@ fn modify_balance(
     Ok(event)
 }
 
+fn prune_pending_transfers(
+    deps: DepsMut<WormholeQuery>,
+    env: &Env,
+    info: MessageInfo,
+    start_after: Option<transfer::Key>,
+    limit: Option<u32>,
+) -> Result<Response, AnyError> {
+    let now = env.block.time.seconds();
+    let limit = limit.map_or(MAX_PRUNE_LIMIT, |l| l.min(MAX_PRUNE_LIMIT)) as usize;
+    let start = start_after.map(|key| Bound::Exclusive((key, PhantomData)));
+
+    let keys = PENDING_TRANSFERS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()
+        .context("failed to load pending transfer keys")?;
+    let last_key = if keys.len() < limit {
+        None
+    } else {
+        keys.last().cloned()
+    };
+
+    let mut events = Vec::new();
+    for key in keys {
+        let pending_key = PENDING_TRANSFERS.key(key.clone());
+        let mut pending = pending_key
+            .load(deps.storage)
+            .context("failed to load `PendingTransfer`")?;
+
+        // Data saved before `observed_at` was added has no record of when it was first observed
+        // so its expiry period starts the first time it is examined here.
+        let stamped = pending
+            .iter_mut()
+            .fold(false, |acc, d| d.stamp_observed_at(now) || acc);
+
+        let mut pruned = Vec::new();
+        pending.retain(|d| {
+            if d.is_expired(now) {
+                pruned.push(d.clone());
+                false
+            } else {
+                true
+            }
+        });
+
+        if pruned.is_empty() {
+            if stamped {
+                pending_key
+                    .save(deps.storage, &pending)
+                    .context("failed to save pending transfers")?;
+            }
+            continue;
+        }
+
+        if pending.is_empty() {
+            pending_key.remove(deps.storage);
+        } else {
+            pending_key
+                .save(deps.storage, &pending)
+                .context("failed to save pending transfers")?;
+        }
+
+        let evt = cw_transcode::to_event(&PrunedPendingTransfer { key, data: pruned })
+            .context("failed to transcode `PrunedPendingTransfer` to `Event`")?;
+        events.push(evt);
+    }
+
+    let data = to_binary(&PrunePendingTransfersResponse { last_key })
+        .context("failed to serialize prune response")?;
+
+    Ok(Response::new()
+        .add_attribute("action", "prune_pending_transfers")
+        .add_attribute("owner", info.sender)
+        .set_data(data)
+        .add_events(events))
+}
+
+fn backfill_invariant_state(
+    deps: DepsMut<WormholeQuery>,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, AnyError> {
+    let limit = limit.map_or(MAX_BACKFILL_LIMIT, |l| l.clamp(1, MAX_BACKFILL_LIMIT)) as usize;
+    let complete = accountant::backfill_invariant_state(deps, limit)
+        .context("failed to backfill invariant state")?;
+
+    let data = to_binary(&BackfillInvariantStateResponse { complete })
+        .context("failed to serialize backfill response")?;
+
+    Ok(Response::new()
+        .add_attribute("action", "backfill_invariant_state")
+        .add_attribute("owner", info.sender)
+        .set_data(data))
+}
+
 fn submit_vaas(
     mut deps: DepsMut<WormholeQuery>,
+    env: &Env,
     info: MessageInfo,
     vaas: Vec<Binary>,
 ) -> Result<Response, AnyError> {
     let evts = vaas
         .into_iter()
-        .map(|v| handle_vaa(deps.branch(), &info, v))
+        .map(|v| handle_vaa(deps.branch(), env, &info, v))
         .collect::<anyhow::Result<Vec<_>>>()?;
     Ok(Response::new()
         .add_attribute("action", "submit_vaas")
@@ -325,6 +452,7 @@ fn submit_vaas(
 
 fn handle_vaa(
     mut deps: DepsMut<WormholeQuery>,
+    env: &Env,
     info: &MessageInfo,
     vaa: Binary,
 ) -> anyhow::Result<Event> {
@@ -377,14 +505,19 @@ fn handle_vaa(
         } else if module == ntt_accountant_module::MODULE {
             let govpacket = serde_wormhole::from_slice(body.payload)
                 .context("failed to parse accountant governance packet")?;
-            handle_accountant_governance_vaa(deps.branch(), info, body.with_payload(govpacket))?
+            handle_accountant_governance_vaa(
+                deps.branch(),
+                env,
+                info,
+                body.with_payload(govpacket),
+            )?
         } else {
             bail!("unknown governance module")
         }
     } else {
         let msg =
             serde_wormhole::from_slice(body.payload).context("failed to parse raw message")?;
-        handle_ntt_vaa(deps.branch(), body.with_payload(msg))?
+        handle_ntt_vaa(deps.branch(), env, body.with_payload(msg))?
     };
 
     digest_key
@@ -427,6 +560,7 @@ fn handle_relayer_governance_vaa(
 
 fn handle_accountant_governance_vaa(
     deps: DepsMut<WormholeQuery>,
+    env: &Env,
     info: &MessageInfo,
     body: Body<ntt_accountant_module::GovernancePacket>,
 ) -> anyhow::Result<Event> {
@@ -463,13 +597,14 @@ fn handle_accountant_governance_vaa(
                 amount,
                 reason: reason.to_string(),
             };
-            modify_balance(deps, info, modification).map_err(|e| e.into())
+            modify_balance(deps, env, info, modification).map_err(|e| e.into())
         }
     }
 }
 
 fn handle_ntt_vaa(
     mut deps: DepsMut<WormholeQuery>,
+    env: &Env,
     body: Body<&RawMessage>,
 ) -> anyhow::Result<Event> {
     let relayer_emitter =
@@ -479,12 +614,8 @@ fn handle_ntt_vaa(
         .is_some_and(|relayer_address| relayer_address == body.emitter_address.0)
     {
         // if the emitter is a known standard relayer, parse the sender and payload from the delivery instruction
-        let delivery_instruction =
-            DeliveryInstruction::deserialize(&Vec::from_slice(body.payload)?)?;
-        (
-            delivery_instruction.sender_address.into(),
-            delivery_instruction.payload,
-        )
+        let (sender, payload) = parse_delivery_instruction(body.payload)?;
+        (sender.into(), payload)
     } else {
         // otherwise, the sender and payload is the same as the VAA
         (
@@ -543,7 +674,7 @@ fn handle_ntt_vaa(
             key: key.clone(),
             data,
         };
-        let evt = accountant::commit_transfer(deps.branch(), tx)
+        let evt = accountant::commit_transfer(deps.branch(), env, tx)
             .with_context(|| format!("failed to commit transfer for key {key}"))?;
 
         PENDING_TRANSFERS.remove(deps.storage, key);
@@ -632,6 +763,16 @@ fn handle_ntt_vaa(
     }
 }
 
+// Returns the sender address and payload of a standard relayer delivery instruction.
+fn parse_delivery_instruction(data: &[u8]) -> anyhow::Result<([u8; 32], Vec<u8>)> {
+    match serde_wormhole::from_slice(data).context("failed to parse delivery instruction")? {
+        relayer::Message::Delivery(delivery) => {
+            Ok((delivery.sender_address.0, delivery.payload.into()))
+        }
+        relayer::Message::Redelivery(_) => bail!("unexpected redelivery instruction"),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps<WormholeQuery>, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -639,6 +780,15 @@ pub fn query(deps: Deps<WormholeQuery>, _env: Env, msg: QueryMsg) -> StdResult<B
         QueryMsg::AllAccounts { start_after, limit } => {
             query_all_accounts(deps, start_after, limit).and_then(|resp| to_binary(&resp))
         }
+        QueryMsg::BalanceAt { key, height } => {
+            query_balance_at(deps, key, height).and_then(|resp| to_binary(&resp))
+        }
+        QueryMsg::AllAccountsAt {
+            height,
+            start_after,
+            limit,
+        } => query_all_accounts_at(deps, height, start_after, limit)
+            .and_then(|resp| to_binary(&resp)),
         QueryMsg::AllTransfers { start_after, limit } => {
             query_all_transfers(deps, start_after, limit).and_then(|resp| to_binary(&resp))
         }
@@ -679,6 +829,9 @@ pub fn query(deps: Deps<WormholeQuery>, _env: Env, msg: QueryMsg) -> StdResult<B
         QueryMsg::BatchTransferStatus(keys) => {
             query_batch_transfer_status(deps, keys).and_then(|resp| to_binary(&resp))
         }
+        QueryMsg::CheckInvariants { start_after, limit } => {
+            check_invariants(deps, start_after, limit).and_then(|resp| to_binary(&resp))
+        }
     }
 }
 
@@ -702,6 +855,40 @@ fn query_all_accounts(
     }
 }
 
+fn query_all_accounts_at(
+    deps: Deps<WormholeQuery>,
+    height: u64,
+    start_after: Option<account::Key>,
+    limit: Option<u32>,
+) -> StdResult<AllAccountsResponse> {
+    if let Some(lim) = limit {
+        let l = lim
+            .try_into()
+            .map_err(|_| ConversionOverflowError::new("u32", "usize", lim.to_string()))?;
+        accountant::query_all_accounts_at(deps, start_after, height)
+            .take(l)
+            .collect::<StdResult<Vec<_>>>()
+            .map(|accounts| AllAccountsResponse { accounts })
+    } else {
+        accountant::query_all_accounts_at(deps, start_after, height)
+            .collect::<StdResult<Vec<_>>>()
+            .map(|accounts| AllAccountsResponse { accounts })
+    }
+}
+
+fn check_invariants(
+    deps: Deps<WormholeQuery>,
+    start_after: Option<(u16, TokenAddress)>,
+    limit: Option<u32>,
+) -> StdResult<CheckInvariantsResponse> {
+    let limit = limit.map_or(MAX_INVARIANT_LIMIT, |l| l.clamp(1, MAX_INVARIANT_LIMIT)) as usize;
+    let (violations, last_key) = accountant::check_invariants(deps, start_after, limit)?;
+    Ok(CheckInvariantsResponse {
+        violations,
+        last_key,
+    })
+}
+
 fn query_all_transfers(
     deps: Deps<WormholeQuery>,
     start_after: Option<transfer::Key>,
@@ -925,17 +1112,14 @@ fn query_batch_transfer_status(
 }
 
 fn normalize_transfer_amount(trimmed_amount: TrimmedAmount) -> Uint256 {
-    let to_decimals = TRIMMED_DECIMALS;
     let from_decimals = trimmed_amount.decimals;
-    let amount = Uint256::from(trimmed_amount.amount);
-    if from_decimals == to_decimals {
-        return amount;
-    }
-    if from_decimals > to_decimals {
-        amount / Uint256::from(10u64).pow((from_decimals - to_decimals).into())
-    } else {
-        amount * Uint256::from(10u64).pow((to_decimals - from_decimals).into())
+    let amount = Amount::from(trimmed_amount.amount);
+    if from_decimals >= TRIMMED_DECIMALS {
+        // Trimmed amounts use the same 8 decimals as token bridge transfers.
+        return amount.normalize(from_decimals).into();
     }
+
+    Uint256::from(amount) * Uint256::from(10u64).pow((TRIMMED_DECIMALS - from_decimals).into())
 }
 
 #[cfg(test)]
@@ -979,4 +1163,40 @@ mod test {
             Uint256::from(0u64)
         );
     }
+
+    #[test]
+    fn transcode_events() {
+        let o = Observation {
+            tx_hash: vec![0x5a; 32].into(),
+            timestamp: 1_672_860_466,
+            nonce: 7,
+            emitter_chain: 2,
+            emitter_address: [0x11; 32],
+            sequence: 3,
+            consistency_level: 15,
+            payload: vec![0x99, 0x45, 0x54, 0x54].into(),
+        };
+        let evt = cw_transcode::to_event(&o).unwrap();
+        assert_eq!(o, cw_transcode::from_event(&evt).unwrap());
+
+        let err = ObservationError {
+            key: transfer::Key::new(2, [0x11; 32].into(), 3),
+            error: "unknown emitter address".into(),
+        };
+        let evt = cw_transcode::to_event(&err).unwrap();
+        assert_eq!(err, cw_transcode::from_event(&evt).unwrap());
+
+        let pruned = PrunedPendingTransfer {
+            key: transfer::Key::new(2, [0x11; 32].into(), 3),
+            data: vec![Data::new(
+                o.digest().unwrap(),
+                o.tx_hash.clone(),
+                o.emitter_chain,
+                4,
+                1_672_860_466,
+            )],
+        };
+        let evt = cw_transcode::to_event(&pruned).unwrap();
+        assert_eq!(pruned, cw_transcode::from_event(&evt).unwrap());
+    }
 }
@@ -2,6 +2,5 @@ pub mod contract;
 mod error;
 pub mod msg;
 pub mod state;
-pub mod structs;
 
 pub use crate::error::ContractError;
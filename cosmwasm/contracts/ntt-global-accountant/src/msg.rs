@@ -1,4 +1,6 @@
-use accountant::state::{account, transfer, Account, Modification, TokenAddress, Transfer};
+use accountant::state::{
+    account, transfer, Account, InvariantViolation, Modification, TokenAddress, Transfer,
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Binary;
 use serde_wormhole::RawMessage;
@@ -86,6 +88,27 @@ pub struct ObservationError {
     pub error: String,
 }
 
+#[cw_serde]
+pub struct PrunedPendingTransfer {
+    pub key: transfer::Key,
+    // The expired observation data that was removed.
+    pub data: Vec<state::Data>,
+}
+
+#[cw_serde]
+pub struct PrunePendingTransfersResponse {
+    // The last pending transfer key that was examined.  Pass this as `start_after` to continue
+    // pruning from where this call stopped.  `None` if there are no more pending transfers.
+    pub last_key: Option<transfer::Key>,
+}
+
+#[cw_serde]
+pub struct BackfillInvariantStateResponse {
+    // True once the per-token invariant state has been completely rebuilt and no more
+    // `BackfillInvariantState` messages are needed.
+    pub complete: bool,
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     /// Submit a series of observations.  Once the contract has received a quorum of signatures
@@ -108,6 +131,23 @@ pub enum ExecuteMsg {
         /// wire format.
         vaas: Vec<Binary>,
     },
+
+    /// Remove pending observation data that has not reached quorum within
+    /// `PENDING_TRANSFER_EXPIRY` seconds of first being observed, for example because the
+    /// guardians observed a transaction that was later reorged out.  Anyone may call this.  At
+    /// most `limit` pending transfers, starting after `start_after`, are examined in a single call.
+    /// Data saved before the contract recorded when it was first observed is not pruned until
+    /// `PENDING_TRANSFER_EXPIRY` seconds after it is first examined.
+    PrunePendingTransfers {
+        start_after: Option<transfer::Key>,
+        limit: Option<u32>,
+    },
+
+    /// Continue rebuilding the per-token state used by `CheckInvariants` after the contract was
+    /// migrated from a version that did not have it.  Anyone may call this.  At most `limit`
+    /// accounts or modifications are examined in a single call and `CheckInvariants` returns an
+    /// error until the response reports that the state is complete.
+    BackfillInvariantState { limit: Option<u32> },
 }
 
 #[cw_serde]
@@ -123,6 +163,16 @@ pub enum QueryMsg {
         start_after: Option<account::Key>,
         limit: Option<u32>,
     },
+    // The balance of an account at the start of block `height`, before any changes made in that
+    // block.
+    #[returns(account::Balance)]
+    BalanceAt { key: account::Key, height: u64 },
+    #[returns(AllAccountsResponse)]
+    AllAccountsAt {
+        height: u64,
+        start_after: Option<account::Key>,
+        limit: Option<u32>,
+    },
     #[returns(AllTransfersResponse)]
     AllTransfers {
         start_after: Option<transfer::Key>,
@@ -160,6 +210,13 @@ pub enum QueryMsg {
     TransferStatus(transfer::Key),
     #[returns(BatchTransferStatusResponse)]
     BatchTransferStatus(Vec<transfer::Key>),
+    // Tokens whose balances violate the accountant's core invariant.  At most `limit` tokens are
+    // checked by each query, from 1 up to a maximum of 100.
+    #[returns(CheckInvariantsResponse)]
+    CheckInvariants {
+        start_after: Option<(u16, TokenAddress)>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -231,3 +288,11 @@ pub struct TransferDetails {
 pub struct BatchTransferStatusResponse {
     pub details: Vec<TransferDetails>,
 }
+
+#[cw_serde]
+pub struct CheckInvariantsResponse {
+    pub violations: Vec<InvariantViolation>,
+    // The last token that was checked.  Pass this as `start_after` to continue checking from where
+    // this query stopped.  `None` if there are no more tokens.
+    pub last_key: Option<(u16, TokenAddress)>,
+}
@@ -30,6 +30,10 @@ pub struct PendingTransfer {
     pub data: Vec<Data>,
 }
 
+/// The number of seconds after which pending observation data that has not reached quorum is
+/// considered stale and may be pruned.
+pub const PENDING_TRANSFER_EXPIRY: u64 = 7 * 24 * 60 * 60;
+
 #[cw_serde]
 #[derive(Default)]
 pub struct Data {
@@ -38,6 +42,11 @@ pub struct Data {
     signatures: u128,
     guardian_set_index: u32,
     emitter_chain: u16,
+    // Seconds since UNIX epoch at which the first signature for this data was received.  Data
+    // saved before this field was added has a value of 0 until it is stamped by
+    // `PrunePendingTransfers`.
+    #[serde(default)]
+    observed_at: u64,
 }
 
 impl Data {
@@ -46,6 +55,7 @@ impl Data {
         tx_hash: Binary,
         emitter_chain: u16,
         guardian_set_index: u32,
+        observed_at: u64,
     ) -> Self {
         Self {
             digest,
@@ -53,6 +63,7 @@ impl Data {
             signatures: 0,
             guardian_set_index,
             emitter_chain,
+            observed_at,
         }
     }
 
@@ -76,6 +87,27 @@ impl Data {
         self.signatures
     }
 
+    pub fn observed_at(&self) -> u64 {
+        self.observed_at
+    }
+
+    /// Sets the time at which this `Data` was first observed to `now` if it was saved before that
+    /// time was recorded.  Returns true if the time was updated.
+    pub fn stamp_observed_at(&mut self, now: u64) -> bool {
+        if self.observed_at == 0 {
+            self.observed_at = now;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns true if at least `PENDING_TRANSFER_EXPIRY` seconds have passed between the time
+    /// this `Data` was first observed and `now`.
+    pub fn is_expired(&self, now: u64) -> bool {
+        now.saturating_sub(self.observed_at) >= PENDING_TRANSFER_EXPIRY
+    }
+
     /// Returns the number of signatures for this `Data`.
     pub fn num_signatures(&self) -> u32 {
         self.signatures.count_ones()
@@ -137,4 +169,36 @@ mod test {
                 .expect_err("successfully added out-of-bounds signature");
         }
     }
+
+    #[test]
+    fn expiry() {
+        let data = Data::new(Binary::default(), Binary::default(), 2, 0, 1000);
+        assert!(!data.is_expired(0));
+        assert!(!data.is_expired(1000));
+        assert!(!data.is_expired(1000 + PENDING_TRANSFER_EXPIRY - 1));
+        assert!(data.is_expired(1000 + PENDING_TRANSFER_EXPIRY));
+    }
+
+    #[test]
+    fn stamp_legacy_data() {
+        let legacy = br#"{
+            "digest": "",
+            "tx_hash": "",
+            "signatures": "3",
+            "guardian_set_index": 0,
+            "emitter_chain": 2
+        }"#;
+
+        let mut data: Data = serde_json_wasm::from_slice(legacy).unwrap();
+        assert_eq!(0, data.observed_at());
+        assert_eq!(2, data.num_signatures());
+
+        assert!(data.stamp_observed_at(1000));
+        assert_eq!(1000, data.observed_at());
+        assert!(!data.is_expired(PENDING_TRANSFER_EXPIRY));
+
+        // Data that already has a time is left alone.
+        assert!(!data.stamp_observed_at(2000));
+        assert_eq!(1000, data.observed_at());
+    }
 }
@@ -1,2 +0,0 @@
-mod relayer;
-pub use relayer::*;
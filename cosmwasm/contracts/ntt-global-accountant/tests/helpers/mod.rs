@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use accountant::state::{account, transfer, Kind, Modification};
+use accountant::state::{account, transfer, Kind, Modification, TokenAddress};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
     testing::{MockApi, MockStorage},
@@ -12,8 +12,9 @@ use cw_multi_test::{
 use ntt_global_accountant::{
     msg::{
         AllAccountsResponse, AllModificationsResponse, AllPendingTransfersResponse,
-        AllTransfersResponse, BatchTransferStatusResponse, ExecuteMsg, MissingObservationsResponse,
-        QueryMsg, RelayerChainRegistrationResponse, TransferStatus, SUBMITTED_OBSERVATIONS_PREFIX,
+        AllTransfersResponse, BatchTransferStatusResponse, CheckInvariantsResponse, ExecuteMsg,
+        MissingObservationsResponse, QueryMsg, RelayerChainRegistrationResponse, TransferStatus,
+        SUBMITTED_OBSERVATIONS_PREFIX,
     },
     state,
 };
@@ -143,6 +144,19 @@ impl Contract {
         )
     }
 
+    pub fn prune_pending_transfers(
+        &mut self,
+        start_after: Option<transfer::Key>,
+        limit: Option<u32>,
+    ) -> anyhow::Result<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(USER),
+            self.addr(),
+            &ExecuteMsg::PrunePendingTransfers { start_after, limit },
+            &[],
+        )
+    }
+
     pub fn query_balance(&self, key: account::Key) -> StdResult<account::Balance> {
         self.app
             .wrap()
@@ -159,6 +173,39 @@ impl Contract {
             .query_wasm_smart(self.addr(), &QueryMsg::AllAccounts { start_after, limit })
     }
 
+    pub fn query_balance_at(&self, key: account::Key, height: u64) -> StdResult<account::Balance> {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.addr(), &QueryMsg::BalanceAt { key, height })
+    }
+
+    pub fn query_all_accounts_at(
+        &self,
+        height: u64,
+        start_after: Option<account::Key>,
+        limit: Option<u32>,
+    ) -> StdResult<AllAccountsResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::AllAccountsAt {
+                height,
+                start_after,
+                limit,
+            },
+        )
+    }
+
+    pub fn check_invariants(
+        &self,
+        start_after: Option<(u16, TokenAddress)>,
+        limit: Option<u32>,
+    ) -> StdResult<CheckInvariantsResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.addr(),
+            &QueryMsg::CheckInvariants { start_after, limit },
+        )
+    }
+
     pub fn query_transfer_status(&self, key: transfer::Key) -> StdResult<TransferStatus> {
         self.app
             .wrap()
@@ -0,0 +1,118 @@
+use std::marker::PhantomData;
+
+use accountant::state::{account, transfer, ACCOUNTS};
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+    OwnedDeps, Storage, Uint256,
+};
+use ntt_global_accountant::{
+    contract::{execute, migrate, query},
+    msg::{BackfillInvariantStateResponse, ExecuteMsg, MigrateMsg, QueryMsg},
+    state::{PENDING_TRANSFERS, PENDING_TRANSFER_EXPIRY},
+};
+use wormhole_bindings::WormholeQuery;
+
+type MockDeps = OwnedDeps<MockStorage, MockApi, MockQuerier<WormholeQuery>, WormholeQuery>;
+
+fn mock_deps() -> MockDeps {
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: MockQuerier::<WormholeQuery>::new(&[]),
+        custom_query_type: PhantomData,
+    }
+}
+
+#[test]
+fn stamp_legacy_pending_transfers() {
+    let mut deps = mock_deps();
+
+    // Pending data as it was saved before `observed_at` was added.
+    let key = transfer::Key::new(2, [2; 32].into(), 7);
+    let legacy = br#"[{
+        "digest": "",
+        "tx_hash": "",
+        "signatures": "3",
+        "guardian_set_index": 0,
+        "emitter_chain": 2
+    }]"#;
+    deps.storage
+        .set(&PENDING_TRANSFERS.key(key.clone()), legacy);
+
+    let mut env = mock_env();
+    migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+
+    // Migrating leaves the data alone and pruning starts its expiry period instead of removing it.
+    let prune = ExecuteMsg::PrunePendingTransfers {
+        start_after: None,
+        limit: None,
+    };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user", &[]),
+        prune.clone(),
+    )
+    .unwrap();
+
+    let now = env.block.time.seconds();
+    let pending = PENDING_TRANSFERS.load(&deps.storage, key.clone()).unwrap();
+    assert_eq!(1, pending.len());
+    assert_eq!(now, pending[0].observed_at());
+    assert_eq!(2, pending[0].num_signatures());
+
+    env.block.time = env.block.time.plus_seconds(PENDING_TRANSFER_EXPIRY);
+    execute(deps.as_mut(), env, mock_info("user", &[]), prune).unwrap();
+    assert!(!PENDING_TRANSFERS.has(&deps.storage, key));
+}
+
+#[test]
+fn backfill_invariant_state() {
+    let mut deps = mock_deps();
+    let env = mock_env();
+
+    // Accounts saved before the per-token invariant state existed.
+    for i in 1..=3u16 {
+        let key = account::Key::new(i, 1, [1; 32].into());
+        ACCOUNTS
+            .save(
+                &mut deps.storage,
+                key,
+                &Uint256::from(100u128).into(),
+                env.block.height,
+            )
+            .unwrap();
+    }
+
+    migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+
+    let check = QueryMsg::CheckInvariants {
+        start_after: None,
+        limit: None,
+    };
+    query(deps.as_ref(), env.clone(), check.clone())
+        .expect_err("checked invariants before the backfill was complete");
+
+    let mut calls = 0;
+    loop {
+        calls += 1;
+        let resp = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("user", &[]),
+            ExecuteMsg::BackfillInvariantState { limit: Some(2) },
+        )
+        .unwrap();
+        let resp: BackfillInvariantStateResponse = from_binary(&resp.data.unwrap()).unwrap();
+        if resp.complete {
+            break;
+        }
+    }
+    // Two calls for the accounts and one to find that there are no modifications.
+    assert_eq!(3, calls);
+
+    // Migrating again must not restart the backfill.
+    migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+    query(deps.as_ref(), env, check).unwrap();
+}
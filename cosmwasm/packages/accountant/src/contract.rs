@@ -1,7 +1,9 @@
 use std::marker::PhantomData;
 
 use anyhow::{bail, ensure, Context};
-use cosmwasm_std::{CustomQuery, Deps, DepsMut, Event, Order, StdResult};
+use cosmwasm_std::{
+    CustomQuery, Deps, DepsMut, Empty, Env, Event, Order, StdError, StdResult, Storage, Uint256,
+};
 use cw_storage_plus::Bound;
 use thiserror::Error as ThisError;
 
@@ -9,20 +11,25 @@ use crate::{
     msg::Instantiate,
     state::{
         account::{self, Balance},
-        transfer, Account, Kind, Modification, Transfer, ACCOUNTS, MODIFICATIONS, TRANSFERS,
+        transfer, Account, InvariantBackfill, InvariantViolation, Kind, Modification,
+        ModificationTotal, Reversion, TokenAddress, Transfer, ACCOUNTS, INVARIANT_BACKFILL,
+        MODIFICATIONS, MODIFICATION_TOTALS, REVERSIONS, TOKEN_ACCOUNTS, TRANSFERS,
     },
 };
 
 /// Instantiate the on-chain state for accountant.  Unlike other methods in this crate,
 /// `instantiate` does not perform any validation of the data in `init`.
-pub fn instantiate<C: CustomQuery>(deps: DepsMut<C>, init: Instantiate) -> anyhow::Result<Event> {
+pub fn instantiate<C: CustomQuery>(
+    deps: DepsMut<C>,
+    env: &Env,
+    init: Instantiate,
+) -> anyhow::Result<Event> {
     let num_accounts = init.accounts.len();
     let num_transfers = init.transfers.len();
     let num_modifications = init.modifications.len();
 
     for a in init.accounts {
-        ACCOUNTS
-            .save(deps.storage, a.key, &a.balance)
+        save_account(deps.storage, a.key, &a.balance, env.block.height)
             .context("failed to save `Account`")?;
     }
 
@@ -33,6 +40,7 @@ pub fn instantiate<C: CustomQuery>(deps: DepsMut<C>, init: Instantiate) -> anyho
     }
 
     for m in init.modifications {
+        add_modification_total(deps.storage, &m).context("failed to update modification total")?;
         MODIFICATIONS
             .save(deps.storage, m.sequence, &m)
             .context("failed to save `Modification`")?;
@@ -77,7 +85,10 @@ pub fn validate_transfer<C: CustomQuery>(deps: Deps<C>, t: &Transfer) -> anyhow:
 /// #         state::{transfer, Transfer},
 /// #         TransferError,
 /// #     };
-/// #     use cosmwasm_std::{testing::mock_dependencies, Uint256};
+/// #     use cosmwasm_std::{
+/// #         testing::{mock_dependencies, mock_env},
+/// #         Uint256,
+/// #     };
 /// #
 /// #     let mut deps = mock_dependencies();
 /// #     let tx = Transfer {
@@ -90,10 +101,10 @@ pub fn validate_transfer<C: CustomQuery>(deps: Deps<C>, t: &Transfer) -> anyhow:
 /// #         },
 /// #     };
 /// #
-///       commit_transfer(deps.as_mut(), tx.clone())?;
+///       commit_transfer(deps.as_mut(), &mock_env(), tx.clone())?;
 ///  
 ///       // Repeating the transfer should return an error.
-///       let err = commit_transfer(deps.as_mut(), tx)
+///       let err = commit_transfer(deps.as_mut(), &mock_env(), tx)
 ///           .expect_err("successfully committed duplicate transfer");
 ///       if let Some(e) = err.downcast_ref::<TransferError>() {
 ///           assert!(matches!(e, TransferError::DuplicateTransfer));
@@ -106,14 +117,16 @@ pub fn validate_transfer<C: CustomQuery>(deps: Deps<C>, t: &Transfer) -> anyhow:
 /// #
 /// # example().unwrap();
 /// ```
-pub fn commit_transfer<C: CustomQuery>(deps: DepsMut<C>, t: Transfer) -> anyhow::Result<Event> {
+pub fn commit_transfer<C: CustomQuery>(
+    deps: DepsMut<C>,
+    env: &Env,
+    t: Transfer,
+) -> anyhow::Result<Event> {
     let (src, dst) = transfer(deps.as_ref(), &t)?;
 
-    ACCOUNTS
-        .save(deps.storage, src.key, &src.balance)
+    save_account(deps.storage, src.key, &src.balance, env.block.height)
         .context("failed to save updated source account")?;
-    ACCOUNTS
-        .save(deps.storage, dst.key, &dst.balance)
+    save_account(deps.storage, dst.key, &dst.balance, env.block.height)
         .context("failed to save updated destination account")?;
 
     let evt = cw_transcode::to_event(&t).context("failed to transcode `Transfer` to `Event`")?;
@@ -216,7 +229,10 @@ pub enum ModifyBalanceError {
 /// #         state::{Kind, Modification},
 /// #         ModifyBalanceError,
 /// #     };
-/// #     use cosmwasm_std::{testing::mock_dependencies, Uint256};
+/// #     use cosmwasm_std::{
+/// #         testing::{mock_dependencies, mock_env},
+/// #         Uint256,
+/// #     };
 /// #     let mut deps = mock_dependencies();
 /// #
 ///       /// Subtract the balance from an account that doesn't exist.
@@ -230,7 +246,7 @@ pub enum ModifyBalanceError {
 ///           reason: "test".try_into().unwrap(),
 ///       };
 ///  
-///       let err = modify_balance(deps.as_mut(), m)
+///       let err = modify_balance(deps.as_mut(), &mock_env(), m)
 ///           .expect_err("successfully modified account with insufficient balance");
 ///       if let Some(e) = err.downcast_ref::<ModifyBalanceError>() {
 ///           assert!(matches!(e, ModifyBalanceError::InsufficientBalance));
@@ -243,20 +259,17 @@ pub enum ModifyBalanceError {
 /// ```
 pub fn modify_balance<C: CustomQuery>(
     deps: DepsMut<C>,
+    env: &Env,
     msg: Modification,
 ) -> anyhow::Result<Event> {
     if MODIFICATIONS.has(deps.storage, msg.sequence) {
         bail!(ModifyBalanceError::DuplicateModification);
     }
 
-    let key = ACCOUNTS.key(account::Key::new(
-        msg.chain_id,
-        msg.token_chain,
-        msg.token_address,
-    ));
+    let key = account::Key::new(msg.chain_id, msg.token_chain, msg.token_address);
 
-    let balance = key
-        .may_load(deps.storage)
+    let balance = ACCOUNTS
+        .may_load(deps.storage, key.clone())
         .context("failed to load account")?
         .unwrap_or(Balance::zero());
 
@@ -267,9 +280,10 @@ pub fn modify_balance<C: CustomQuery>(
     .map(Balance::from)
     .context(ModifyBalanceError::InsufficientBalance)?;
 
-    key.save(deps.storage, &new_balance)
+    save_account(deps.storage, key, &new_balance, env.block.height)
         .context("failed to save account")?;
 
+    add_modification_total(deps.storage, &msg).context("failed to update modification total")?;
     MODIFICATIONS
         .save(deps.storage, msg.sequence, &msg)
         .context("failed to store `Modification`")?;
@@ -277,6 +291,138 @@ pub fn modify_balance<C: CustomQuery>(
     cw_transcode::to_event(&msg).context("failed to transcode `Modification` to `Event`")
 }
 
+#[derive(ThisError, Debug)]
+pub enum RevertTransferError {
+    #[error("transfer already reverted")]
+    DuplicateReversion,
+    #[error("cannot revert a transfer that was never committed")]
+    MissingTransfer,
+    #[error("insufficient balance in destination account")]
+    InsufficientDestinationBalance,
+    #[error("insufficient balance in source account")]
+    InsufficientSourceBalance,
+}
+
+/// Reverts a committed transfer by undoing its changes to the source and destination accounts.
+/// The transfer itself remains in the on-chain state so that it cannot be committed again.  If an
+/// error occurs that is not due to the underlying cosmwasm framework, the returned error will be
+/// downcastable to `RevertTransferError`.
+///
+/// # Examples
+///
+/// ```
+/// # fn example() -> anyhow::Result<()> {
+/// #     use accountant::{
+/// #         commit_transfer, query_balance, revert_transfer,
+/// #         state::{account, transfer, Reversion, Transfer},
+/// #         RevertTransferError,
+/// #     };
+/// #     use cosmwasm_std::{
+/// #         testing::{mock_dependencies, mock_env},
+/// #         Uint256,
+/// #     };
+/// #
+/// #     let mut deps = mock_dependencies();
+/// #     let tx = Transfer {
+/// #         key: transfer::Key::new(3, [1u8; 32].into(), 5),
+/// #         data: transfer::Data {
+/// #             amount: Uint256::from(400u128),
+/// #             token_chain: 3,
+/// #             token_address: [3u8; 32].into(),
+/// #             recipient_chain: 9,
+/// #         },
+/// #     };
+/// #
+///       commit_transfer(deps.as_mut(), &mock_env(), tx.clone())?;
+///
+///       let r = Reversion {
+///           key: tx.key.clone(),
+///           reason: "test".into(),
+///       };
+///       revert_transfer(deps.as_mut(), &mock_env(), r.clone())?;
+///
+///       // The locked balance on the source chain has been released.
+///       let src = account::Key::new(3, 3, [3u8; 32].into());
+///       assert_eq!(Uint256::zero(), *query_balance(deps.as_ref(), src)?);
+///
+///       // A transfer can only be reverted once.
+///       let err = revert_transfer(deps.as_mut(), &mock_env(), r)
+///           .expect_err("successfully reverted transfer twice");
+///       if let Some(e) = err.downcast_ref::<RevertTransferError>() {
+///           assert!(matches!(e, RevertTransferError::DuplicateReversion));
+///       } else {
+///           println!("framework error: {err:#}");
+///       }
+/// #
+/// #     Ok(())
+/// # }
+/// #
+/// # example().unwrap();
+/// ```
+pub fn revert_transfer<C: CustomQuery>(
+    deps: DepsMut<C>,
+    env: &Env,
+    r: Reversion,
+) -> anyhow::Result<Event> {
+    if REVERSIONS.has(deps.storage, r.key.clone()) {
+        bail!(RevertTransferError::DuplicateReversion);
+    }
+
+    let data = TRANSFERS
+        .may_load(deps.storage, r.key.clone())
+        .context("failed to load `transfer::Data`")?
+        .ok_or(RevertTransferError::MissingTransfer)?;
+
+    let mut src = {
+        let key = account::Key::new(r.key.emitter_chain(), data.token_chain, data.token_address);
+        let balance = ACCOUNTS
+            .load(deps.storage, key.clone())
+            .context("failed to load source account")?;
+        Account { key, balance }
+    };
+
+    let mut dst = if r.key.emitter_chain() == data.recipient_chain {
+        // This was a self-transfer so the source and destination accounts are the same.
+        src.clone()
+    } else {
+        let key = account::Key::new(data.recipient_chain, data.token_chain, data.token_address);
+        let balance = ACCOUNTS
+            .load(deps.storage, key.clone())
+            .context("failed to load destination account")?;
+        Account { key, balance }
+    };
+
+    // Apply the inverse of each change made by `transfer`, in reverse order.
+    if src.key == dst.key {
+        src.lock_or_burn(data.amount)
+            .context(RevertTransferError::InsufficientDestinationBalance)?;
+    } else {
+        dst.lock_or_burn(data.amount)
+            .context(RevertTransferError::InsufficientDestinationBalance)?;
+    }
+
+    src.unlock_or_mint(data.amount)
+        .context(RevertTransferError::InsufficientSourceBalance)?;
+
+    save_account(
+        deps.storage,
+        src.key.clone(),
+        &src.balance,
+        env.block.height,
+    )
+    .context("failed to save updated source account")?;
+    if src.key != dst.key {
+        save_account(deps.storage, dst.key, &dst.balance, env.block.height)
+            .context("failed to save updated destination account")?;
+    }
+
+    REVERSIONS
+        .save(deps.storage, r.key.clone(), &r)
+        .context("failed to save `Reversion`")?;
+
+    cw_transcode::to_event(&r).context("failed to transcode `Reversion` to `Event`")
+}
+
 /// Query the balance for the account associated with `key`.
 pub fn query_balance<C: CustomQuery>(deps: Deps<C>, key: account::Key) -> StdResult<Balance> {
     ACCOUNTS.load(deps.storage, key)
@@ -294,6 +440,40 @@ pub fn query_all_accounts<C: CustomQuery>(
         .map(|item| item.map(|(key, balance)| Account { key, balance }))
 }
 
+/// Query the balance for the account associated with `key` as it was at the start of block
+/// `height`, i.e. before any changes made in that block.  Returns `StdError::NotFound` if the
+/// account did not exist at that point.
+pub fn query_balance_at<C: CustomQuery>(
+    deps: Deps<C>,
+    key: account::Key,
+    height: u64,
+) -> StdResult<Balance> {
+    ACCOUNTS
+        .may_load_at_height(deps.storage, key.clone(), height)?
+        .ok_or_else(|| StdError::not_found(format!("account {key:?} at height {height}")))
+}
+
+/// Query information for all accounts as they were at the start of block `height`.  Accounts that
+/// did not exist at that point are skipped.
+pub fn query_all_accounts_at<C: CustomQuery>(
+    deps: Deps<C>,
+    start_after: Option<account::Key>,
+    height: u64,
+) -> impl Iterator<Item = StdResult<Account>> + '_ {
+    let start = start_after.map(|key| Bound::Exclusive((key, PhantomData)));
+
+    ACCOUNTS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .filter_map(move |item| {
+            item.and_then(|key| {
+                ACCOUNTS
+                    .may_load_at_height(deps.storage, key.clone(), height)
+                    .map(|b| b.map(|balance| Account { key, balance }))
+            })
+            .transpose()
+        })
+}
+
 /// Query the data associated with a transfer.
 pub fn query_transfer<C: CustomQuery>(
     deps: Deps<C>,
@@ -336,14 +516,243 @@ pub fn query_all_modifications<C: CustomQuery>(
         .map(|item| item.map(|(_, v)| v))
 }
 
+/// Query the reversion of the transfer associated with `key`.
+pub fn query_reversion<C: CustomQuery>(deps: Deps<C>, key: transfer::Key) -> StdResult<Reversion> {
+    REVERSIONS.load(deps.storage, key)
+}
+
+/// Check if the transfer associated with `key` has been reverted.
+pub fn is_reverted<C: CustomQuery>(deps: Deps<C>, key: transfer::Key) -> bool {
+    REVERSIONS.has(deps.storage, key)
+}
+
+/// Query information for all reverted transfers.
+pub fn query_all_reversions<C: CustomQuery>(
+    deps: Deps<C>,
+    start_after: Option<transfer::Key>,
+) -> impl Iterator<Item = StdResult<Reversion>> + '_ {
+    let start = start_after.map(|key| Bound::Exclusive((key, PhantomData)));
+
+    REVERSIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .map(|item| item.map(|(_, v)| v))
+}
+
+// Saves `balance` as the balance of the account for `key` and adds the account to
+// `TOKEN_ACCOUNTS` if it is new.
+fn save_account(
+    storage: &mut dyn Storage,
+    key: account::Key,
+    balance: &Balance,
+    height: u64,
+) -> StdResult<()> {
+    let token_key = (key.token_chain(), *key.token_address(), key.chain_id());
+    if !TOKEN_ACCOUNTS.has(storage, token_key) {
+        TOKEN_ACCOUNTS.save(storage, token_key, &Empty {})?;
+    }
+
+    ACCOUNTS.save(storage, key, balance, height)
+}
+
+// Adds the effect of `m` to the modification total for its token.
+fn add_modification_total(storage: &mut dyn Storage, m: &Modification) -> StdResult<()> {
+    // Modifications that the backfill has not reached yet are added when it reaches them.
+    match INVARIANT_BACKFILL.may_load(storage)? {
+        Some(InvariantBackfill::Accounts { .. }) => return Ok(()),
+        Some(InvariantBackfill::Modifications { start_after })
+            if start_after.map_or(true, |s| m.sequence > s) =>
+        {
+            return Ok(())
+        }
+        _ => {}
+    }
+
+    MODIFICATION_TOTALS.update(storage, (m.token_chain, m.token_address), |total| {
+        let mut total = total.unwrap_or_default();
+        total.add(m)?;
+        Ok(total)
+    })
+}
+
+/// Mark the per-token state used by [`check_invariants`] as complete.  Contracts must call this
+/// when they are instantiated so that it is never rebuilt.
+pub fn init_invariant_state(storage: &mut dyn Storage) -> StdResult<()> {
+    INVARIANT_BACKFILL.save(storage, &InvariantBackfill::Complete)
+}
+
+/// Start rebuilding the per-token state used by [`check_invariants`] from the accounts and
+/// modifications that were saved before it existed.  Contracts that were instantiated before this
+/// state existed must call this while migrating and then call [`backfill_invariant_state`] until
+/// it is complete.  Does nothing if the state was already initialized or a rebuild was already
+/// started.
+pub fn start_invariant_backfill(storage: &mut dyn Storage) -> StdResult<()> {
+    if INVARIANT_BACKFILL.may_load(storage)?.is_none() {
+        INVARIANT_BACKFILL.save(storage, &InvariantBackfill::Accounts { start_after: None })?;
+    }
+
+    Ok(())
+}
+
+/// Continue rebuilding the per-token state used by [`check_invariants`], examining at most `limit`
+/// accounts or modifications.  Returns true once the state is complete.
+pub fn backfill_invariant_state<C: CustomQuery>(deps: DepsMut<C>, limit: usize) -> StdResult<bool> {
+    let limit = limit.max(1);
+    let progress = INVARIANT_BACKFILL
+        .may_load(deps.storage)?
+        .unwrap_or(InvariantBackfill::Complete);
+
+    let next = match progress {
+        InvariantBackfill::Accounts { start_after } => {
+            let start = start_after.map(|key| Bound::Exclusive((key, PhantomData)));
+            let keys = ACCOUNTS
+                .keys(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for key in &keys {
+                TOKEN_ACCOUNTS.save(
+                    deps.storage,
+                    (key.token_chain(), *key.token_address(), key.chain_id()),
+                    &Empty {},
+                )?;
+            }
+
+            if keys.len() < limit {
+                InvariantBackfill::Modifications { start_after: None }
+            } else {
+                InvariantBackfill::Accounts {
+                    start_after: keys.last().cloned(),
+                }
+            }
+        }
+        InvariantBackfill::Modifications { start_after } => {
+            let start = start_after.map(|seq| Bound::Exclusive((seq, PhantomData)));
+            let modifications = MODIFICATIONS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (_, m) in &modifications {
+                MODIFICATION_TOTALS.update(
+                    deps.storage,
+                    (m.token_chain, m.token_address),
+                    |t| {
+                        let mut total = t.unwrap_or_default();
+                        total.add(m)?;
+                        StdResult::Ok(total)
+                    },
+                )?;
+            }
+
+            if modifications.len() < limit {
+                InvariantBackfill::Complete
+            } else {
+                InvariantBackfill::Modifications {
+                    start_after: modifications.last().map(|(seq, _)| *seq),
+                }
+            }
+        }
+        InvariantBackfill::Complete => return Ok(true),
+    };
+
+    let complete = next == InvariantBackfill::Complete;
+    INVARIANT_BACKFILL.save(deps.storage, &next)?;
+
+    Ok(complete)
+}
+
+/// Check the core invariant of the accountant for up to `limit` tokens, ordered by
+/// `(token_chain, token_address)` and starting after `start_after`.  Returns the tokens that
+/// violate it along with the last token that was checked, which can be passed as `start_after` to
+/// continue from where this call stopped.  The last token is `None` once every token has been
+/// checked.  A `limit` of 0 is treated as 1.  Returns an error while [`backfill_invariant_state`]
+/// is still rebuilding the per-token state.
+pub fn check_invariants<C: CustomQuery>(
+    deps: Deps<C>,
+    start_after: Option<(u16, TokenAddress)>,
+    limit: usize,
+) -> StdResult<(Vec<InvariantViolation>, Option<(u16, TokenAddress)>)> {
+    if !matches!(
+        INVARIANT_BACKFILL.may_load(deps.storage)?,
+        None | Some(InvariantBackfill::Complete)
+    ) {
+        return Err(StdError::generic_err(
+            "per-token invariant state is still being rebuilt",
+        ));
+    }
+
+    // Always check at least one token so that an empty result is never mistaken for the end.
+    let limit = limit.max(1);
+
+    // Skip every account of the `start_after` token.
+    let start = start_after.map(|(token_chain, token_address)| {
+        Bound::Exclusive(((token_chain, token_address, u16::MAX), PhantomData))
+    });
+
+    let mut tokens = Vec::<InvariantViolation>::new();
+    for item in TOKEN_ACCOUNTS.keys(deps.storage, start, None, Order::Ascending) {
+        let (token_chain, token_address, chain_id) = item?;
+        let current = tokens.last().map(|t| (t.token_chain, t.token_address));
+        if current != Some((token_chain, token_address)) {
+            if tokens.len() == limit {
+                break;
+            }
+
+            tokens.push(InvariantViolation {
+                token_chain,
+                token_address,
+                locked: Uint256::zero(),
+                wrapped: Uint256::zero(),
+                adjusted_locked: Uint256::zero(),
+                adjusted_wrapped: Uint256::zero(),
+            });
+        }
+
+        let balance = ACCOUNTS.load(
+            deps.storage,
+            account::Key::new(chain_id, token_chain, token_address),
+        )?;
+        let last = tokens.len() - 1;
+        let t = &mut tokens[last];
+        if chain_id == token_chain {
+            t.locked = t.locked.checked_add(*balance)?;
+        } else {
+            t.wrapped = t.wrapped.checked_add(*balance)?;
+        }
+    }
+
+    let last_key = if tokens.len() < limit {
+        None
+    } else {
+        tokens.last().map(|t| (t.token_chain, t.token_address))
+    };
+
+    let mut violations = Vec::new();
+    for mut t in tokens {
+        let total = MODIFICATION_TOTALS
+            .may_load(deps.storage, (t.token_chain, t.token_address))?
+            .unwrap_or_default();
+        t.adjusted_locked = t.locked.checked_add(total.locked)?;
+        t.adjusted_wrapped = t.wrapped.checked_add(total.wrapped)?;
+        if t.adjusted_locked != t.adjusted_wrapped {
+            violations.push(t);
+        }
+    }
+
+    Ok((violations, last_key))
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
 
-    use cosmwasm_std::{testing::mock_dependencies, StdError, Uint256};
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env},
+        StdError, Uint256,
+    };
 
     use super::*;
 
+    use proptest::prelude::*;
+
     fn create_accounts(count: usize) -> Vec<Account> {
         let mut out = Vec::with_capacity(count * count);
         for i in 0..count {
@@ -360,7 +769,12 @@ mod tests {
     fn save_accounts(deps: DepsMut, accounts: &[Account]) {
         for a in accounts {
             ACCOUNTS
-                .save(deps.storage, a.key.clone(), &a.balance)
+                .save(
+                    deps.storage,
+                    a.key.clone(),
+                    &a.balance,
+                    mock_env().block.height,
+                )
                 .unwrap();
         }
     }
@@ -424,7 +838,7 @@ mod tests {
             modifications: create_modifications(count),
         };
 
-        instantiate(deps.as_mut(), msg.clone()).unwrap();
+        instantiate(deps.as_mut(), &mock_env(), msg.clone()).unwrap();
 
         for a in msg.accounts {
             assert_eq!(a.balance, query_balance(deps.as_ref(), a.key).unwrap());
@@ -453,7 +867,7 @@ mod tests {
         };
 
         validate_transfer(deps.as_ref(), &tx).unwrap();
-        let evt = commit_transfer(deps.as_mut(), tx.clone()).unwrap();
+        let evt = commit_transfer(deps.as_mut(), &mock_env(), tx.clone()).unwrap();
 
         let src = account::Key::new(
             tx.key.emitter_chain(),
@@ -477,6 +891,23 @@ mod tests {
         assert_eq!(tx.data, query_transfer(deps.as_ref(), tx.key).unwrap());
     }
 
+    #[test]
+    fn transfer_event_round_trip() {
+        let mut deps = mock_dependencies();
+        let tx = Transfer {
+            key: transfer::Key::new(3, [1u8; 32].into(), 5),
+            data: transfer::Data {
+                amount: Uint256::from(400u128),
+                token_chain: 3,
+                token_address: [3u8; 32].into(),
+                recipient_chain: 9,
+            },
+        };
+
+        let evt = commit_transfer(deps.as_mut(), &mock_env(), tx.clone()).unwrap();
+        assert_eq!(tx, cw_transcode::from_event(&evt).unwrap());
+    }
+
     #[test]
     fn local_native_transfer() {
         let mut deps = mock_dependencies();
@@ -491,7 +922,7 @@ mod tests {
         };
 
         validate_transfer(deps.as_ref(), &tx).unwrap();
-        commit_transfer(deps.as_mut(), tx.clone()).unwrap();
+        commit_transfer(deps.as_mut(), &mock_env(), tx.clone()).unwrap();
 
         // Since we transfered within the same chain, the balance should be 0.
         let src = account::Key::new(
@@ -531,7 +962,7 @@ mod tests {
             TransferError::MissingWrappedAccount
         ));
 
-        let e = commit_transfer(deps.as_mut(), tx.clone())
+        let e = commit_transfer(deps.as_mut(), &mock_env(), tx.clone())
             .expect_err("successfully committed duplicate transfer");
         assert!(matches!(
             e.downcast().unwrap(),
@@ -549,7 +980,7 @@ mod tests {
             amount: wrapped,
             reason: "test".into(),
         };
-        modify_balance(deps.as_mut(), m).unwrap();
+        modify_balance(deps.as_mut(), &mock_env(), m).unwrap();
 
         // The transfer should still fail because we're trying to move more wrapped tokens than
         // were issued.
@@ -559,7 +990,7 @@ mod tests {
             TransferError::InsufficientSourceBalance
         ));
 
-        let e = commit_transfer(deps.as_mut(), tx.clone())
+        let e = commit_transfer(deps.as_mut(), &mock_env(), tx.clone())
             .expect_err("successfully committed duplicate transfer");
         assert!(matches!(
             e.downcast().unwrap(),
@@ -571,7 +1002,7 @@ mod tests {
 
         // Now the transfer should be fine.
         validate_transfer(deps.as_ref(), &tx).unwrap();
-        commit_transfer(deps.as_mut(), tx.clone()).unwrap();
+        commit_transfer(deps.as_mut(), &mock_env(), tx.clone()).unwrap();
 
         // The balance should not have changed.
         let src = account::Key::new(
@@ -605,7 +1036,7 @@ mod tests {
         };
 
         validate_transfer(deps.as_ref(), &tx).unwrap();
-        commit_transfer(deps.as_mut(), tx.clone()).unwrap();
+        commit_transfer(deps.as_mut(), &mock_env(), tx.clone()).unwrap();
 
         // Repeating the transfer should return an error and should not change the balances.
         let e = validate_transfer(deps.as_ref(), &tx).unwrap_err();
@@ -614,7 +1045,7 @@ mod tests {
             TransferError::DuplicateTransfer
         ));
 
-        let e = commit_transfer(deps.as_mut(), tx.clone())
+        let e = commit_transfer(deps.as_mut(), &mock_env(), tx.clone())
             .expect_err("successfully committed duplicate transfer");
         assert!(matches!(
             e.downcast().unwrap(),
@@ -652,7 +1083,7 @@ mod tests {
         };
 
         validate_transfer(deps.as_ref(), &tx).unwrap();
-        commit_transfer(deps.as_mut(), tx.clone()).unwrap();
+        commit_transfer(deps.as_mut(), &mock_env(), tx.clone()).unwrap();
 
         let rx = Transfer {
             key: transfer::Key::new(tx.data.recipient_chain, [6u8; 32].into(), 2),
@@ -665,7 +1096,7 @@ mod tests {
         };
 
         validate_transfer(deps.as_ref(), &rx).unwrap();
-        commit_transfer(deps.as_mut(), rx.clone()).unwrap();
+        commit_transfer(deps.as_mut(), &mock_env(), rx.clone()).unwrap();
 
         let src = account::Key::new(
             tx.key.emitter_chain(),
@@ -703,7 +1134,7 @@ mod tests {
             e.downcast().unwrap(),
             TransferError::MissingWrappedAccount
         ));
-        let e = commit_transfer(deps.as_mut(), tx)
+        let e = commit_transfer(deps.as_mut(), &mock_env(), tx)
             .expect_err("successfully committed transfer with missing wrapped account");
         assert!(matches!(
             e.downcast().unwrap(),
@@ -734,6 +1165,7 @@ mod tests {
                     tx.data.token_address,
                 ),
                 &tx.data.amount.into(),
+                mock_env().block.height,
             )
             .unwrap();
 
@@ -743,7 +1175,7 @@ mod tests {
             TransferError::MissingNativeAccount
         ));
 
-        let e = commit_transfer(deps.as_mut(), tx)
+        let e = commit_transfer(deps.as_mut(), &mock_env(), tx)
             .expect_err("successfully committed transfer with missing native account");
         assert!(matches!(
             e.downcast().unwrap(),
@@ -771,7 +1203,7 @@ mod tests {
             };
 
             validate_transfer(deps.as_ref(), &tx).unwrap();
-            commit_transfer(deps.as_mut(), tx).unwrap();
+            commit_transfer(deps.as_mut(), &mock_env(), tx).unwrap();
         }
 
         let src = account::Key::new(emitter_chain, data.token_chain, data.token_address);
@@ -803,7 +1235,7 @@ mod tests {
         };
 
         validate_transfer(deps.as_ref(), &tx).unwrap();
-        commit_transfer(deps.as_mut(), tx.clone()).unwrap();
+        commit_transfer(deps.as_mut(), &mock_env(), tx.clone()).unwrap();
 
         // Now transfer some of the wrapped tokens to a new chain.
         let wrapped = Transfer {
@@ -817,7 +1249,7 @@ mod tests {
         };
 
         validate_transfer(deps.as_ref(), &wrapped).unwrap();
-        commit_transfer(deps.as_mut(), wrapped.clone()).unwrap();
+        commit_transfer(deps.as_mut(), &mock_env(), wrapped.clone()).unwrap();
 
         // The balance on the original chain should not have changed.
         let src = account::Key::new(
@@ -864,7 +1296,7 @@ mod tests {
         };
 
         validate_transfer(deps.as_ref(), &tx).unwrap();
-        commit_transfer(deps.as_mut(), tx.clone()).unwrap();
+        commit_transfer(deps.as_mut(), &mock_env(), tx.clone()).unwrap();
 
         // Now try to transfer back more tokens than were originally sent.
         let rx = Transfer {
@@ -883,7 +1315,7 @@ mod tests {
             TransferError::InsufficientSourceBalance
         ));
 
-        let e = commit_transfer(deps.as_mut(), rx)
+        let e = commit_transfer(deps.as_mut(), &mock_env(), rx)
             .expect_err("successfully transferred more tokens than available");
         assert!(matches!(
             e.downcast().unwrap(),
@@ -905,7 +1337,7 @@ mod tests {
         };
 
         validate_transfer(deps.as_ref(), &tx).unwrap();
-        commit_transfer(deps.as_mut(), tx.clone()).unwrap();
+        commit_transfer(deps.as_mut(), &mock_env(), tx.clone()).unwrap();
 
         // Artificially increase the wrapped balance so that the check for wrapped tokens passes.
         ACCOUNTS
@@ -916,6 +1348,7 @@ mod tests {
                     tx.data.token_chain,
                     tx.data.token_address,
                 ),
+                mock_env().block.height,
                 |b| {
                     b.unwrap()
                         .checked_mul(Uint256::from(2u128))
@@ -942,7 +1375,7 @@ mod tests {
             TransferError::InsufficientDestinationBalance
         ));
 
-        let e = commit_transfer(deps.as_mut(), rx)
+        let e = commit_transfer(deps.as_mut(), &mock_env(), rx)
             .expect_err("successfully transferred more tokens than available");
         assert!(matches!(
             e.downcast().unwrap(),
@@ -963,7 +1396,7 @@ mod tests {
             reason: "test".into(),
         };
 
-        let evt = modify_balance(deps.as_mut(), m.clone()).unwrap();
+        let evt = modify_balance(deps.as_mut(), &mock_env(), m.clone()).unwrap();
 
         let acc = account::Key::new(m.chain_id, m.token_chain, m.token_address);
         assert_eq!(m.amount, *query_balance(deps.as_ref(), acc).unwrap());
@@ -987,6 +1420,23 @@ mod tests {
         assert_eq!(expected, evt);
     }
 
+    #[test]
+    fn modification_event_round_trip() {
+        let mut deps = mock_dependencies();
+        let m = Modification {
+            sequence: 0,
+            chain_id: 1,
+            token_chain: 2,
+            token_address: [3u8; 32].into(),
+            kind: Kind::Add,
+            amount: Uint256::from(4u128),
+            reason: "test".into(),
+        };
+
+        let evt = modify_balance(deps.as_mut(), &mock_env(), m.clone()).unwrap();
+        assert_eq!(m, cw_transcode::from_event(&evt).unwrap());
+    }
+
     #[test]
     fn duplicate_modify() {
         let mut deps = mock_dependencies();
@@ -1000,10 +1450,11 @@ mod tests {
             reason: "test".into(),
         };
 
-        modify_balance(deps.as_mut(), m.clone()).unwrap();
+        modify_balance(deps.as_mut(), &mock_env(), m.clone()).unwrap();
 
         // Trying the same modification again should fail.
-        let e = modify_balance(deps.as_mut(), m).expect_err("successfully modified balance twice");
+        let e = modify_balance(deps.as_mut(), &mock_env(), m)
+            .expect_err("successfully modified balance twice");
         assert!(matches!(
             e.downcast().unwrap(),
             ModifyBalanceError::DuplicateModification
@@ -1023,11 +1474,11 @@ mod tests {
             reason: "test".into(),
         };
 
-        modify_balance(deps.as_mut(), m.clone()).unwrap();
+        modify_balance(deps.as_mut(), &mock_env(), m.clone()).unwrap();
 
         m.sequence += 1;
         m.kind = Kind::Sub;
-        modify_balance(deps.as_mut(), m.clone()).unwrap();
+        modify_balance(deps.as_mut(), &mock_env(), m.clone()).unwrap();
 
         let acc = account::Key::new(m.chain_id, m.token_chain, m.token_address);
         assert_eq!(Balance::zero(), query_balance(deps.as_ref(), acc).unwrap());
@@ -1049,7 +1500,7 @@ mod tests {
 
         for i in 0..ITERATIONS {
             m.sequence = i;
-            modify_balance(deps.as_mut(), m.clone()).unwrap();
+            modify_balance(deps.as_mut(), &mock_env(), m.clone()).unwrap();
         }
 
         let acc = account::Key::new(m.chain_id, m.token_chain, m.token_address);
@@ -1072,7 +1523,7 @@ mod tests {
             reason: "test".into(),
         };
 
-        let e = modify_balance(deps.as_mut(), m)
+        let e = modify_balance(deps.as_mut(), &mock_env(), m)
             .expect_err("successfully modified account with insufficient balance");
         assert!(matches!(
             e.downcast().unwrap(),
@@ -1161,6 +1612,77 @@ mod tests {
         }
     }
 
+    // Adds `amount` to the account for `chain` at the given block height.
+    fn add_balance_at(deps: DepsMut, chain: u16, sequence: u64, amount: u128, height: u64) {
+        let mut env = mock_env();
+        env.block.height = height;
+        let m = Modification {
+            sequence,
+            chain_id: chain,
+            token_chain: chain,
+            token_address: [chain as u8; 32].into(),
+            kind: Kind::Add,
+            amount: Uint256::from(amount),
+            reason: "test".into(),
+        };
+        modify_balance(deps, &env, m).unwrap();
+    }
+
+    #[test]
+    fn query_balance_at_height() {
+        let mut deps = mock_dependencies();
+        let start = mock_env().block.height;
+        let key = account::Key::new(1, 1, [1u8; 32].into());
+
+        add_balance_at(deps.as_mut(), 1, 0, 100, start);
+        add_balance_at(deps.as_mut(), 1, 1, 200, start + 1);
+        add_balance_at(deps.as_mut(), 1, 2, 300, start + 1);
+        add_balance_at(deps.as_mut(), 1, 3, 400, start + 3);
+
+        // Balances are reported as of the start of the block so changes made in that block are
+        // not included.
+        query_balance_at(deps.as_ref(), key.clone(), start)
+            .expect_err("successfully queried account before it was created");
+        for (height, expected) in [(1, 100u128), (2, 600), (3, 600), (4, 1000), (100, 1000)] {
+            let balance = query_balance_at(deps.as_ref(), key.clone(), start + height).unwrap();
+            assert_eq!(Balance::new(Uint256::from(expected)), balance);
+        }
+    }
+
+    #[test]
+    fn query_all_balances_at_height() {
+        let mut deps = mock_dependencies();
+        let start = mock_env().block.height;
+        let count = 4;
+        for i in 0..count {
+            add_balance_at(deps.as_mut(), i as u16, i, 100, start + i);
+            add_balance_at(deps.as_mut(), 0, count + i, 100, start + i);
+        }
+
+        for i in 0..=count {
+            let found = query_all_accounts_at(deps.as_ref(), None, start + i)
+                .collect::<StdResult<Vec<_>>>()
+                .unwrap();
+            assert_eq!(i as usize, found.len());
+
+            for (chain, acc) in found.into_iter().enumerate() {
+                assert_eq!(chain as u16, acc.key.chain_id());
+                let expected = if chain == 0 { 100 * (i + 1) } else { 100 };
+                assert_eq!(Balance::new(Uint256::from(expected as u128)), acc.balance);
+            }
+        }
+
+        let found = query_all_accounts_at(
+            deps.as_ref(),
+            Some(account::Key::new(1, 1, [1u8; 32].into())),
+            start + 3,
+        )
+        .collect::<StdResult<Vec<_>>>()
+        .unwrap();
+        assert_eq!(1, found.len());
+        assert_eq!(2, found[0].key.chain_id());
+    }
+
     #[test]
     fn query_transfer_data() {
         let mut deps = mock_dependencies();
@@ -1311,4 +1833,562 @@ mod tests {
             }
         }
     }
+
+    fn commit_transfers(mut deps: DepsMut, count: usize) -> Vec<Transfer> {
+        let transfers = create_transfers(count);
+        for t in &transfers {
+            commit_transfer(deps.branch(), &mock_env(), t.clone()).unwrap();
+        }
+
+        transfers
+    }
+
+    #[test]
+    fn simple_revert() {
+        let mut deps = mock_dependencies();
+        let tx = Transfer {
+            key: transfer::Key::new(3, [1u8; 32].into(), 5),
+            data: transfer::Data {
+                amount: Uint256::from(400u128),
+                token_chain: 3,
+                token_address: [3u8; 32].into(),
+                recipient_chain: 9,
+            },
+        };
+
+        commit_transfer(deps.as_mut(), &mock_env(), tx.clone()).unwrap();
+        assert!(!is_reverted(deps.as_ref(), tx.key.clone()));
+
+        let r = Reversion {
+            key: tx.key.clone(),
+            reason: "test".into(),
+        };
+        let evt = revert_transfer(deps.as_mut(), &mock_env(), r.clone()).unwrap();
+
+        let src = account::Key::new(
+            tx.key.emitter_chain(),
+            tx.data.token_chain,
+            tx.data.token_address,
+        );
+        assert_eq!(Balance::zero(), query_balance(deps.as_ref(), src).unwrap());
+
+        let dst = account::Key::new(
+            tx.data.recipient_chain,
+            tx.data.token_chain,
+            tx.data.token_address,
+        );
+        assert_eq!(Balance::zero(), query_balance(deps.as_ref(), dst).unwrap());
+
+        // The transfer is still recorded so it cannot be committed again.
+        assert_eq!(
+            tx.data,
+            query_transfer(deps.as_ref(), tx.key.clone()).unwrap()
+        );
+        let err = validate_transfer(deps.as_ref(), &tx).unwrap_err();
+        assert!(matches!(
+            err.downcast().unwrap(),
+            TransferError::DuplicateTransfer
+        ));
+
+        assert!(is_reverted(deps.as_ref(), tx.key.clone()));
+        assert_eq!(r, query_reversion(deps.as_ref(), tx.key).unwrap());
+
+        let expected = Event::new("Reversion")
+            .add_attribute("key", serde_json_wasm::to_string(&r.key).unwrap())
+            .add_attribute("reason", serde_json_wasm::to_string(&r.reason).unwrap());
+        assert_eq!(expected, evt);
+    }
+
+    #[test]
+    fn reversion_event_round_trip() {
+        let mut deps = mock_dependencies();
+        let tx = Transfer {
+            key: transfer::Key::new(3, [1u8; 32].into(), 5),
+            data: transfer::Data {
+                amount: Uint256::from(400u128),
+                token_chain: 3,
+                token_address: [3u8; 32].into(),
+                recipient_chain: 9,
+            },
+        };
+        commit_transfer(deps.as_mut(), &mock_env(), tx.clone()).unwrap();
+
+        let r = Reversion {
+            key: tx.key,
+            reason: "test".into(),
+        };
+        let evt = revert_transfer(deps.as_mut(), &mock_env(), r.clone()).unwrap();
+        assert_eq!(r, cw_transcode::from_event(&evt).unwrap());
+    }
+
+    #[test]
+    fn revert_self_transfer() {
+        let mut deps = mock_dependencies();
+        let tx = Transfer {
+            key: transfer::Key::new(3, [1u8; 32].into(), 5),
+            data: transfer::Data {
+                amount: Uint256::from(400u128),
+                token_chain: 3,
+                token_address: [3u8; 32].into(),
+                recipient_chain: 3,
+            },
+        };
+
+        commit_transfer(deps.as_mut(), &mock_env(), tx.clone()).unwrap();
+
+        let r = Reversion {
+            key: tx.key.clone(),
+            reason: "test".into(),
+        };
+        revert_transfer(deps.as_mut(), &mock_env(), r).unwrap();
+
+        let acc = account::Key::new(
+            tx.key.emitter_chain(),
+            tx.data.token_chain,
+            tx.data.token_address,
+        );
+        assert_eq!(Balance::zero(), query_balance(deps.as_ref(), acc).unwrap());
+    }
+
+    #[test]
+    fn revert_missing_transfer() {
+        let mut deps = mock_dependencies();
+        let r = Reversion {
+            key: transfer::Key::new(3, [1u8; 32].into(), 5),
+            reason: "test".into(),
+        };
+
+        let err = revert_transfer(deps.as_mut(), &mock_env(), r.clone()).unwrap_err();
+        assert!(matches!(
+            err.downcast().unwrap(),
+            RevertTransferError::MissingTransfer
+        ));
+        assert!(!is_reverted(deps.as_ref(), r.key));
+    }
+
+    #[test]
+    fn duplicate_revert() {
+        let mut deps = mock_dependencies();
+        let tx = commit_transfers(deps.as_mut(), 1).remove(0);
+
+        let r = Reversion {
+            key: tx.key,
+            reason: "test".into(),
+        };
+        revert_transfer(deps.as_mut(), &mock_env(), r.clone()).unwrap();
+
+        let err = revert_transfer(deps.as_mut(), &mock_env(), r).unwrap_err();
+        assert!(matches!(
+            err.downcast().unwrap(),
+            RevertTransferError::DuplicateReversion
+        ));
+    }
+
+    #[test]
+    fn revert_insufficient_balance() {
+        let mut deps = mock_dependencies();
+        let tx = Transfer {
+            key: transfer::Key::new(3, [1u8; 32].into(), 5),
+            data: transfer::Data {
+                amount: Uint256::from(400u128),
+                token_chain: 3,
+                token_address: [3u8; 32].into(),
+                recipient_chain: 9,
+            },
+        };
+        commit_transfer(deps.as_mut(), &mock_env(), tx.clone()).unwrap();
+
+        // Move the wrapped tokens to another chain so that they can no longer be burned on the
+        // destination chain.
+        let rx = Transfer {
+            key: transfer::Key::new(tx.data.recipient_chain, [6u8; 32].into(), 2),
+            data: transfer::Data {
+                amount: tx.data.amount,
+                token_chain: tx.data.token_chain,
+                token_address: tx.data.token_address,
+                recipient_chain: 11,
+            },
+        };
+        commit_transfer(deps.as_mut(), &mock_env(), rx).unwrap();
+
+        let r = Reversion {
+            key: tx.key.clone(),
+            reason: "test".into(),
+        };
+        let err = revert_transfer(deps.as_mut(), &mock_env(), r).unwrap_err();
+        assert!(matches!(
+            err.downcast().unwrap(),
+            RevertTransferError::InsufficientDestinationBalance
+        ));
+        assert!(!is_reverted(deps.as_ref(), tx.key));
+    }
+
+    #[test]
+    fn query_all_reversion_data() {
+        let mut deps = mock_dependencies();
+        let count = 3;
+        let transfers = commit_transfers(deps.as_mut(), count);
+
+        let mut reversions = transfers
+            .into_iter()
+            .map(|t| Reversion {
+                key: t.key,
+                reason: "test".into(),
+            })
+            .collect::<Vec<_>>();
+        reversions.sort();
+
+        for r in &reversions {
+            revert_transfer(deps.as_mut(), &mock_env(), r.clone()).unwrap();
+        }
+
+        let found = query_all_reversions(deps.as_ref(), None)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(reversions, found);
+
+        let found = query_all_reversions(deps.as_ref(), Some(reversions[0].key.clone()))
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(reversions[1..], found);
+    }
+
+    fn wrapped_account(token_chain: u16) -> account::Key {
+        account::Key::new(9, token_chain, [token_chain as u8; 32].into())
+    }
+
+    #[test]
+    fn invariants_hold_after_transfers() {
+        let mut deps = mock_dependencies();
+        commit_transfers(deps.as_mut(), 3);
+
+        let tx = Transfer {
+            key: transfer::Key::new(3, [1u8; 32].into(), 5),
+            data: transfer::Data {
+                amount: Uint256::from(400u128),
+                token_chain: 3,
+                token_address: [3u8; 32].into(),
+                recipient_chain: 9,
+            },
+        };
+        commit_transfer(deps.as_mut(), &mock_env(), tx).unwrap();
+
+        let (violations, last_key) = check_invariants(deps.as_ref(), None, usize::MAX).unwrap();
+        assert!(violations.is_empty());
+        assert_eq!(None, last_key);
+    }
+
+    #[test]
+    fn invariant_violations() {
+        let mut deps = mock_dependencies();
+
+        // Mint wrapped tokens without locking any on the native chain.
+        for token_chain in 1..=3 {
+            save_account(
+                &mut deps.storage,
+                wrapped_account(token_chain),
+                &Uint256::from(100u128).into(),
+                mock_env().block.height,
+            )
+            .unwrap();
+        }
+
+        let (violations, _) = check_invariants(deps.as_ref(), None, usize::MAX).unwrap();
+        assert_eq!(3, violations.len());
+        assert_eq!(
+            InvariantViolation {
+                token_chain: 1,
+                token_address: [1u8; 32].into(),
+                locked: Uint256::zero(),
+                wrapped: Uint256::from(100u128),
+                adjusted_locked: Uint256::zero(),
+                adjusted_wrapped: Uint256::from(100u128),
+            },
+            violations[0]
+        );
+
+        let (found, _) =
+            check_invariants(deps.as_ref(), Some((1, [1u8; 32].into())), usize::MAX).unwrap();
+        assert_eq!(violations[1..], found);
+    }
+
+    #[test]
+    fn paginate_invariant_checks() {
+        let mut deps = mock_dependencies();
+        commit_transfers(deps.as_mut(), 3);
+
+        // Only the token on chain 1 violates the invariant.
+        save_account(
+            &mut deps.storage,
+            wrapped_account(1),
+            &Uint256::from(100u128).into(),
+            mock_env().block.height,
+        )
+        .unwrap();
+
+        let mut checked = Vec::new();
+        let mut found = Vec::new();
+        let mut start_after = None;
+        loop {
+            let (violations, last_key) = check_invariants(deps.as_ref(), start_after, 1).unwrap();
+            found.extend(violations);
+            match last_key {
+                Some(k) => {
+                    checked.push(k);
+                    start_after = Some(k);
+                }
+                None => break,
+            }
+        }
+
+        let mut tokens = TOKEN_ACCOUNTS
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .map(|k| k.map(|(token_chain, token_address, _)| (token_chain, token_address)))
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        tokens.dedup();
+        assert_eq!(tokens, checked);
+
+        assert_eq!(1, found.len());
+        assert_eq!(1, found[0].token_chain);
+        assert_eq!(
+            found,
+            check_invariants(deps.as_ref(), None, usize::MAX).unwrap().0
+        );
+    }
+
+    #[test]
+    fn zero_invariant_limit() {
+        let mut deps = mock_dependencies();
+        commit_transfers(deps.as_mut(), 3);
+
+        // A limit of 0 must not report that every token has been checked.
+        let (_, last_key) = check_invariants(deps.as_ref(), None, 0).unwrap();
+        let (_, expected) = check_invariants(deps.as_ref(), None, 1).unwrap();
+        assert!(last_key.is_some());
+        assert_eq!(expected, last_key);
+    }
+
+    #[test]
+    fn backfill_invariants() {
+        let mut deps = mock_dependencies();
+
+        // Accounts and modifications saved before the per-token state existed.
+        for (sequence, token_chain) in [(0, 1), (2, 2)] {
+            let key = wrapped_account(token_chain);
+            ACCOUNTS
+                .save(
+                    &mut deps.storage,
+                    key.clone(),
+                    &Uint256::from(150u128).into(),
+                    mock_env().block.height,
+                )
+                .unwrap();
+            let m = Modification {
+                sequence,
+                chain_id: key.chain_id(),
+                token_chain: key.token_chain(),
+                token_address: *key.token_address(),
+                kind: Kind::Add,
+                amount: Uint256::from(100u128),
+                reason: "test".into(),
+            };
+            MODIFICATIONS
+                .save(&mut deps.storage, m.sequence, &m)
+                .unwrap();
+        }
+
+        start_invariant_backfill(&mut deps.storage).unwrap();
+        check_invariants(deps.as_ref(), None, usize::MAX)
+            .expect_err("checked invariants before the backfill was complete");
+        assert!(!backfill_invariant_state(deps.as_mut(), 1).unwrap());
+
+        // A modification made while the backfill is running must only be counted once.
+        let key = wrapped_account(1);
+        let m = Modification {
+            sequence: 1,
+            chain_id: key.chain_id(),
+            token_chain: key.token_chain(),
+            token_address: *key.token_address(),
+            kind: Kind::Add,
+            amount: Uint256::from(50u128),
+            reason: "test".into(),
+        };
+        modify_balance(deps.as_mut(), &mock_env(), m).unwrap();
+
+        let mut calls = 1;
+        loop {
+            calls += 1;
+            if backfill_invariant_state(deps.as_mut(), 1).unwrap() {
+                break;
+            }
+        }
+        // One call for each of the 2 accounts and 3 modifications plus one more to find the end of
+        // each.
+        assert_eq!(7, calls);
+
+        // Starting again once the state is complete has no effect.
+        start_invariant_backfill(&mut deps.storage).unwrap();
+        assert!(backfill_invariant_state(deps.as_mut(), 1).unwrap());
+
+        let (violations, _) = check_invariants(deps.as_ref(), None, usize::MAX).unwrap();
+        assert_eq!(2, violations.len());
+        assert_eq!(Uint256::from(200u128), violations[0].wrapped);
+        assert_eq!(Uint256::from(150u128), violations[0].adjusted_locked);
+        assert_eq!(Uint256::from(150u128), violations[1].wrapped);
+        assert_eq!(Uint256::from(100u128), violations[1].adjusted_locked);
+    }
+
+    #[test]
+    fn invariants_include_modifications() {
+        let mut deps = mock_dependencies();
+        let key = wrapped_account(1);
+
+        let m = Modification {
+            sequence: 0,
+            chain_id: key.chain_id(),
+            token_chain: key.token_chain(),
+            token_address: *key.token_address(),
+            kind: Kind::Add,
+            amount: Uint256::from(100u128),
+            reason: "test".into(),
+        };
+        modify_balance(deps.as_mut(), &mock_env(), m).unwrap();
+        let (violations, last_key) = check_invariants(deps.as_ref(), None, usize::MAX).unwrap();
+        assert!(violations.is_empty());
+        assert_eq!(None, last_key);
+
+        // Changing the balance without a modification should be caught.
+        ACCOUNTS
+            .save(
+                &mut deps.storage,
+                key,
+                &Uint256::from(150u128).into(),
+                mock_env().block.height,
+            )
+            .unwrap();
+        let (violations, _) = check_invariants(deps.as_ref(), None, usize::MAX).unwrap();
+        assert_eq!(1, violations.len());
+        assert_eq!(Uint256::from(150u128), violations[0].wrapped);
+        assert_eq!(Uint256::from(100u128), violations[0].adjusted_locked);
+        assert_eq!(Uint256::from(150u128), violations[0].adjusted_wrapped);
+    }
+
+    // The native (chain, address) pairs of the tokens used by the property tests.
+    const TOKENS: [(u16, [u8; 32]); 2] = [(1, [1; 32]), (2, [2; 32])];
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Transfer {
+            emitter_chain: u16,
+            token: usize,
+            recipient_chain: u16,
+            amount: u128,
+        },
+        Modify {
+            chain_id: u16,
+            token: usize,
+            kind: Kind,
+            amount: u128,
+        },
+        // Revert one of the previously committed transfers.
+        Revert(usize),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        let chain = || 1u16..=3;
+        let token = || 0..TOKENS.len();
+        let kind = prop_oneof![Just(Kind::Add), Just(Kind::Sub)];
+
+        prop_oneof![
+            4 => (chain(), token(), chain(), 0u128..1000).prop_map(
+                |(emitter_chain, token, recipient_chain, amount)| Op::Transfer {
+                    emitter_chain,
+                    token,
+                    recipient_chain,
+                    amount,
+                }
+            ),
+            1 => (chain(), token(), kind, 0u128..1000).prop_map(
+                |(chain_id, token, kind, amount)| Op::Modify {
+                    chain_id,
+                    token,
+                    kind,
+                    amount,
+                }
+            ),
+            1 => any::<usize>().prop_map(Op::Revert),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn invariants_hold(ops in prop::collection::vec(op(), 1..64)) {
+            let mut deps = mock_dependencies();
+            let mut committed = Vec::new();
+
+            for (i, op) in ops.into_iter().enumerate() {
+                let sequence = i as u64;
+
+                // Most of these will fail, e.g. because there is not enough balance, which is fine
+                // since failures must not affect the invariant either.
+                match op {
+                    Op::Transfer {
+                        emitter_chain,
+                        token,
+                        recipient_chain,
+                        amount,
+                    } => {
+                        let (token_chain, token_address) = TOKENS[token];
+                        let tx = Transfer {
+                            key: transfer::Key::new(
+                                emitter_chain,
+                                [emitter_chain as u8; 32].into(),
+                                sequence,
+                            ),
+                            data: transfer::Data {
+                                amount: Uint256::from(amount),
+                                token_chain,
+                                token_address: token_address.into(),
+                                recipient_chain,
+                            },
+                        };
+                        if commit_transfer(deps.as_mut(), &mock_env(), tx.clone()).is_ok() {
+                            committed.push(tx.key);
+                        }
+                    }
+                    Op::Modify {
+                        chain_id,
+                        token,
+                        kind,
+                        amount,
+                    } => {
+                        let (token_chain, token_address) = TOKENS[token];
+                        let m = Modification {
+                            sequence,
+                            chain_id,
+                            token_chain,
+                            token_address: token_address.into(),
+                            kind,
+                            amount: Uint256::from(amount),
+                            reason: "test".into(),
+                        };
+                        let _ = modify_balance(deps.as_mut(), &mock_env(), m);
+                    }
+                    Op::Revert(idx) => {
+                        if !committed.is_empty() {
+                            let r = Reversion {
+                                key: committed.swap_remove(idx % committed.len()),
+                                reason: "test".into(),
+                            };
+                            let _ = revert_transfer(deps.as_mut(), &mock_env(), r);
+                        }
+                    }
+                }
+
+                let (violations, _) = check_invariants(deps.as_ref(), None, usize::MAX).unwrap();
+                prop_assert!(violations.is_empty(), "{:?}", violations);
+            }
+        }
+    }
 }
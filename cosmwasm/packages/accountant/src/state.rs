@@ -1,8 +1,8 @@
 use std::fmt;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Uint256;
-use cw_storage_plus::Map;
+use cosmwasm_std::{Empty, OverflowError, Uint256};
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
 
 pub mod account;
 mod addr;
@@ -12,10 +12,36 @@ pub use account::Account;
 pub use addr::TokenAddress;
 pub use transfer::Transfer;
 
-pub const ACCOUNTS: Map<account::Key, account::Balance> = Map::new("accountant/accounts");
+/// Account balances.  Every change is recorded in a changelog keyed by block height so that
+/// balances can also be queried as of an earlier block.  Only changes made after the accounts were
+/// moved into a `SnapshotMap` are recorded.
+pub const ACCOUNTS: SnapshotMap<account::Key, account::Balance> = SnapshotMap::new(
+    "accountant/accounts",
+    "accountant/accounts__checkpoints",
+    "accountant/accounts__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Every account in `ACCOUNTS`, keyed by `(token_chain, token_address, chain_id)` so that the
+/// accounts for a single token can be read without scanning all of `ACCOUNTS`.
+pub const TOKEN_ACCOUNTS: Map<(u16, TokenAddress, u16), Empty> =
+    Map::new("accountant/token_accounts");
 
 pub const TRANSFERS: Map<transfer::Key, transfer::Data> = Map::new("accountant/transfers");
 
+/// How far the per-token state in `TOKEN_ACCOUNTS` and `MODIFICATION_TOTALS` has been rebuilt from
+/// the accounts and modifications that were saved before that state existed.
+pub const INVARIANT_BACKFILL: Item<InvariantBackfill> = Item::new("accountant/invariant_backfill");
+
+#[cw_serde]
+pub enum InvariantBackfill {
+    // Every account after `start_after` still needs to be added to `TOKEN_ACCOUNTS`.
+    Accounts { start_after: Option<account::Key> },
+    // Every modification after `start_after` still needs to be added to `MODIFICATION_TOTALS`.
+    Modifications { start_after: Option<u64> },
+    Complete,
+}
+
 #[cw_serde]
 #[derive(Eq, PartialOrd, Ord)]
 pub enum Kind {
@@ -53,3 +79,70 @@ pub struct Modification {
 }
 
 pub const MODIFICATIONS: Map<u64, Modification> = Map::new("accountant/modifications");
+
+/// The combined effect of every modification to the accounts of each token, keyed by
+/// `(token_chain, token_address)`.
+pub const MODIFICATION_TOTALS: Map<(u16, TokenAddress), ModificationTotal> =
+    Map::new("accountant/modification_totals");
+
+/// The amounts that modifications add to each side of the accountant's core invariant for a single
+/// token.  See `InvariantViolation`.
+#[cw_serde]
+#[derive(Default, Eq)]
+pub struct ModificationTotal {
+    // The amount subtracted from the native account or added to wrapped accounts.
+    pub locked: Uint256,
+    // The amount added to the native account or subtracted from wrapped accounts.
+    pub wrapped: Uint256,
+}
+
+impl ModificationTotal {
+    /// Adds the effect of `m` to this total.  Rather than subtracting the amount of a modification
+    /// from one side of the invariant, which may underflow, it is added to the other side.
+    pub fn add(&mut self, m: &Modification) -> Result<(), OverflowError> {
+        match (m.chain_id == m.token_chain, &m.kind) {
+            (true, Kind::Add) | (false, Kind::Sub) => {
+                self.wrapped = self.wrapped.checked_add(m.amount)?;
+            }
+            (true, Kind::Sub) | (false, Kind::Add) => {
+                self.locked = self.locked.checked_add(m.amount)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cw_serde]
+#[derive(Eq, PartialOrd, Ord)]
+pub struct Reversion {
+    // The key of the committed transfer that was reverted.
+    pub key: transfer::Key,
+    // A human-readable reason for the reversion.
+    pub reason: String,
+}
+
+pub const REVERSIONS: Map<transfer::Key, Reversion> = Map::new("accountant/reversions");
+
+/// A token whose balances do not satisfy the accountant's core invariant: the amount locked on the
+/// token's native chain must equal the amount issued on all other chains, once balance
+/// modifications are taken into account.  The invariant holds when `adjusted_locked` and
+/// `adjusted_wrapped` are equal.
+#[cw_serde]
+#[derive(Eq)]
+pub struct InvariantViolation {
+    // The chain id of the native chain for the token.
+    pub token_chain: u16,
+    // The address of the token on its native chain.
+    pub token_address: TokenAddress,
+    // The balance of the account on the token's native chain.
+    pub locked: Uint256,
+    // The sum of the balances of the accounts on all other chains.
+    pub wrapped: Uint256,
+    // `locked` plus the amounts that modifications subtracted from the native account or added to
+    // wrapped accounts.
+    pub adjusted_locked: Uint256,
+    // `wrapped` plus the amounts that modifications added to the native account or subtracted from
+    // wrapped accounts.
+    pub adjusted_wrapped: Uint256,
+}
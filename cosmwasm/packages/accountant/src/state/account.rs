@@ -2,7 +2,7 @@ use std::ops::{Deref, DerefMut};
 
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{StdResult, Uint256};
-use cw_storage_plus::{Key as CwKey, KeyDeserialize, PrimaryKey};
+use cw_storage_plus::{Key as CwKey, KeyDeserialize, Prefixer, PrimaryKey};
 
 use crate::state::TokenAddress;
 
@@ -97,6 +97,12 @@ impl PrimaryKey<'_> for Key {
     }
 }
 
+impl Prefixer<'_> for Key {
+    fn prefix(&self) -> Vec<CwKey> {
+        self.key()
+    }
+}
+
 #[cw_serde]
 pub struct Balance(Uint256);
 
@@ -0,0 +1,147 @@
+use cosmwasm_std::{Attribute, Event};
+use serde::de::{self, DeserializeOwned, IgnoredAny, Visitor};
+
+use crate::Error;
+
+// wasmd prefixes the type of every event emitted by a contract with this string.
+const WASM_PREFIX: &str = "wasm-";
+
+// The attribute that wasmd adds to every event emitted by a contract.
+const CONTRACT_ADDRESS_KEY: &str = "_contract_address";
+
+// The top-level shape of a type, as requested by its `Deserialize` impl.
+enum Shape {
+    Struct(&'static str),
+    UnitStruct(&'static str),
+    Enum(&'static str),
+}
+
+// A `Deserializer` that records the first struct or enum requested by a `Deserialize` impl and
+// then bails out.  This lets us check the event type against the name of `T` before converting
+// the event attributes into json.
+struct Probe(Option<Shape>);
+
+impl<'de> de::Deserializer<'de> for &mut Probe {
+    type Error = Error;
+
+    #[inline]
+    fn deserialize_any<V>(self, _: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::NotAStruct)
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit_struct<V>(self, name: &'static str, _: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0 = Some(Shape::UnitStruct(name));
+        Err(Error::NotAStruct)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        _: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0 = Some(Shape::Struct(name));
+        Err(Error::NotAStruct)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        _: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0 = Some(Shape::Enum(name));
+        Err(Error::NotAStruct)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        newtype_struct seq tuple tuple_struct map identifier ignored_any
+    }
+}
+
+pub fn from_event<T: DeserializeOwned>(event: &Event) -> Result<T, Error> {
+    let mut probe = Probe(None);
+    // The probe always fails; we only care about the shape that it recorded.
+    let _ = T::deserialize(&mut probe);
+    let shape = probe.0.ok_or(Error::NotAStruct)?;
+
+    let name = match shape {
+        Shape::Struct(name) | Shape::UnitStruct(name) | Shape::Enum(name) => name,
+    };
+
+    let (ty, wasm) = match event.ty.strip_prefix(WASM_PREFIX) {
+        Some(ty) if event.ty != name => (ty, true),
+        _ => (&*event.ty, false),
+    };
+    let attrs = event
+        .attributes
+        .iter()
+        .filter(|a| !(wasm && a.key == CONTRACT_ADDRESS_KEY))
+        .collect::<Vec<_>>();
+
+    let mismatch = || Error::EventType {
+        expected: name.into(),
+        actual: event.ty.clone(),
+    };
+
+    let json = match shape {
+        Shape::Struct(_) if ty == name => object(&attrs)?,
+        Shape::UnitStruct(_) if ty == name && attrs.is_empty() => "null".into(),
+        Shape::Enum(_) => {
+            let variant = ty
+                .strip_prefix(name)
+                .and_then(|v| v.strip_prefix("::"))
+                .ok_or_else(mismatch)?;
+            let variant = serde_json_wasm::to_string(variant)?;
+            if attrs.is_empty() {
+                variant
+            } else {
+                format!("{{{variant}:{}}}", object(&attrs)?)
+            }
+        }
+        _ => return Err(mismatch()),
+    };
+
+    serde_json_wasm::from_str(&json).map_err(From::from)
+}
+
+// Joins the attributes into a json object.  Every value must be a single, complete json value so
+// that a malformed attribute cannot inject other fields into the object.
+fn object(attrs: &[&Attribute]) -> Result<String, Error> {
+    let mut out = String::from("{");
+    for (i, a) in attrs.iter().enumerate() {
+        // `serde-json-wasm` can only skip over a bare number when it is followed by a delimiter.
+        serde_json_wasm::from_str::<(IgnoredAny,)>(&format!("[{}]", a.value))?;
+
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&serde_json_wasm::to_string(&a.key)?);
+        out.push(':');
+        out.push_str(&a.value);
+    }
+    out.push('}');
+
+    Ok(out)
+}
@@ -1,6 +1,6 @@
 use std::fmt;
 
-use serde::ser;
+use serde::{de, ser};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -14,6 +14,10 @@ pub enum Error {
     MultipleStructs,
     #[error("no event produced by serialization")]
     NoEvent,
+    #[error("{0}")]
+    JsonDe(#[from] serde_json_wasm::de::Error),
+    #[error("expected event type `{expected}`, found `{actual}`")]
+    EventType { expected: String, actual: String },
 }
 
 impl ser::Error for Error {
@@ -24,3 +28,12 @@ impl ser::Error for Error {
         Error::Custom(msg.to_string().into_boxed_str())
     }
 }
+
+impl de::Error for Error {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Error::Custom(msg.to_string().into_boxed_str())
+    }
+}
@@ -1,9 +1,11 @@
 //! This crate provides a mechanism to convert arbitrary rust structs into `cosmwasm_std::Event`s
-//! via that struct's `Serialize` impl.
+//! via that struct's `Serialize` impl, and to convert those events back into structs via the
+//! struct's `Deserialize` impl.
 
 use cosmwasm_std::Event;
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 
+mod de;
 mod error;
 mod ser;
 
@@ -81,6 +83,66 @@ pub fn to_event<T: Serialize + ?Sized>(value: &T) -> Result<Event, Error> {
     s.finish().ok_or(Error::NoEvent)
 }
 
+/// Convert a `cosmwasm_std::Event` produced by [`to_event`] back into a `T` via its `Deserialize`
+/// impl.
+///
+/// This is the inverse of `to_event` and follows the same rules: `T` must be a regular struct, a
+/// unit struct, an enum whose variants are unit or struct variants, or an `Option` of one of those
+/// types.  The type of the event must match the name of the struct or "{name}::{variant}" for an
+/// enum and every attribute value must be encoded using the `serde-json-wasm` crate.  Fields of
+/// type `Option` may be missing from the event, in which case they are deserialized as `None`.
+///
+/// Events read back from the chain are also accepted: the "wasm-" prefix that wasmd adds to the
+/// type of contract events is ignored, as is the `_contract_address` attribute.
+///
+/// The expected event type is taken from the name that `T`'s `Deserialize` impl passes to serde
+/// before any data is read.  Types that never provide one return `Error::NotAStruct`, even when
+/// `to_event` accepts them: `#[serde(untagged)]` enums, structs with `#[serde(flatten)]` fields
+/// (which serde deserializes as maps), and hand-written impls that don't call
+/// `deserialize_struct` or `deserialize_enum`.
+///
+/// # Examples
+///
+/// ```
+/// # fn example() -> Result<(), cw_transcode::Error> {
+/// #    use cosmwasm_std::Event;
+/// #    use serde::{Deserialize, Serialize};
+/// #
+///     use cw_transcode::{from_event, to_event};
+///
+///     #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+///     struct Example {
+///         primitive: u64,
+///         text: String,
+///         missing: Option<u32>,
+///     }
+///
+///     let evt = Event::new("wasm-Example")
+///         .add_attribute("_contract_address", "wormhole14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9srrg465")
+///         .add_attribute("primitive", "17")
+///         .add_attribute("text", "\"TEST\"");
+///
+///     let e: Example = from_event(&evt)?;
+///
+///     let expected = Example {
+///         primitive: 17,
+///         text: "TEST".into(),
+///         missing: None,
+///     };
+///     assert_eq!(expected, e);
+///
+///     // Converting back and forth is lossless.
+///     assert_eq!(e, from_event(&to_event(&e)?)?);
+/// #
+/// #    Ok(())
+/// # }
+/// #
+/// # example().unwrap();
+/// ```
+pub fn from_event<T: DeserializeOwned>(event: &Event) -> Result<T, Error> {
+    de::from_event(event)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -88,6 +150,7 @@ mod test {
     use std::{any::type_name, collections::BTreeMap};
 
     use cosmwasm_std::Binary;
+    use serde::Deserialize;
     use serde_bytes::ByteBuf;
 
     #[test]
@@ -282,4 +345,173 @@ mod test {
         let actual = to_event(&o).unwrap();
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn round_trip() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+        struct Nested {
+            a: u32,
+            b: Binary,
+        }
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+        struct Outer {
+            #[serde(with = "hex")]
+            address: [u8; 32],
+            nested: Nested,
+            c: String,
+            d: Option<u64>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            e: Option<i8>,
+        }
+
+        let o = Outer {
+            address: [0x6e; 32],
+            nested: Nested {
+                a: 0xfeb42045,
+                b: Binary(vec![0x11, 0x7d, 0x83, 0xa4, 0x06, 0xbf, 0x3e, 0x50]),
+            },
+            c: "TEST".into(),
+            d: None,
+            e: None,
+        };
+
+        let evt = to_event(&o).unwrap();
+        assert_eq!(o, from_event(&evt).unwrap());
+
+        let o = Outer {
+            d: Some(1),
+            e: Some(-1),
+            ..o
+        };
+
+        let evt = to_event(&o).unwrap();
+        assert_eq!(o, from_event(&evt).unwrap());
+        assert_eq!(Some(o), from_event(&evt).unwrap());
+    }
+
+    #[test]
+    fn round_trip_unit_struct() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+        struct MyEvent;
+
+        let evt = to_event(&MyEvent).unwrap();
+        assert_eq!(MyEvent, from_event(&evt).unwrap());
+
+        let evt = evt.add_attribute("a", "1");
+        let err = from_event::<MyEvent>(&evt).unwrap_err();
+        assert!(matches!(err, Error::EventType { .. }));
+    }
+
+    #[test]
+    fn round_trip_enum_variants() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+        enum MyEvent {
+            A,
+            C { f1: u64, f2: String },
+        }
+
+        let a = MyEvent::A;
+        let evt = to_event(&a).unwrap();
+        assert_eq!(a, from_event(&evt).unwrap());
+
+        let c = MyEvent::C {
+            f1: 500,
+            f2: "test struct variant".into(),
+        };
+        let evt = to_event(&c).unwrap();
+        assert_eq!(c, from_event(&evt).unwrap());
+
+        let err = from_event::<MyEvent>(&Event::new("MyEvent::B")).unwrap_err();
+        assert!(matches!(err, Error::JsonDe(_)));
+
+        let err = from_event::<MyEvent>(&Event::new("OtherEvent::A")).unwrap_err();
+        assert!(matches!(err, Error::EventType { .. }));
+    }
+
+    #[test]
+    fn wasm_event() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+        struct Data {
+            a: u32,
+        }
+
+        let evt = Event::new("wasm-Data")
+            .add_attribute(
+                "_contract_address",
+                "wormhole1466nf3zuxpya8q9emxukd7vftaf6h4psr0a07srl5zw74zh84yjq4lyjmh",
+            )
+            .add_attribute("a", "17");
+        assert_eq!(Data { a: 17 }, from_event(&evt).unwrap());
+
+        // The contract address is only stripped from events read back from the chain.
+        let evt = Event::new("Data")
+            .add_attribute(
+                "_contract_address",
+                "\"wormhole1466nf3zuxpya8q9emxukd7vftaf6h4psr0a07srl5zw74zh84yjq4lyjmh\"",
+            )
+            .add_attribute("a", "17");
+        assert_eq!(Data { a: 17 }, from_event(&evt).unwrap());
+    }
+
+    #[test]
+    fn from_event_errors() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+        struct Data {
+            a: u32,
+            b: String,
+        }
+
+        let err = from_event::<u64>(&Event::new("u64")).unwrap_err();
+        assert!(matches!(err, Error::NotAStruct));
+
+        let evt = Event::new("Other")
+            .add_attribute("a", "17")
+            .add_attribute("b", "\"BEEF\"");
+        let err = from_event::<Data>(&evt).unwrap_err();
+        assert!(matches!(err, Error::EventType { .. }));
+
+        let evt = Event::new("Data").add_attribute("a", "17");
+        let err = from_event::<Data>(&evt).unwrap_err();
+        assert!(matches!(err, Error::JsonDe(_)));
+
+        // Attribute values must be a single json value.
+        let evt = Event::new("Data").add_attribute("a", r#"17,"b":"BEEF""#);
+        let err = from_event::<Data>(&evt).unwrap_err();
+        assert!(matches!(err, Error::JsonDe(_)));
+    }
+
+    #[test]
+    fn from_event_unnamed() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+        struct Inner {
+            b: u32,
+        }
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+        struct Flattened {
+            a: u32,
+            #[serde(flatten)]
+            inner: Inner,
+        }
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+        #[serde(untagged)]
+        enum Untagged {
+            A { a: u32 },
+        }
+
+        // Neither type tells serde its name before reading data, so the event type can't be checked.
+        let evt = to_event(&Flattened {
+            a: 1,
+            inner: Inner { b: 2 },
+        })
+        .unwrap();
+        let err = from_event::<Flattened>(&evt).unwrap_err();
+        assert!(matches!(err, Error::NotAStruct));
+
+        let evt = Event::new("Untagged").add_attribute("a", "1");
+        let err = from_event::<Untagged>(&evt).unwrap_err();
+        assert!(matches!(err, Error::NotAStruct));
+    }
 }
@@ -69,8 +69,9 @@ type (
 
 	// TransferStatus contains the status returned for a transfer.
 	TransferStatus struct {
-		Committed *TransferStatusCommitted `json:"committed"`
-		Pending   *[]TransferStatusPending `json:"pending"`
+		Committed   *TransferStatusCommitted   `json:"committed"`
+		Pending     *[]TransferStatusPending   `json:"pending"`
+		RateLimited *TransferStatusRateLimited `json:"rate_limited"`
 	}
 
 	// TransferStatusCommitted contains the data returned for a committed transfer.
@@ -87,6 +88,12 @@ type (
 		RecipientChain uint16         `json:"recipient_chain"`
 	}
 
+	// TransferStatusRateLimited contains the data returned for a transfer that reached quorum but exceeds the outflow limit of its source account.
+	TransferStatusRateLimited struct {
+		Data    TransferData            `json:"data"`
+		Pending []TransferStatusPending `json:"pending"`
+	}
+
 	// TransferStatusPending contains the data returned for a committed transfer.
 	TransferStatusPending struct {
 		Digest           []byte `json:"digest"`
@@ -245,6 +252,11 @@ func (acct *Accountant) performAudit(tmpMap map[string]*pendingEntry, wormchainC
 				}
 			} else if status.Pending != nil {
 				acct.logger.Debug("contract says transfer is still pending", zap.String("msgId", pe.msgId))
+			} else if status.RateLimited != nil {
+				// The contract only re-checks the outflow limit when the observation is submitted again.
+				if acct.submitObservation(pe) {
+					acct.logger.Info("contract says transfer is rate limited, resubmitted it", zap.String("msgId", pe.msgId))
+				}
 			} else {
 				// This is the case when the contract does not know about a transfer. Resubmit it.
 				if acct.submitObservation(pe) {
@@ -252,6 +252,8 @@ func (acct *Accountant) submitObservationsToContract(msgs []*common.MessagePubli
 			acct.logger.Info(fmt.Sprintf("transfer is pending on %s", tag), zap.String("msgId", msgId))
 		case "committed":
 			acct.handleCommittedTransfer(msgId)
+		case "rate_limited":
+			acct.logger.Info(fmt.Sprintf("transfer is rate limited on %s", tag), zap.String("msgId", msgId))
 		case "error":
 			submitFailures.Inc()
 			acct.handleTransferError(msgId, status.Data, "transfer failed")
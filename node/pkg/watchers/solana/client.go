@@ -166,15 +166,19 @@ const retryDelay = 5 * time.Second
 
 type ConsistencyLevel uint8
 
-// Mappings from consistency levels constants to commitment level.
+// Mappings from consistency levels constants to commitment level. These are the Borsh indexes of
+// the `ConsistencyLevel` enum in the Solana core bridge instruction data. Solana watchers only run
+// at confirmed and finalized commitment, so instant and safe messages are observed once confirmed.
 const (
 	consistencyLevelConfirmed ConsistencyLevel = 0
 	consistencyLevelFinalized ConsistencyLevel = 1
+	consistencyLevelInstant   ConsistencyLevel = 2
+	consistencyLevelSafe      ConsistencyLevel = 3
 )
 
 func (c ConsistencyLevel) Commitment() (rpc.CommitmentType, error) {
 	switch c {
-	case consistencyLevelConfirmed:
+	case consistencyLevelConfirmed, consistencyLevelInstant, consistencyLevelSafe:
 		return rpc.CommitmentConfirmed, nil
 	case consistencyLevelFinalized:
 		return rpc.CommitmentFinalized, nil
@@ -185,7 +189,7 @@ func (c ConsistencyLevel) Commitment() (rpc.CommitmentType, error) {
 
 func accountConsistencyLevelToCommitment(c uint8) (rpc.CommitmentType, error) {
 	switch c {
-	case 1:
+	case 1, vaa.ConsistencyLevelPublishImmediately, vaa.ConsistencyLevelSafe:
 		return rpc.CommitmentConfirmed, nil
 	case 32:
 		return rpc.CommitmentFinalized, nil
@@ -0,0 +1,69 @@
+package solana
+
+import (
+	"testing"
+
+	"github.com/gagliardetto/solana-go/rpc"
+	"github.com/near/borsh-go"
+
+	"github.com/stretchr/testify/assert"
+	"github.com/stretchr/testify/require"
+)
+
+func TestConsistencyLevelCommitment(t *testing.T) {
+	tests := []struct {
+		level      ConsistencyLevel
+		commitment rpc.CommitmentType
+	}{
+		{consistencyLevelConfirmed, rpc.CommitmentConfirmed},
+		{consistencyLevelFinalized, rpc.CommitmentFinalized},
+		{consistencyLevelInstant, rpc.CommitmentConfirmed},
+		{consistencyLevelSafe, rpc.CommitmentConfirmed},
+	}
+
+	for _, tc := range tests {
+		commitment, err := tc.level.Commitment()
+		require.NoError(t, err)
+		assert.Equal(t, tc.commitment, commitment)
+	}
+
+	_, err := ConsistencyLevel(4).Commitment()
+	assert.Error(t, err)
+}
+
+func TestAccountConsistencyLevelToCommitment(t *testing.T) {
+	tests := []struct {
+		level      uint8
+		commitment rpc.CommitmentType
+	}{
+		{1, rpc.CommitmentConfirmed},
+		{32, rpc.CommitmentFinalized},
+		{200, rpc.CommitmentConfirmed},
+		{201, rpc.CommitmentConfirmed},
+	}
+
+	for _, tc := range tests {
+		commitment, err := accountConsistencyLevelToCommitment(tc.level)
+		require.NoError(t, err)
+		assert.Equal(t, tc.commitment, commitment)
+	}
+
+	for _, level := range []uint8{0, 2, 15, 202} {
+		_, err := accountConsistencyLevelToCommitment(level)
+		assert.Error(t, err)
+	}
+}
+
+func TestPostMessageDataConsistencyLevel(t *testing.T) {
+	// Instruction data as encoded by the Solana core bridge: nonce, payload and the Borsh index of
+	// the consistency level.
+	for _, level := range []ConsistencyLevel{consistencyLevelConfirmed, consistencyLevelFinalized, consistencyLevelInstant, consistencyLevelSafe} {
+		data := []byte{7, 0, 0, 0, 2, 0, 0, 0, 0xab, 0xcd, byte(level)}
+
+		var decoded PostMessageData
+		require.NoError(t, borsh.Deserialize(&decoded, data))
+		assert.Equal(t, uint32(7), decoded.Nonce)
+		assert.Equal(t, []byte{0xab, 0xcd}, decoded.Payload)
+		assert.Equal(t, level, decoded.ConsistencyLevel)
+	}
+}
@@ -5,7 +5,10 @@ import {
   TransactionInstruction,
 } from "@solana/web3.js";
 import { TOKEN_METADATA_PROGRAM_ID, deriveTokenMetadataKey } from "../../utils";
-import { getPostMessageAccounts } from "../../wormhole";
+import {
+  getPostMessageAccounts,
+  getEmitterFeeAccountMeta,
+} from "../../wormhole";
 import {
   deriveAuthoritySignerKey,
   deriveCustodyKey,
@@ -44,7 +47,9 @@ export function createTransferNativeInstruction(
       mint
     ) as any,
     signers: undefined,
-    remainingAccounts: undefined,
+    remainingAccounts: [
+      getEmitterFeeAccountMeta(wormholeProgramId, nftBridgeProgramId),
+    ],
     preInstructions: undefined,
     postInstructions: undefined,
   });
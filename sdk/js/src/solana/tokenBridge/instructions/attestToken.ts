@@ -4,7 +4,10 @@ import {
   TransactionInstruction,
 } from "@solana/web3.js";
 import { createReadOnlyTokenBridgeProgramInterface } from "../program";
-import { getPostMessageAccounts } from "../../wormhole";
+import {
+  getPostMessageAccounts,
+  getEmitterFeeAccountMeta,
+} from "../../wormhole";
 import {
   deriveTokenMetadataKey,
   deriveTokenBridgeConfigKey,
@@ -34,7 +37,9 @@ export function createAttestTokenInstruction(
       message
     ) as any,
     signers: undefined,
-    remainingAccounts: undefined,
+    remainingAccounts: [
+      getEmitterFeeAccountMeta(wormholeProgramId, tokenBridgeProgramId),
+    ],
     preInstructions: undefined,
     postInstructions: undefined,
   });
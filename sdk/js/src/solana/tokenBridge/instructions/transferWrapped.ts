@@ -5,7 +5,10 @@ import {
 } from "@solana/web3.js";
 import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { createReadOnlyTokenBridgeProgramInterface } from "../program";
-import { getPostMessageCpiAccounts } from "../../wormhole";
+import {
+  getPostMessageCpiAccounts,
+  getEmitterFeeAccountMeta,
+} from "../../wormhole";
 import {
   deriveAuthoritySignerKey,
   deriveTokenBridgeConfigKey,
@@ -51,7 +54,9 @@ export function createTransferWrappedInstruction(
       tokenAddress
     ) as any,
     signers: undefined,
-    remainingAccounts: undefined,
+    remainingAccounts: [
+      getEmitterFeeAccountMeta(wormholeProgramId, tokenBridgeProgramId),
+    ],
     preInstructions: undefined,
     postInstructions: undefined,
   });
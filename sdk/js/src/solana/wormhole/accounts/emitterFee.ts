@@ -0,0 +1,12 @@
+import { PublicKey, PublicKeyInitData } from "@solana/web3.js";
+import { deriveAddress } from "../../utils";
+
+export function deriveEmitterFeeKey(
+  emitter: PublicKeyInitData,
+  wormholeProgramId: PublicKeyInitData
+): PublicKey {
+  return deriveAddress(
+    [Buffer.from("EmitterFee"), new PublicKey(emitter).toBytes()],
+    wormholeProgramId
+  );
+}
@@ -1,6 +1,7 @@
 export * from "./claim";
 export * from "./config";
 export * from "./emitter";
+export * from "./emitterFee";
 export * from "./feeCollector";
 export * from "./guardianSet";
 export * from "./postedVaa";
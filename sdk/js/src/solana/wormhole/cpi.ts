@@ -54,6 +54,12 @@ export interface PostMessageCpiAccounts extends WormholeDerivedAccounts {
   clock: PublicKey;
   rent: PublicKey;
   systemProgram: PublicKey;
+  /**
+   * seeds = ["EmitterFee", wormholeEmitter], seeds::program = wormholeProgram
+   *
+   * Optional, passed after all other accounts
+   */
+  wormholeEmitterFee: PublicKey;
 }
 
 /**
@@ -88,5 +94,6 @@ export function getPostMessageCpiAccounts(
     clock: accounts.clock,
     rent: accounts.rent,
     systemProgram: accounts.systemProgram,
+    wormholeEmitterFee: accounts.emitterFee,
   };
 }
@@ -1,4 +1,5 @@
 import {
+  AccountMeta,
   PublicKey,
   PublicKeyInitData,
   SYSVAR_CLOCK_PUBKEY,
@@ -7,7 +8,9 @@ import {
 } from "@solana/web3.js";
 import {
   deriveWormholeBridgeDataKey,
+  deriveEmitterFeeKey,
   deriveFeeCollectorKey,
+  deriveWormholeEmitterKey,
   getEmitterKeys,
 } from "../accounts";
 
@@ -22,6 +25,8 @@ export interface PostMessageAccounts {
   clock: PublicKey;
   rent: PublicKey;
   systemProgram: PublicKey;
+  /** Optional fee override of the emitter, passed after all other accounts */
+  emitterFee: PublicKey;
 }
 
 export function getPostMessageAccounts(
@@ -44,5 +49,29 @@ export function getPostMessageAccounts(
     clock: SYSVAR_CLOCK_PUBKEY,
     rent: SYSVAR_RENT_PUBKEY,
     systemProgram: SystemProgram.programId,
+    emitterFee: deriveEmitterFeeKey(emitter, wormholeProgramId),
+  };
+}
+
+/**
+ * Fee override of a program's emitter set by governance. It is only applied
+ * when passed as the last account of an instruction posting a message, so
+ * programs forward it to the Core Bridge if their caller included it.
+ *
+ * @param wormholeProgramId
+ * @param emitterProgramId
+ * @returns
+ */
+export function getEmitterFeeAccountMeta(
+  wormholeProgramId: PublicKeyInitData,
+  emitterProgramId: PublicKeyInitData
+): AccountMeta {
+  return {
+    pubkey: deriveEmitterFeeKey(
+      deriveWormholeEmitterKey(emitterProgramId),
+      wormholeProgramId
+    ),
+    isSigner: false,
+    isWritable: false,
   };
 }
@@ -1,4 +1,4 @@
-use std::{
+use core::{
     convert::TryFrom,
     mem::{self, size_of},
 };
@@ -8,7 +8,11 @@ use serde::de::{
     IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor,
 };
 
-use crate::error::Error;
+use crate::{
+    error::Error,
+    len_prefixed::Prefix,
+    option::{PRESENCE_TOKEN, TRAILING_TOKEN},
+};
 
 /// A struct that deserializes the VAA payload wire format into rust values.
 pub struct Deserializer<'de> {
@@ -26,6 +30,22 @@ impl<'de> Deserializer<'de> {
     pub fn end(self) -> &'de [u8] {
         self.input
     }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'de [u8], Error> {
+        if self.input.len() < len {
+            return Err(Error::Eof);
+        }
+
+        let (data, rem) = self.input.split_at(len);
+        self.input = rem;
+
+        Ok(data)
+    }
+
+    fn read_str(&mut self, len: usize) -> Result<&'de str, Error> {
+        self.read_bytes(len)
+            .and_then(|data| core::str::from_utf8(data).map_err(Error::custom))
+    }
 }
 
 // This has to be a macro because `<type>::from_be_bytes` is not a trait function so there is no
@@ -176,16 +196,7 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
         V: Visitor<'de>,
     {
         let len = usize::from(deserialize_be_number!(self, u8));
-
-        if self.input.len() < len {
-            return Err(Error::Eof);
-        }
-
-        let (data, rem) = self.input.split_at(len);
-        self.input = rem;
-
-        std::str::from_utf8(data)
-            .map_err(Error::custom)
+        self.read_str(len)
             .and_then(|s| visitor.visit_borrowed_str(s))
     }
 
@@ -202,15 +213,8 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
         V: Visitor<'de>,
     {
         let len = usize::from(deserialize_be_number!(self, u8));
-
-        if self.input.len() < len {
-            return Err(Error::Eof);
-        }
-
-        let (data, rem) = self.input.split_at(len);
-        self.input = rem;
-
-        visitor.visit_borrowed_bytes(data)
+        self.read_bytes(len)
+            .and_then(|data| visitor.visit_borrowed_bytes(data))
     }
 
     #[inline]
@@ -262,6 +266,22 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
         if name == crate::raw::TOKEN {
             let rem = mem::take(&mut self.input);
             visitor.visit_newtype_struct(BorrowedBytesDeserializer::new(rem))
+        } else if let Some(prefix) = Prefix::from_token(name) {
+            visitor.visit_newtype_struct(LenPrefixedDeserializer { de: self, prefix })
+        } else if name == TRAILING_TOKEN {
+            let present = !self.input.is_empty();
+            visitor.visit_newtype_struct(OptionDeserializer { de: self, present })
+        } else if name == PRESENCE_TOKEN {
+            let present = match deserialize_be_number!(self, u8) {
+                0 => false,
+                1 => true,
+                v => {
+                    return Err(Error::custom(format_args!(
+                        "invalid presence byte {v}, expected 0 or 1"
+                    )))
+                }
+            };
+            visitor.visit_newtype_struct(OptionDeserializer { de: self, present })
         } else {
             visitor.visit_newtype_struct(self)
         }
@@ -375,6 +395,148 @@ impl<'de> VariantAccess<'de> for &mut Deserializer<'de> {
     }
 }
 
+/// Deserializes a sequence, map, string, or byte array whose length is encoded with `prefix`
+/// rather than a single byte.
+struct LenPrefixedDeserializer<'de, 'a> {
+    de: &'a mut Deserializer<'de>,
+    prefix: Prefix,
+}
+
+impl<'de> de::Deserializer<'de> for LenPrefixedDeserializer<'de, '_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, _: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::custom(
+            "expected a length-prefixed sequence, map, string, or byte array",
+        ))
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let len = self.prefix.read(&mut self.de.input)?;
+        self.de
+            .read_str(len)
+            .and_then(|s| visitor.visit_borrowed_str(s))
+    }
+
+    #[inline]
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let len = self.prefix.read(&mut self.de.input)?;
+        self.de
+            .read_bytes(len)
+            .and_then(|data| visitor.visit_borrowed_bytes(data))
+    }
+
+    #[inline]
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let len = self.prefix.read(&mut self.de.input)?;
+        visitor.visit_seq(BoundedSequence::new(self.de, len))
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let len = self.prefix.read(&mut self.de.input)?;
+        visitor.visit_map(BoundedSequence::new(self.de, len))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char unit unit_struct tuple
+        tuple_struct struct enum identifier ignored_any
+    }
+}
+
+/// Deserializes an optional value whose presence has already been determined.
+struct OptionDeserializer<'de, 'a> {
+    de: &'a mut Deserializer<'de>,
+    present: bool,
+}
+
+impl<'de> de::Deserializer<'de> for OptionDeserializer<'de, '_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, _: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::custom("expected an `Option`"))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.present {
+            visitor.visit_some(self.de)
+        } else {
+            visitor.visit_none()
+        }
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
 struct BoundedSequence<'de, 'a> {
     de: &'a mut Deserializer<'de>,
     count: usize,
@@ -1,4 +1,5 @@
-use std::{fmt::Display, io, num::ParseIntError};
+use alloc::{boxed::Box, string::ToString};
+use core::{fmt::Display, num::ParseIntError};
 
 use serde::{de, ser};
 use thiserror::Error as ThisError;
@@ -7,8 +8,9 @@ use thiserror::Error as ThisError;
 pub enum Error {
     #[error("{0}")]
     Message(Box<str>),
+    #[cfg(feature = "std")]
     #[error("{0}")]
-    Io(#[from] io::Error),
+    Io(#[from] std::io::Error),
     #[error("unexpected end of input")]
     Eof,
     #[error("`deserialize_any` is not supported")]
@@ -19,6 +21,8 @@ pub enum Error {
     Unsupported,
     #[error("sequence is too large ({0} elements), max supported length = 255")]
     SequenceTooLarge(usize),
+    #[error("sequence is too large ({0} elements), max supported length = {1}")]
+    LengthPrefixOverflow(usize, u64),
     #[error("enum variant {0}::{1} cannot be parsed as a `u8`: {2}")]
     EnumVariant(&'static str, &'static str, ParseIntError),
     #[error("sequence length must be known before serialization")]
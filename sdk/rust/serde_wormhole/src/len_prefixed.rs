@@ -0,0 +1,386 @@
+use core::{
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{ser::Write, Error};
+
+const U8_TOKEN: &str = "$serde_wormhole::private::LenPrefixed<u8>";
+const U16_TOKEN: &str = "$serde_wormhole::private::LenPrefixed<u16>";
+const U32_TOKEN: &str = "$serde_wormhole::private::LenPrefixed<u32>";
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+}
+
+/// An integer type that can be used as the length prefix of a [`LenPrefixed`] value. This trait is
+/// implemented for `u8`, `u16`, and `u32` and cannot be implemented outside this crate.
+pub trait LengthPrefix: private::Sealed {
+    #[doc(hidden)]
+    const TOKEN: &'static str;
+}
+
+impl LengthPrefix for ::core::primitive::u8 {
+    const TOKEN: &'static str = U8_TOKEN;
+}
+
+impl LengthPrefix for ::core::primitive::u16 {
+    const TOKEN: &'static str = U16_TOKEN;
+}
+
+impl LengthPrefix for ::core::primitive::u32 {
+    const TOKEN: &'static str = U32_TOKEN;
+}
+
+// The `u8`, `u16`, and `u32` modules at the bottom of this file shadow the primitive types so
+// refer to them through an alias instead.
+type Len = ::core::primitive::u32;
+
+/// The width of the length prefix for a sequence, map, string, or byte array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Prefix {
+    U8,
+    U16,
+    U32,
+}
+
+impl Prefix {
+    pub(crate) fn from_token(name: &str) -> Option<Self> {
+        match name {
+            U8_TOKEN => Some(Prefix::U8),
+            U16_TOKEN => Some(Prefix::U16),
+            U32_TOKEN => Some(Prefix::U32),
+            _ => None,
+        }
+    }
+
+    fn width(self) -> usize {
+        match self {
+            Prefix::U8 => 1,
+            Prefix::U16 => 2,
+            Prefix::U32 => 4,
+        }
+    }
+
+    fn max(self) -> Len {
+        match self {
+            Prefix::U8 => 0xff,
+            Prefix::U16 => 0xffff,
+            Prefix::U32 => Len::MAX,
+        }
+    }
+
+    /// Writes `len` to `w` as a big-endian integer of the appropriate width.
+    pub(crate) fn write<W: Write>(self, w: &mut W, len: usize) -> Result<(), Error> {
+        let l = Len::try_from(len)
+            .ok()
+            .filter(|l| *l <= self.max())
+            .ok_or_else(|| Error::LengthPrefixOverflow(len, self.max().into()))?;
+
+        w.write_all(&l.to_be_bytes()[4 - self.width()..])
+    }
+
+    /// Reads a big-endian integer of the appropriate width from the front of `input`.
+    pub(crate) fn read(self, input: &mut &[u8]) -> Result<usize, Error> {
+        let width = self.width();
+        if input.len() < width {
+            return Err(Error::Eof);
+        }
+
+        let (data, rem) = input.split_at(width);
+        let mut buf = [0u8; 4];
+        buf[4 - width..].copy_from_slice(data);
+        *input = rem;
+
+        usize::try_from(Len::from_be_bytes(buf)).map_err(|_| Error::Unsupported)
+    }
+}
+
+/// A sequence, map, string, or byte array whose length is encoded as an `L` rather than as a
+/// single byte.
+///
+/// By default, the wire format encodes the length of variable-length types with a single byte,
+/// which limits them to 255 elements. Wrapping a value in `LenPrefixed<u16, T>` or
+/// `LenPrefixed<u32, T>` instead encodes the length as a big-endian `u16` or `u32`, respectively.
+/// The inner value must serialize as a sequence, map, string, or byte array. Note that, just like
+/// with the default encoding, `Vec<u8>` is treated as a sequence so you will probably want to use
+/// `serde_bytes` for byte arrays.
+///
+/// For human readable formats like JSON, `LenPrefixed<L, T>` is serialized exactly like `T`.
+///
+/// Fields can also use the modules in [`len_prefixed`](crate::len_prefixed) with
+/// `#[serde(with = "...")]` to get the same encoding without changing the type of the field.
+///
+/// # Examples
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use serde_wormhole::LenPrefixed;
+///
+/// #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+/// struct Instruction {
+///     targets: LenPrefixed<u16, Vec<u64>>,
+///     #[serde(with = "serde_wormhole::len_prefixed::u32")]
+///     payload: serde_bytes::ByteBuf,
+/// }
+///
+/// let instruction = Instruction {
+///     targets: LenPrefixed::new(vec![7; 300]),
+///     payload: vec![0xa5; 70_000].into(),
+/// };
+///
+/// let buf = serde_wormhole::to_vec(&instruction).unwrap();
+/// assert_eq!(&[0x01, 0x2c], &buf[..2]);
+/// assert_eq!(&[0x00, 0x01, 0x11, 0x70], &buf[2 + 300 * 8..][..4]);
+/// assert_eq!(instruction, serde_wormhole::from_slice(&buf).unwrap());
+/// ```
+#[repr(transparent)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LenPrefixed<L, T> {
+    value: T,
+    _prefix: PhantomData<fn() -> L>,
+}
+
+impl<L, T> LenPrefixed<L, T> {
+    /// Wraps `value` so that its length is encoded as an `L`.
+    pub const fn new(value: T) -> Self {
+        Self {
+            value,
+            _prefix: PhantomData,
+        }
+    }
+
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<L, T> From<T> for LenPrefixed<L, T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<L, T> Deref for LenPrefixed<L, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<L, T> DerefMut for LenPrefixed<L, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<L: LengthPrefix, T: Serialize> Serialize for LenPrefixed<L, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize::<L, T, S>(&self.value, serializer)
+    }
+}
+
+impl<'de, L: LengthPrefix, T: Deserialize<'de>> Deserialize<'de> for LenPrefixed<L, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize::<L, T, D>(deserializer).map(Self::new)
+    }
+}
+
+fn serialize<L, T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    L: LengthPrefix,
+    T: ?Sized + Serialize,
+    S: Serializer,
+{
+    serializer.serialize_newtype_struct(L::TOKEN, value)
+}
+
+fn deserialize<'de, L, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    L: LengthPrefix,
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    struct LenPrefixedVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for LenPrefixedVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a length-prefixed value")
+        }
+
+        fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            T::deserialize(deserializer)
+        }
+    }
+
+    deserializer.deserialize_newtype_struct(L::TOKEN, LenPrefixedVisitor(PhantomData))
+}
+
+macro_rules! with_module {
+    ($ty:ident) => {
+        #[doc = concat!(
+            "Encodes the length of a field with a `", stringify!($ty), "` prefix when used with ",
+            "`#[serde(with = \"serde_wormhole::len_prefixed::", stringify!($ty), "\")]`.",
+        )]
+        ///
+        /// See [`LenPrefixed`](crate::LenPrefixed) for details.
+        pub mod $ty {
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: ?Sized + Serialize,
+                S: Serializer,
+            {
+                super::serialize::<::core::primitive::$ty, T, S>(value, serializer)
+            }
+
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+            where
+                T: Deserialize<'de>,
+                D: Deserializer<'de>,
+            {
+                super::deserialize::<::core::primitive::$ty, T, D>(deserializer)
+            }
+        }
+    };
+}
+
+with_module!(u8);
+with_module!(u16);
+with_module!(u32);
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::LenPrefixed;
+    use crate::{from_slice, to_vec, Error};
+
+    #[test]
+    fn long_sequence() {
+        let v = LenPrefixed::<u16, Vec<u16>>::new((0..300).collect());
+
+        let buf = to_vec(&v).unwrap();
+        assert_eq!(2 + 300 * 2, buf.len());
+        assert_eq!(&[0x01, 0x2c, 0x00, 0x00, 0x00, 0x01], &buf[..6]);
+
+        assert_eq!(v, from_slice(&buf).unwrap());
+    }
+
+    #[test]
+    fn u8_prefix_matches_default() {
+        let v = vec![1u32, 2, 3];
+        let prefixed = LenPrefixed::<u8, _>::new(v.clone());
+
+        assert_eq!(to_vec(&v).unwrap(), to_vec(&prefixed).unwrap());
+    }
+
+    #[test]
+    fn strings_and_bytes() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+        struct Message<'a> {
+            #[serde(with = "super::u16")]
+            name: String,
+            #[serde(borrow, with = "super::u32")]
+            data: &'a [u8],
+            trailer: u8,
+        }
+
+        let name = "a".repeat(1000);
+        let data = vec![0x5a; 300];
+        let buf = to_vec(&Message {
+            name: name.clone(),
+            data: &data,
+            trailer: 0xff,
+        })
+        .unwrap();
+
+        let mut expected = vec![0x03, 0xe8];
+        expected.extend_from_slice(name.as_bytes());
+        expected.extend_from_slice(&[0x00, 0x00, 0x01, 0x2c]);
+        expected.extend_from_slice(&data);
+        expected.push(0xff);
+        assert_eq!(expected, buf);
+
+        let msg = from_slice::<Message>(&buf).unwrap();
+        assert_eq!(name, msg.name);
+        assert_eq!(&data[..], msg.data);
+        assert_eq!(0xff, msg.trailer);
+    }
+
+    #[test]
+    fn map() {
+        let m = (0..400u32)
+            .map(|i| (i, i % 7 == 0))
+            .collect::<BTreeMap<_, _>>();
+        let v = LenPrefixed::<u32, _>::new(m);
+
+        let buf = to_vec(&v).unwrap();
+        assert_eq!(&[0x00, 0x00, 0x01, 0x90], &buf[..4]);
+        assert_eq!(4 + 400 * 5, buf.len());
+
+        assert_eq!(v, from_slice(&buf).unwrap());
+    }
+
+    #[test]
+    fn too_large() {
+        let v = LenPrefixed::<u16, Vec<()>>::new(vec![(); 0x1_0000]);
+        assert!(matches!(
+            to_vec(&v).unwrap_err(),
+            Error::LengthPrefixOverflow(0x1_0000, 0xffff)
+        ));
+    }
+
+    #[test]
+    fn truncated() {
+        let buf = [0x00, 0x03, 0x01, 0x02];
+        assert!(matches!(
+            from_slice::<LenPrefixed<u16, Vec<u8>>>(&buf).unwrap_err(),
+            Error::Eof
+        ));
+        assert!(matches!(
+            from_slice::<LenPrefixed<u32, Vec<u8>>>(&buf[..3]).unwrap_err(),
+            Error::Eof
+        ));
+    }
+
+    #[test]
+    fn not_a_sequence() {
+        let v = LenPrefixed::<u16, _>::new(17u64);
+        assert!(to_vec(&v).is_err());
+
+        assert!(from_slice::<LenPrefixed<u16, u64>>(&[0, 0, 0, 0, 0, 0, 0, 17]).is_err());
+    }
+
+    #[test]
+    fn json() {
+        let v = LenPrefixed::<u16, Vec<u8>>::new(vec![1, 2, 3]);
+
+        let encoded = serde_json::to_string(&v).unwrap();
+        assert_eq!("[1,2,3]", encoded);
+        assert_eq!(v, serde_json::from_str(&encoded).unwrap());
+    }
+}
@@ -42,6 +42,11 @@
 //! sequences cannot have more than 255 elements. Additionally, during serialization the length must
 //! be known ahead of time.
 //!
+//! Longer sequences can be described by wrapping them in [`LenPrefixed<u16, T>`](LenPrefixed) or
+//! `LenPrefixed<u32, T>` (or by using `#[serde(with = "serde_wormhole::len_prefixed::u16")]` on a
+//! field), which encodes the length as a big-endian `u16` or `u32` instead. This also applies to
+//! byte arrays, strings, and maps.
+//!
 //! ### Byte arrays - `&[u8]`, `Vec<u8>`, and `Cow<'a, [u8]>`
 //!
 //! Byte arrays are treated as a subset of variable-length sequences and are encoded as a single
@@ -64,6 +69,10 @@
 //! The wire format does not support optional values. Options are always deserialized as `Some(T)`
 //! while trying to serialize an `Option::None` will result in an error.
 //!
+//! Optional fields can instead opt into one of the encodings in the [`option`] module with
+//! `#[serde(with = "...")]`: either a trailing value that is present only if there is data left in
+//! the input, or a value preceded by a presence byte.
+//!
 //! ### Structs
 //!
 //! Structs are represented the same way as tuples and the wire format for a struct is identical to
@@ -120,21 +129,35 @@
 //! is identical to that of a `BTreeMap<K, V>`. During serialiazation, the number of elements in the
 //! map must be known ahead of time. Like other sequences, the maximum number of elements in the map
 //! is 255.
+//!
+//! ## `no_std` support
+//!
+//! This crate only requires `alloc`. The `std` feature, which is enabled by default, adds
+//! `from_reader` and `to_writer` for working with `std::io` streams. Without it, [`from_slice`] and
+//! [`to_vec`] are still available and the [`Error`] type only depends on `core`.
+
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+extern crate alloc;
 
-use std::io::{Read, Write};
+use alloc::vec::Vec;
 
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde::{Deserialize, Serialize};
 
 mod de;
 mod error;
+pub mod len_prefixed;
+pub mod option;
 mod raw;
 mod ser;
 
 pub use error::Error;
+pub use len_prefixed::{LenPrefixed, LengthPrefix};
 pub use raw::{to_raw_message, RawMessage};
 
 /// Deserialize an instance of type `T` from the provided reader.
-pub fn from_reader<R: Read, T: DeserializeOwned>(mut r: R) -> Result<T, Error> {
+#[cfg(feature = "std")]
+pub fn from_reader<R: std::io::Read, T: serde::de::DeserializeOwned>(mut r: R) -> Result<T, Error> {
     // We can do something smarter here by making the deserializer generic over the reader (see
     // serde_json::Deserializer) but for now this is probably good enough.
     let mut buf = Vec::with_capacity(128);
@@ -160,12 +183,13 @@ pub fn from_slice<'a, T: Deserialize<'a>>(buf: &'a [u8]) -> Result<T, Error> {
 pub fn to_vec<T: ?Sized + Serialize>(val: &T) -> Result<Vec<u8>, Error> {
     let mut buf = Vec::with_capacity(128);
 
-    to_writer(&mut buf, val)?;
+    val.serialize(&mut ser::Serializer::new(&mut buf))?;
     Ok(buf)
 }
 
 /// Serialize `T` into the provided writer.
-pub fn to_writer<W: Write, T: ?Sized + Serialize>(w: W, val: &T) -> Result<(), Error> {
+#[cfg(feature = "std")]
+pub fn to_writer<W: std::io::Write, T: ?Sized + Serialize>(w: W, val: &T) -> Result<(), Error> {
     let mut serializer = ser::Serializer::new(w);
     val.serialize(&mut serializer)
 }
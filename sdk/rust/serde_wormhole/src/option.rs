@@ -0,0 +1,277 @@
+//! Optional fields in the VAA payload wire format.
+//!
+//! The wire format has no native representation for optional values. This module provides two
+//! opt-in encodings for `Option<T>` fields, which can be selected with `#[serde(with = "...")]`:
+//!
+//! * [`trailing`] - the value is present if there is any data left in the input. This is useful for
+//!   versioned payloads where newer versions append fields to the end of the message.
+//! * [`presence`] - the value is preceded by a single byte that is 1 if the value is present and
+//!   0 if it is not.
+//!
+//! For human readable formats like JSON, both encodings serialize the field like a regular
+//! `Option<T>`.
+//!
+//! # Examples
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+//! struct Message {
+//!     amount: u64,
+//!     #[serde(with = "serde_wormhole::option::presence")]
+//!     memo: Option<u32>,
+//!     // Added in version 2 of the message.
+//!     #[serde(with = "serde_wormhole::option::trailing")]
+//!     fee: Option<u64>,
+//! }
+//!
+//! let v1 = [0, 0, 0, 0, 0, 0, 0, 5, 0];
+//! let msg = serde_wormhole::from_slice::<Message>(&v1).unwrap();
+//! assert_eq!(
+//!     Message {
+//!         amount: 5,
+//!         memo: None,
+//!         fee: None
+//!     },
+//!     msg
+//! );
+//! assert_eq!(&v1[..], serde_wormhole::to_vec(&msg).unwrap());
+//!
+//! let v2 = [0, 0, 0, 0, 0, 0, 0, 5, 1, 0, 0, 0, 9, 0, 0, 0, 0, 0, 0, 0, 1];
+//! let msg = serde_wormhole::from_slice::<Message>(&v2).unwrap();
+//! assert_eq!(
+//!     Message {
+//!         amount: 5,
+//!         memo: Some(9),
+//!         fee: Some(1)
+//!     },
+//!     msg
+//! );
+//! assert_eq!(&v2[..], serde_wormhole::to_vec(&msg).unwrap());
+//! ```
+
+use core::{fmt, marker::PhantomData};
+
+use serde::{de::Visitor, ser::SerializeTuple, Deserialize, Deserializer, Serialize, Serializer};
+
+pub(crate) const TRAILING_TOKEN: &str = "$serde_wormhole::private::Trailing";
+pub(crate) const PRESENCE_TOKEN: &str = "$serde_wormhole::private::Presence";
+
+struct OptionVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for OptionVisitor<T> {
+    type Value = Option<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an optional value")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<T>::deserialize(deserializer)
+    }
+}
+
+/// An optional value that is present if there is any data left in the input.
+///
+/// When deserializing, the field is `None` if the input has been fully consumed and `Some(T)`
+/// otherwise. When serializing, `None` produces no output at all. This means that a trailing
+/// field should only be followed by other trailing fields.
+pub mod trailing {
+    use super::*;
+
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            return value.serialize(serializer);
+        }
+
+        match value {
+            Some(v) => v.serialize(serializer),
+            None => serializer.serialize_unit(),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(TRAILING_TOKEN, OptionVisitor(PhantomData))
+    }
+}
+
+/// An optional value preceded by a single byte indicating whether it is present.
+///
+/// A presence byte of 0 indicates `None` and is not followed by any data. A presence byte of 1
+/// indicates `Some(T)` and is followed by the serialized value. All other values are invalid.
+pub mod presence {
+    use super::*;
+
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            return value.serialize(serializer);
+        }
+
+        match value {
+            Some(v) => {
+                let mut tup = serializer.serialize_tuple(2)?;
+                tup.serialize_element(&1u8)?;
+                tup.serialize_element(v)?;
+                tup.end()
+            }
+            None => serializer.serialize_u8(0),
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(PRESENCE_TOKEN, OptionVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{from_slice, to_vec, Error};
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+    struct Versioned {
+        version: u8,
+        #[serde(with = "super::trailing")]
+        fee: Option<u64>,
+        #[serde(with = "super::trailing")]
+        extra: Option<Vec<u16>>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+    struct Tagged {
+        #[serde(with = "super::presence")]
+        first: Option<u16>,
+        #[serde(with = "super::presence")]
+        second: Option<(u8, u8)>,
+        last: u8,
+    }
+
+    #[test]
+    fn trailing() {
+        let cases = [
+            (
+                &[1][..],
+                Versioned {
+                    version: 1,
+                    fee: None,
+                    extra: None,
+                },
+            ),
+            (
+                &[2, 0, 0, 0, 0, 0, 0, 0, 7][..],
+                Versioned {
+                    version: 2,
+                    fee: Some(7),
+                    extra: None,
+                },
+            ),
+            (
+                &[3, 0, 0, 0, 0, 0, 0, 0, 7, 2, 0, 1, 0, 2][..],
+                Versioned {
+                    version: 3,
+                    fee: Some(7),
+                    extra: Some(vec![1, 2]),
+                },
+            ),
+        ];
+
+        for (buf, expected) in cases {
+            assert_eq!(expected, from_slice(buf).unwrap());
+            assert_eq!(buf, to_vec(&expected).unwrap());
+        }
+    }
+
+    #[test]
+    fn truncated_trailing() {
+        let buf = [2, 0, 0, 0, 7];
+        assert!(matches!(
+            from_slice::<Versioned>(&buf).unwrap_err(),
+            Error::Eof
+        ));
+    }
+
+    #[test]
+    fn presence() {
+        let cases = [
+            (
+                &[0, 0, 9][..],
+                Tagged {
+                    first: None,
+                    second: None,
+                    last: 9,
+                },
+            ),
+            (
+                &[1, 0x12, 0x34, 0, 9][..],
+                Tagged {
+                    first: Some(0x1234),
+                    second: None,
+                    last: 9,
+                },
+            ),
+            (
+                &[0, 1, 3, 4, 9][..],
+                Tagged {
+                    first: None,
+                    second: Some((3, 4)),
+                    last: 9,
+                },
+            ),
+        ];
+
+        for (buf, expected) in cases {
+            assert_eq!(expected, from_slice(buf).unwrap());
+            assert_eq!(buf, to_vec(&expected).unwrap());
+        }
+    }
+
+    #[test]
+    fn invalid_presence_byte() {
+        let buf = [2, 0, 0, 0, 9];
+        assert!(from_slice::<Tagged>(&buf).is_err());
+
+        assert!(matches!(from_slice::<Tagged>(&[]).unwrap_err(), Error::Eof));
+    }
+
+    #[test]
+    fn json() {
+        let v = Versioned {
+            version: 1,
+            fee: Some(3),
+            extra: None,
+        };
+        let encoded = serde_json::to_string(&v).unwrap();
+        assert_eq!(r#"{"version":1,"fee":3,"extra":null}"#, encoded);
+        assert_eq!(v, serde_json::from_str(&encoded).unwrap());
+
+        let t = Tagged {
+            first: None,
+            second: Some((1, 2)),
+            last: 3,
+        };
+        let encoded = serde_json::to_string(&t).unwrap();
+        assert_eq!(r#"{"first":null,"second":[1,2],"last":3}"#, encoded);
+        assert_eq!(t, serde_json::from_str(&encoded).unwrap());
+    }
+}
@@ -1,4 +1,5 @@
-use std::{fmt, ops::Deref};
+use alloc::{borrow::ToOwned, boxed::Box, vec::Vec};
+use core::{fmt, ops::Deref};
 
 use base64::display::Base64Display;
 use serde::{
@@ -117,7 +118,7 @@ impl RawMessage {
     fn from_owned(b: Box<[u8]>) -> Box<Self> {
         #[cfg(debug_assertions)]
         {
-            use std::alloc::Layout;
+            use alloc::alloc::Layout;
 
             let a = Layout::for_value::<[u8]>(&b);
             let b = Layout::for_value::<RawMessage>(Self::from_borrowed(&b));
@@ -132,7 +133,7 @@ impl RawMessage {
     fn into_owned(self: Box<Self>) -> Box<[u8]> {
         #[cfg(debug_assertions)]
         {
-            use std::alloc::Layout;
+            use alloc::alloc::Layout;
 
             let a = Layout::for_value::<RawMessage>(&self);
             let b = Layout::for_value::<[u8]>(&self.bytes);
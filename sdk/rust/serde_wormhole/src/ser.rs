@@ -1,11 +1,43 @@
-use std::{convert::TryFrom, fmt::Display, io::Write};
+use alloc::string::ToString;
+use core::{convert::TryFrom, fmt::Display};
 
 use serde::{
     ser::{self, Impossible},
     Serialize,
 };
 
-use crate::Error;
+use crate::{len_prefixed::Prefix, Error};
+
+/// A sink for serialized bytes. This is implemented for all types that implement `std::io::Write`
+/// when the `std` feature is enabled and for `Vec<u8>` otherwise.
+pub trait Write {
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Error>;
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Write for W {
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Error> {
+        std::io::Write::write_all(self, buf).map_err(Error::from)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl Write for alloc::vec::Vec<u8> {
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Error> {
+        self.extend_from_slice(buf);
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl<W: Write + ?Sized> Write for &mut W {
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Error> {
+        (**self).write_all(buf)
+    }
+}
 
 /// A struct that serializes a rust value into the VAA payload wire format.
 pub struct Serializer<W> {
@@ -33,60 +65,60 @@ impl<W: Write> ser::Serializer for &mut Serializer<W> {
     #[inline]
     fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
         if v {
-            self.writer.write_all(&[1]).map_err(Error::from)
+            self.writer.write_all(&[1])
         } else {
-            self.writer.write_all(&[0]).map_err(Error::from)
+            self.writer.write_all(&[0])
         }
     }
 
     #[inline]
     fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
-        self.writer.write_all(&v.to_be_bytes()).map_err(Error::from)
+        self.writer.write_all(&v.to_be_bytes())
     }
 
     #[inline]
     fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
-        self.writer.write_all(&v.to_be_bytes()).map_err(Error::from)
+        self.writer.write_all(&v.to_be_bytes())
     }
 
     #[inline]
     fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
-        self.writer.write_all(&v.to_be_bytes()).map_err(Error::from)
+        self.writer.write_all(&v.to_be_bytes())
     }
 
     #[inline]
     fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
-        self.writer.write_all(&v.to_be_bytes()).map_err(Error::from)
+        self.writer.write_all(&v.to_be_bytes())
     }
 
     #[inline]
     fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
-        self.writer.write_all(&v.to_be_bytes()).map_err(Error::from)
+        self.writer.write_all(&v.to_be_bytes())
     }
 
     #[inline]
     fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
-        self.writer.write_all(&v.to_be_bytes()).map_err(Error::from)
+        self.writer.write_all(&v.to_be_bytes())
     }
 
     #[inline]
     fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
-        self.writer.write_all(&v.to_be_bytes()).map_err(Error::from)
+        self.writer.write_all(&v.to_be_bytes())
     }
 
     #[inline]
     fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
-        self.writer.write_all(&v.to_be_bytes()).map_err(Error::from)
+        self.writer.write_all(&v.to_be_bytes())
     }
 
     #[inline]
     fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
-        self.writer.write_all(&v.to_be_bytes()).map_err(Error::from)
+        self.writer.write_all(&v.to_be_bytes())
     }
 
     #[inline]
     fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
-        self.writer.write_all(&v.to_be_bytes()).map_err(Error::from)
+        self.writer.write_all(&v.to_be_bytes())
     }
 
     #[inline]
@@ -113,7 +145,7 @@ impl<W: Write> ser::Serializer for &mut Serializer<W> {
         let len = u8::try_from(v.len()).map_err(|_| Error::SequenceTooLarge(v.len()))?;
 
         self.writer.write_all(&[len])?;
-        self.writer.write_all(v).map_err(Error::from)
+        self.writer.write_all(v)
     }
 
     #[inline]
@@ -149,7 +181,7 @@ impl<W: Write> ser::Serializer for &mut Serializer<W> {
             .parse()
             .map_err(|e| Error::EnumVariant(name, variant, e))?;
 
-        self.writer.write_all(&[v]).map_err(Error::from)
+        self.writer.write_all(&[v])
     }
 
     fn serialize_newtype_struct<T>(
@@ -162,6 +194,8 @@ impl<W: Write> ser::Serializer for &mut Serializer<W> {
     {
         if name == crate::raw::TOKEN {
             value.serialize(RawMessageSerializer(&mut self.writer))
+        } else if let Some(prefix) = Prefix::from_token(name) {
+            value.serialize(LenPrefixedSerializer { ser: self, prefix })
         } else {
             value.serialize(self)
         }
@@ -413,9 +447,9 @@ impl<W: Write> ser::SerializeMap for &mut Serializer<W> {
     }
 }
 
-struct RawMessageSerializer<W>(W);
+struct RawMessageSerializer<'a, W>(&'a mut W);
 
-impl<W: Write> ser::Serializer for RawMessageSerializer<W> {
+impl<W: Write> ser::Serializer for RawMessageSerializer<'_, W> {
     type Ok = ();
     type Error = Error;
 
@@ -487,7 +521,7 @@ impl<W: Write> ser::Serializer for RawMessageSerializer<W> {
         Err(ser::Error::custom("expected RawMessage"))
     }
 
-    fn serialize_bytes(mut self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
         self.0.write_all(v)?;
         Ok(())
     }
@@ -597,20 +631,249 @@ impl<W: Write> ser::Serializer for RawMessageSerializer<W> {
     }
 }
 
+const EXPECTED_LEN_PREFIXED: &str =
+    "expected a length-prefixed sequence, map, string, or byte array";
+
+/// Serializes a sequence, map, string, or byte array with a length prefix of the given width rather
+/// than a single byte.
+struct LenPrefixedSerializer<'a, W> {
+    ser: &'a mut Serializer<W>,
+    prefix: Prefix,
+}
+
+impl<'a, W: Write> ser::Serializer for LenPrefixedSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = &'a mut Serializer<W>;
+    type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = &'a mut Serializer<W>;
+    type SerializeStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_i128(self, _v: i128) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_u128(self, _v: u128) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.prefix.write(&mut self.ser.writer, v.len())?;
+        self.ser.writer.write_all(v)
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(Error::Unsupported)
+    }
+
+    #[inline]
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        let len = len.ok_or(Error::UnknownSequenceLength)?;
+        self.prefix.write(&mut self.ser.writer, len)?;
+        Ok(self.ser)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        let len = len.ok_or(Error::UnknownSequenceLength)?;
+        self.prefix.write(&mut self.ser.writer, len)?;
+        Ok(self.ser)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(ser::Error::custom(EXPECTED_LEN_PREFIXED))
+    }
+
+    #[inline]
+    fn collect_str<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + Display,
+    {
+        self.serialize_str(&value.to_string())
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{borrow::Cow, collections::BTreeMap};
 
     use serde::{Deserialize, Serialize};
 
-    use crate::{to_vec, to_writer, Error};
+    use crate::{to_vec, Error};
 
     #[test]
+    #[cfg(feature = "std")]
     fn empty_buffer() {
-        to_writer(&mut [][..], &0xcc1a_7e0e_31f4_eae4u64)
+        crate::to_writer(&mut [][..], &0xcc1a_7e0e_31f4_eae4u64)
             .expect_err("serialized data to empty buffer");
     }
 
+    #[test]
+    fn vec_writer() {
+        let mut buf = Vec::new();
+        0x7e0eu16
+            .serialize(&mut super::Serializer::new(&mut buf))
+            .unwrap();
+        true.serialize(&mut super::Serializer::new(&mut buf))
+            .unwrap();
+        assert_eq!(&[0x7e, 0x0e, 1][..], buf);
+    }
+
+    #[test]
+    fn core_error() {
+        fn assert_error<E: core::error::Error + Send + Sync + 'static>(_: &E) {}
+
+        let err = crate::from_slice::<u32>(&[0, 1]).unwrap_err();
+        assert_error(&err);
+        assert_eq!("unexpected end of input", err.to_string());
+    }
+
     #[test]
     fn bool() {
         assert_eq!(to_vec(&true).unwrap(), &[1]);
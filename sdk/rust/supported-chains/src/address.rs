@@ -0,0 +1,401 @@
+//! Conversions between 32-byte wormhole addresses and chain-native address strings.
+//!
+//! Hex addresses are always supported. Every other encoding is enabled by the cargo feature of the
+//! same name, or all at once with the `native-addresses` feature.
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{AddressEncoding, AddressError, ChainInfo};
+
+pub(crate) fn format(info: &ChainInfo, addr: &[u8; 32]) -> Result<String, AddressError> {
+    match info.address_encoding {
+        AddressEncoding::Hex => format_hex(info, addr),
+        #[cfg(feature = "base58")]
+        AddressEncoding::Base58 => Ok(bs58::encode(info.native_bytes(addr)?).into_string()),
+        #[cfg(feature = "bech32")]
+        AddressEncoding::Bech32 { hrp } => format_bech32(info, hrp, addr),
+        #[cfg(feature = "algorand")]
+        AddressEncoding::Algorand => Ok(format_algorand(addr)),
+        AddressEncoding::NearAccount => Err(AddressError::Hashed(info.chain)),
+        #[allow(unreachable_patterns)]
+        enc => Err(AddressError::UnsupportedEncoding(enc)),
+    }
+}
+
+pub(crate) fn parse(info: &ChainInfo, s: &str) -> Result<[u8; 32], AddressError> {
+    match info.address_encoding {
+        AddressEncoding::Hex => parse_hex(info, s),
+        #[cfg(feature = "base58")]
+        AddressEncoding::Base58 => parse_base58(info, s),
+        #[cfg(feature = "bech32")]
+        AddressEncoding::Bech32 { hrp } => parse_bech32(info, hrp, s),
+        #[cfg(feature = "algorand")]
+        AddressEncoding::Algorand => parse_algorand(s),
+        #[cfg(feature = "near")]
+        AddressEncoding::NearAccount => parse_near(s),
+        #[allow(unreachable_patterns)]
+        enc => Err(AddressError::UnsupportedEncoding(enc)),
+    }
+}
+
+// Left-pads `native` with zeros to produce a wormhole address.
+#[cfg(any(feature = "base58", feature = "bech32"))]
+fn left_pad(info: &ChainInfo, native: &[u8]) -> Result<[u8; 32], AddressError> {
+    if native.len() > info.address_len {
+        return Err(AddressError::TooLong(info.chain, info.address_len));
+    }
+
+    let mut addr = [0u8; 32];
+    addr[32 - native.len()..].copy_from_slice(native);
+    Ok(addr)
+}
+
+// Solana addresses are always 32 bytes, so shorter decodings are rejected. Re-encoding must also
+// reproduce the input, which rejects any non-canonical spelling of the same bytes.
+#[cfg(feature = "base58")]
+fn parse_base58(info: &ChainInfo, s: &str) -> Result<[u8; 32], AddressError> {
+    let invalid = || AddressError::Invalid(info.address_encoding, s.into());
+
+    let mut addr = [0u8; 32];
+    let len = bs58::decode(s).onto(&mut addr[..]).map_err(|_| invalid())?;
+    if len != info.address_len || bs58::encode(&addr[..len]).into_string() != s {
+        return Err(invalid());
+    }
+
+    left_pad(info, &addr[..len])
+}
+
+fn format_hex(info: &ChainInfo, addr: &[u8; 32]) -> Result<String, AddressError> {
+    let native = info.native_bytes(addr)?;
+
+    let mut s = String::with_capacity(2 + 2 * native.len());
+    s.push_str("0x");
+    for b in native {
+        // Writing to a `String` cannot fail.
+        let _ = write!(s, "{b:02x}");
+    }
+
+    #[cfg(feature = "eip55")]
+    if info.platform == crate::Platform::Evm {
+        s = eip55::checksum(&s[2..]);
+    }
+
+    Ok(s)
+}
+
+fn parse_hex(info: &ChainInfo, s: &str) -> Result<[u8; 32], AddressError> {
+    let invalid = || AddressError::InvalidHex(s.into());
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+    if digits.is_empty() {
+        return Err(invalid());
+    }
+    if digits.len() > 2 * info.address_len {
+        return Err(AddressError::TooLong(info.chain, info.address_len));
+    }
+
+    let mut addr = [0u8; 32];
+    for (i, d) in digits.chars().rev().enumerate() {
+        let v = d.to_digit(16).ok_or_else(invalid)? as u8;
+        addr[31 - i / 2] |= v << (4 * (i % 2));
+    }
+
+    #[cfg(feature = "eip55")]
+    if info.platform == crate::Platform::Evm && !eip55::verify(digits) {
+        return Err(AddressError::Checksum(s.into()));
+    }
+
+    Ok(addr)
+}
+
+#[cfg(feature = "eip55")]
+mod eip55 {
+    use alloc::string::String;
+
+    use sha3::{Digest, Keccak256};
+
+    /// Returns the `0x`-prefixed EIP-55 checksummed form of `lower`, which must be 40 lowercase
+    /// hex digits.
+    pub fn checksum(lower: &str) -> String {
+        let hash = Keccak256::digest(lower.as_bytes());
+
+        let mut s = String::with_capacity(2 + lower.len());
+        s.push_str("0x");
+        for (i, c) in lower.chars().enumerate() {
+            let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0xf;
+            s.push(if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            });
+        }
+        s
+    }
+
+    /// Checks the EIP-55 checksum of a mixed-case address. All lowercase or all uppercase
+    /// addresses have no checksum.
+    pub fn verify(digits: &str) -> bool {
+        let mixed_case = digits.chars().any(|c| c.is_ascii_lowercase())
+            && digits.chars().any(|c| c.is_ascii_uppercase());
+
+        !mixed_case || checksum(&digits.to_ascii_lowercase())[2..] == *digits
+    }
+}
+
+#[cfg(feature = "bech32")]
+fn format_bech32(info: &ChainInfo, hrp: &str, addr: &[u8; 32]) -> Result<String, AddressError> {
+    use bech32::{Bech32, Hrp};
+
+    // Cosmos chains use 20-byte addresses for accounts and 32-byte addresses for contracts. Assume
+    // an address is an account if it fits in 20 bytes.
+    let native = if addr[..12].iter().all(|&b| b == 0) {
+        &addr[12..]
+    } else {
+        info.native_bytes(addr)?
+    };
+
+    let hrp = Hrp::parse(hrp).expect("invalid bech32 prefix in chain metadata");
+    Ok(bech32::encode::<Bech32>(hrp, native).expect("32-byte addresses are within bech32 limits"))
+}
+
+#[cfg(feature = "bech32")]
+fn parse_bech32(info: &ChainInfo, hrp: &'static str, s: &str) -> Result<[u8; 32], AddressError> {
+    use alloc::vec::Vec;
+
+    use bech32::{primitives::decode::CheckedHrpstring, Bech32};
+
+    let checked = CheckedHrpstring::new::<Bech32>(s)
+        .map_err(|_| AddressError::Invalid(info.address_encoding, s.into()))?;
+    if !checked.hrp().as_str().eq_ignore_ascii_case(hrp) {
+        return Err(AddressError::Prefix {
+            expected: hrp,
+            address: s.into(),
+        });
+    }
+
+    // Accounts are 20 bytes and contracts are 32 bytes.
+    let native = checked.byte_iter().collect::<Vec<u8>>();
+    if native.len() != 20 && native.len() != 32 {
+        return Err(AddressError::Invalid(info.address_encoding, s.into()));
+    }
+
+    left_pad(info, &native)
+}
+
+#[cfg(feature = "algorand")]
+fn algorand_checksum(addr: &[u8; 32]) -> [u8; 4] {
+    use sha2::{Digest, Sha512_256};
+
+    let hash = Sha512_256::digest(addr);
+    let mut checksum = [0u8; 4];
+    checksum.copy_from_slice(&hash[hash.len() - 4..]);
+    checksum
+}
+
+#[cfg(feature = "algorand")]
+fn format_algorand(addr: &[u8; 32]) -> String {
+    let mut buf = [0u8; 36];
+    buf[..32].copy_from_slice(addr);
+    buf[32..].copy_from_slice(&algorand_checksum(addr));
+    data_encoding::BASE32_NOPAD.encode(&buf)
+}
+
+#[cfg(feature = "algorand")]
+fn parse_algorand(s: &str) -> Result<[u8; 32], AddressError> {
+    let buf = data_encoding::BASE32_NOPAD
+        .decode(s.as_bytes())
+        .ok()
+        .filter(|buf| buf.len() == 36)
+        .ok_or_else(|| AddressError::Invalid(AddressEncoding::Algorand, s.into()))?;
+
+    let mut addr = [0u8; 32];
+    addr.copy_from_slice(&buf[..32]);
+    if algorand_checksum(&addr) != buf[32..] {
+        return Err(AddressError::Checksum(s.into()));
+    }
+
+    Ok(addr)
+}
+
+// See https://nomicon.io/DataStructures/Account#account-id-rules.
+#[cfg(feature = "near")]
+fn parse_near(s: &str) -> Result<[u8; 32], AddressError> {
+    use sha2::{Digest, Sha256};
+
+    let valid = (2..=64).contains(&s.len())
+        && s.split(['.', '-', '_']).all(|part| {
+            !part.is_empty()
+                && part
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        });
+    if !valid {
+        return Err(AddressError::Invalid(
+            AddressEncoding::NearAccount,
+            s.into(),
+        ));
+    }
+
+    Ok(Sha256::digest(s.as_bytes()).into())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Chain;
+
+    use super::*;
+
+    fn hex(s: &str) -> [u8; 32] {
+        Chain::Sui.parse_address(s).unwrap()
+    }
+
+    #[test]
+    fn near_is_one_way() {
+        assert_eq!(
+            AddressError::Hashed(Chain::Near),
+            Chain::Near.format_address(&hex("0x1")).unwrap_err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "eip55")]
+    fn eip55() {
+        let addr = hex("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+        let s = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+        assert_eq!(s, Chain::Ethereum.format_address(&addr).unwrap());
+        assert_eq!(addr, Chain::Ethereum.parse_address(s).unwrap());
+        assert_eq!(
+            addr,
+            Chain::Ethereum
+                .parse_address(&s.to_ascii_lowercase())
+                .unwrap()
+        );
+        assert_eq!(
+            AddressError::Checksum("0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".into()),
+            Chain::Ethereum
+                .parse_address("0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed")
+                .unwrap_err()
+        );
+
+        // Only EVM addresses are checksummed.
+        assert_eq!(
+            [
+                "0x",
+                &"0".repeat(24),
+                "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+            ]
+            .concat(),
+            Chain::Aptos.format_address(&addr).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "base58")]
+    fn base58() {
+        let addr = hex("ec7372995d5cc8732397fb0ad35c0121e0eaa90d26f828a534cab54391b3a4f5");
+        let s = "Gv1KWf8DT1jKv5pKBmGaTmVszqa56Xn8YGx2Pg7i7qAk";
+
+        assert_eq!(s, Chain::Solana.format_address(&addr).unwrap());
+        assert_eq!(addr, Chain::Solana.parse_address(s).unwrap());
+
+        assert_eq!(
+            "11111111111111111111111111111111",
+            Chain::Pythnet.format_address(&[0; 32]).unwrap()
+        );
+        assert_eq!(
+            [0; 32],
+            Chain::Pythnet
+                .parse_address("11111111111111111111111111111111")
+                .unwrap()
+        );
+
+        assert!(matches!(
+            Chain::Solana.parse_address("0OIl"),
+            Err(AddressError::Invalid(..))
+        ));
+        assert!(matches!(
+            Chain::Solana.parse_address(&"z".repeat(50)),
+            Err(AddressError::Invalid(..))
+        ));
+
+        // Short addresses are not valid public keys.
+        assert!(matches!(
+            Chain::Solana.parse_address("2"),
+            Err(AddressError::Invalid(..))
+        ));
+        // Extra leading zeros change the length.
+        assert!(matches!(
+            Chain::Solana.parse_address(&["1", s].concat()),
+            Err(AddressError::Invalid(..))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "bech32")]
+    fn bech32() {
+        let contract = hex("a463ad028fb79679cfc8ce1efba35ac0e77b35080a1abe9bebe83461f176b0a3");
+        let s = "terra153366q50k7t8nn7gec00hg66crnhkdggpgdtaxltaq6xrutkkz3s992fw9";
+
+        assert_eq!(s, Chain::Terra2.format_address(&contract).unwrap());
+        assert_eq!(contract, Chain::Terra2.parse_address(s).unwrap());
+        // Contracts on chains with 20-byte accounts are still 32 bytes.
+        assert_eq!(contract, Chain::Terra.parse_address(s).unwrap());
+        assert_eq!(s, Chain::Terra.format_address(&contract).unwrap());
+        assert_eq!(
+            AddressError::Prefix {
+                expected: "wormhole",
+                address: s.into(),
+            },
+            Chain::Wormchain.parse_address(s).unwrap_err()
+        );
+
+        let mut account = [0u8; 32];
+        account[12..].copy_from_slice(&core::array::from_fn::<u8, 20, _>(|i| i as u8));
+        let s = "cosmos1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnrk363e";
+        assert_eq!(s, Chain::CosmosHub.format_address(&account).unwrap());
+        assert_eq!(account, Chain::CosmosHub.parse_address(s).unwrap());
+        assert!(matches!(
+            Chain::CosmosHub.parse_address("cosmos1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnrk363f"),
+            Err(AddressError::Invalid(..))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "algorand")]
+    fn algorand() {
+        let addr = hex("67e93fa6c8ac5c819990aa7340c0c16b508abb1178be9b30d024b8ac25193d45");
+        let s = "M7UT7JWIVROIDGMQVJZUBQGBNNIIVOYRPC7JWMGQES4KYJIZHVCRZEGFRQ";
+
+        assert_eq!(s, Chain::Algorand.format_address(&addr).unwrap());
+        assert_eq!(addr, Chain::Algorand.parse_address(s).unwrap());
+        assert_eq!(
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAY5HFKQ",
+            Chain::Algorand.format_address(&[0; 32]).unwrap()
+        );
+
+        let bad = "M7UT7JWIVROIDGMQVJZUBQGBNNIIVOYRPC7JWMGQES4KYJIZHVCRZEGFRA";
+        assert_eq!(
+            AddressError::Checksum(bad.into()),
+            Chain::Algorand.parse_address(bad).unwrap_err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "near")]
+    fn near() {
+        assert_eq!(
+            hex("148410499d3fcda4dcfd68a1ebfcdddda16ab28326448d4aae4d2f0465cdfcb7"),
+            Chain::Near
+                .parse_address("contract.portalbridge.near")
+                .unwrap()
+        );
+
+        for invalid in ["a", "Upper.near", "double..dot", "trailing.", "-leading"] {
+            assert!(matches!(
+                Chain::Near.parse_address(invalid),
+                Err(AddressError::Invalid(..))
+            ));
+        }
+    }
+}
@@ -0,0 +1,444 @@
+//! Static metadata about the chains supported by Wormhole.
+
+use alloc::string::String;
+use core::fmt;
+
+use thiserror::Error;
+
+use crate::Chain;
+
+/// The family of runtimes that a chain belongs to. Chains on the same platform share contract
+/// implementations and native address formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Platform {
+    Evm,
+    Solana,
+    Cosmos,
+    Algorand,
+    Near,
+    Sui,
+    Aptos,
+    Btc,
+}
+
+/// How a chain renders its native addresses as strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AddressEncoding {
+    /// `0x`-prefixed hex.
+    Hex,
+    /// Base58 using the bitcoin alphabet.
+    Base58,
+    /// Bech32 with the given human-readable part.
+    Bech32 { hrp: &'static str },
+    /// Base32 with a 4-byte checksum, as used by Algorand.
+    Algorand,
+    /// NEAR account ids. The wormhole address of an account is a hash of its id and cannot be
+    /// converted back.
+    NearAccount,
+}
+
+impl fmt::Display for AddressEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressEncoding::Hex => f.write_str("hex"),
+            AddressEncoding::Base58 => f.write_str("base58"),
+            AddressEncoding::Bech32 { hrp } => write!(f, "bech32 ({hrp})"),
+            AddressEncoding::Algorand => f.write_str("algorand"),
+            AddressEncoding::NearAccount => f.write_str("NEAR account"),
+        }
+    }
+}
+
+/// A Wormhole deployment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Devnet,
+}
+
+/// A hint for how the guardians determine that a message emitted on a chain is final.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Finality {
+    /// Blocks are final as soon as they are produced.
+    Instant,
+    /// The chain reports finalized blocks.
+    Finalized,
+    /// The chain reports both "safe" and finalized blocks.
+    SafeAndFinalized,
+    /// The guardians use a chain-specific rule, like waiting for a number of confirmations.
+    Custom,
+}
+
+/// Metadata about a single chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChainInfo {
+    pub chain: Chain,
+    pub platform: Platform,
+
+    /// The maximum length of a native address in bytes. Native addresses shorter than 32 bytes are
+    /// left-padded with zeros to produce a wormhole address.
+    ///
+    /// Cosmos chains use 20-byte addresses for accounts and 32-byte addresses for contracts, so
+    /// this is 32 for every bech32 chain.
+    pub address_len: usize,
+    pub address_encoding: AddressEncoding,
+
+    /// The networks on which Wormhole is deployed to this chain.
+    pub networks: &'static [Network],
+
+    /// For chains that are a testnet of another chain, like `Sepolia`, the corresponding mainnet
+    /// chain.
+    pub mainnet_chain: Option<Chain>,
+    pub finality: Finality,
+}
+
+/// The reason an address could not be converted to or from its native string form.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum AddressError {
+    #[error("address is not a valid {0} address: too long for a {1}-byte native address")]
+    TooLong(Chain, usize),
+    #[error("invalid hex address: {0}")]
+    InvalidHex(String),
+    /// The address is malformed for a non-hex encoding.
+    #[error("invalid {0} address: {1}")]
+    Invalid(AddressEncoding, String),
+    #[error("invalid checksum for address {0}")]
+    Checksum(String),
+    #[error("expected an address with the `{expected}` prefix, found {address}")]
+    Prefix {
+        expected: &'static str,
+        address: String,
+    },
+    #[error("{0} addresses are hashes and cannot be converted to a native address")]
+    Hashed(Chain),
+    #[error("support for {0} addresses is not enabled")]
+    UnsupportedEncoding(AddressEncoding),
+    #[error("no metadata for chain {0}")]
+    UnknownChain(Chain),
+}
+
+const ALL: &[Network] = &[Network::Mainnet, Network::Testnet, Network::Devnet];
+const PUBLIC: &[Network] = &[Network::Mainnet, Network::Testnet];
+const TESTNET: &[Network] = &[Network::Testnet];
+
+const fn evm(chain: Chain, networks: &'static [Network], finality: Finality) -> ChainInfo {
+    ChainInfo {
+        chain,
+        platform: Platform::Evm,
+        address_len: 20,
+        address_encoding: AddressEncoding::Hex,
+        networks,
+        mainnet_chain: None,
+        finality,
+    }
+}
+
+const fn cosmos(chain: Chain, hrp: &'static str, networks: &'static [Network]) -> ChainInfo {
+    ChainInfo {
+        chain,
+        platform: Platform::Cosmos,
+        address_len: 32,
+        address_encoding: AddressEncoding::Bech32 { hrp },
+        networks,
+        mainnet_chain: None,
+        finality: Finality::Instant,
+    }
+}
+
+const fn solana(chain: Chain, networks: &'static [Network]) -> ChainInfo {
+    ChainInfo {
+        chain,
+        platform: Platform::Solana,
+        address_len: 32,
+        address_encoding: AddressEncoding::Base58,
+        networks,
+        mainnet_chain: None,
+        finality: Finality::Finalized,
+    }
+}
+
+const fn move_vm(chain: Chain, platform: Platform) -> ChainInfo {
+    ChainInfo {
+        chain,
+        platform,
+        address_len: 32,
+        address_encoding: AddressEncoding::Hex,
+        networks: ALL,
+        mainnet_chain: None,
+        finality: Finality::Instant,
+    }
+}
+
+/// Metadata for every chain known to this crate, ordered by chain id.
+pub static CHAINS: &[ChainInfo] = &[
+    solana(Chain::Solana, ALL),
+    evm(Chain::Ethereum, ALL, Finality::SafeAndFinalized),
+    cosmos(Chain::Terra, "terra", ALL),
+    evm(Chain::Bsc, ALL, Finality::SafeAndFinalized),
+    evm(Chain::Polygon, PUBLIC, Finality::Finalized),
+    evm(Chain::Avalanche, PUBLIC, Finality::Instant),
+    evm(Chain::Oasis, PUBLIC, Finality::Instant),
+    ChainInfo {
+        chain: Chain::Algorand,
+        platform: Platform::Algorand,
+        address_len: 32,
+        address_encoding: AddressEncoding::Algorand,
+        networks: ALL,
+        mainnet_chain: None,
+        finality: Finality::Instant,
+    },
+    evm(Chain::Aurora, PUBLIC, Finality::Instant),
+    evm(Chain::Fantom, PUBLIC, Finality::Instant),
+    evm(Chain::Karura, PUBLIC, Finality::SafeAndFinalized),
+    evm(Chain::Acala, PUBLIC, Finality::SafeAndFinalized),
+    evm(Chain::Klaytn, PUBLIC, Finality::Instant),
+    evm(Chain::Celo, PUBLIC, Finality::Custom),
+    ChainInfo {
+        chain: Chain::Near,
+        platform: Platform::Near,
+        address_len: 32,
+        address_encoding: AddressEncoding::NearAccount,
+        networks: ALL,
+        mainnet_chain: None,
+        finality: Finality::Finalized,
+    },
+    evm(Chain::Moonbeam, PUBLIC, Finality::SafeAndFinalized),
+    evm(Chain::Neon, TESTNET, Finality::Finalized),
+    cosmos(Chain::Terra2, "terra", ALL),
+    cosmos(Chain::Injective, "inj", PUBLIC),
+    cosmos(Chain::Osmosis, "osmo", PUBLIC),
+    move_vm(Chain::Sui, Platform::Sui),
+    move_vm(Chain::Aptos, Platform::Aptos),
+    evm(Chain::Arbitrum, PUBLIC, Finality::SafeAndFinalized),
+    evm(Chain::Optimism, PUBLIC, Finality::SafeAndFinalized),
+    evm(Chain::Gnosis, TESTNET, Finality::Custom),
+    solana(Chain::Pythnet, &[Network::Mainnet]),
+    cosmos(Chain::Xpla, "xpla", PUBLIC),
+    ChainInfo {
+        chain: Chain::Btc,
+        platform: Platform::Btc,
+        address_len: 32,
+        address_encoding: AddressEncoding::Hex,
+        networks: &[],
+        mainnet_chain: None,
+        finality: Finality::Custom,
+    },
+    evm(Chain::Base, PUBLIC, Finality::SafeAndFinalized),
+    cosmos(Chain::Sei, "sei", PUBLIC),
+    evm(Chain::Rootstock, TESTNET, Finality::Custom),
+    evm(Chain::Scroll, PUBLIC, Finality::Finalized),
+    evm(Chain::Mantle, PUBLIC, Finality::SafeAndFinalized),
+    cosmos(Chain::Wormchain, "wormhole", ALL),
+    cosmos(Chain::CosmosHub, "cosmos", PUBLIC),
+    cosmos(Chain::Evmos, "evmos", PUBLIC),
+    cosmos(Chain::Kujira, "kujira", PUBLIC),
+    cosmos(Chain::Neutron, "neutron", PUBLIC),
+    cosmos(Chain::Celestia, "celestia", PUBLIC),
+    cosmos(Chain::Stargaze, "stars", PUBLIC),
+    cosmos(Chain::Seda, "seda", PUBLIC),
+    cosmos(Chain::Dymension, "dym", PUBLIC),
+    cosmos(Chain::Provenance, "pb", PUBLIC),
+    ChainInfo {
+        mainnet_chain: Some(Chain::Ethereum),
+        ..evm(Chain::Sepolia, TESTNET, Finality::SafeAndFinalized)
+    },
+];
+
+impl ChainInfo {
+    /// Returns true if Wormhole is deployed to this chain on `network`.
+    pub fn is_available(&self, network: Network) -> bool {
+        self.networks.contains(&network)
+    }
+
+    /// Converts a 32-byte wormhole address into this chain's native string form.
+    ///
+    /// Hex addresses are always supported. Other encodings require the cargo feature of the same
+    /// name, otherwise this returns [`AddressError::UnsupportedEncoding`]. NEAR addresses are
+    /// hashes of the account id and cannot be converted back.
+    pub fn format_address(&self, addr: &[u8; 32]) -> Result<String, AddressError> {
+        crate::address::format(self, addr)
+    }
+
+    /// Parses a native address string for this chain into a 32-byte wormhole address.
+    ///
+    /// Hex addresses may omit the `0x` prefix and leading zeros, like Aptos' `0x1`. Mixed-case
+    /// EVM addresses must have a valid EIP-55 checksum when the `eip55` feature is enabled. See
+    /// [`ChainInfo::format_address`] for the supported encodings.
+    pub fn parse_address(&self, s: &str) -> Result<[u8; 32], AddressError> {
+        crate::address::parse(self, s)
+    }
+
+    /// Returns the native bytes of `addr`, checking that the left padding is all zeros.
+    pub fn native_bytes<'a>(&self, addr: &'a [u8; 32]) -> Result<&'a [u8], AddressError> {
+        let (padding, native) = addr.split_at(32 - self.address_len);
+        if padding.iter().any(|&b| b != 0) {
+            return Err(AddressError::TooLong(self.chain, self.address_len));
+        }
+        Ok(native)
+    }
+}
+
+impl Chain {
+    /// Returns the metadata for this chain, or `None` for `Any` and unknown chains.
+    pub fn info(self) -> Option<&'static ChainInfo> {
+        CHAINS.iter().find(|info| info.chain == self)
+    }
+
+    /// Returns the platform of this chain, if known.
+    pub fn platform(self) -> Option<Platform> {
+        self.info().map(|info| info.platform)
+    }
+
+    /// Returns true if this chain runs the EVM.
+    pub fn is_evm(self) -> bool {
+        self.platform() == Some(Platform::Evm)
+    }
+
+    /// Returns the mainnet chain for this chain. This is the chain itself unless it is a testnet
+    /// of another chain.
+    pub fn mainnet_chain(self) -> Chain {
+        self.info()
+            .and_then(|info| info.mainnet_chain)
+            .unwrap_or(self)
+    }
+
+    /// Converts a 32-byte wormhole address into this chain's native string form. See
+    /// [`ChainInfo::format_address`].
+    pub fn format_address(self, addr: &[u8; 32]) -> Result<String, AddressError> {
+        self.info()
+            .ok_or(AddressError::UnknownChain(self))?
+            .format_address(addr)
+    }
+
+    /// Parses a native address string for this chain. See [`ChainInfo::parse_address`].
+    pub fn parse_address(self, s: &str) -> Result<[u8; 32], AddressError> {
+        self.info()
+            .ok_or(AddressError::UnknownChain(self))?
+            .parse_address(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn every_chain_has_info() {
+        for i in 0u16..=u16::MAX {
+            let c = Chain::from(i);
+            match c {
+                Chain::Any | Chain::Unknown(_) => assert!(c.info().is_none()),
+                _ => assert_eq!(c, c.info().unwrap().chain),
+            }
+        }
+
+        assert!(CHAINS
+            .windows(2)
+            .all(|w| u16::from(w[0].chain) < u16::from(w[1].chain)));
+        assert!(CHAINS
+            .iter()
+            .all(|info| (1..=32).contains(&info.address_len)));
+    }
+
+    #[test]
+    fn networks() {
+        assert!(Chain::Ethereum.is_evm());
+        assert!(!Chain::Solana.is_evm());
+        assert!(!Chain::Any.is_evm());
+
+        assert_eq!(Chain::Ethereum, Chain::Sepolia.mainnet_chain());
+        assert_eq!(Chain::Ethereum, Chain::Ethereum.mainnet_chain());
+        assert_eq!(Chain::Unknown(7777), Chain::Unknown(7777).mainnet_chain());
+
+        let sepolia = Chain::Sepolia.info().unwrap();
+        assert!(sepolia.is_available(Network::Testnet));
+        assert!(!sepolia.is_available(Network::Mainnet));
+    }
+
+    #[test]
+    fn bech32_address_len() {
+        // Both 20-byte accounts and 32-byte contracts are valid on every cosmos chain.
+        let contract = [0xab; 32];
+        for info in CHAINS
+            .iter()
+            .filter(|info| matches!(info.address_encoding, AddressEncoding::Bech32 { .. }))
+        {
+            assert_eq!(32, info.address_len);
+            assert_eq!(&contract[..], info.native_bytes(&contract).unwrap());
+        }
+    }
+
+    #[test]
+    fn evm_address() {
+        let s = "0x3ee18b2214aff97000d974cf647e7c347e8fa585";
+        let mut expected = [0u8; 32];
+        expected[12..].copy_from_slice(&[
+            0x3e, 0xe1, 0x8b, 0x22, 0x14, 0xaf, 0xf9, 0x70, 0x00, 0xd9, 0x74, 0xcf, 0x64, 0x7e,
+            0x7c, 0x34, 0x7e, 0x8f, 0xa5, 0x85,
+        ]);
+
+        assert_eq!(expected, Chain::Ethereum.parse_address(s).unwrap());
+        assert_eq!(
+            expected,
+            Chain::Ethereum
+                .parse_address("3EE18B2214AFF97000D974CF647E7C347E8FA585")
+                .unwrap()
+        );
+        // The output is checksummed when the `eip55` feature is enabled.
+        assert_eq!(
+            s,
+            Chain::Ethereum
+                .format_address(&expected)
+                .unwrap()
+                .to_ascii_lowercase()
+        );
+
+        let mut long = expected;
+        long[0] = 1;
+        assert_eq!(
+            AddressError::TooLong(Chain::Ethereum, 20),
+            Chain::Ethereum.format_address(&long).unwrap_err()
+        );
+        assert!(matches!(
+            Chain::Ethereum.parse_address(&["0x", &"11".repeat(21)].concat()),
+            Err(AddressError::TooLong(..))
+        ));
+        assert!(matches!(
+            Chain::Ethereum.parse_address("0xzz"),
+            Err(AddressError::InvalidHex(_))
+        ));
+        assert!(matches!(
+            Chain::Ethereum.parse_address("0x"),
+            Err(AddressError::InvalidHex(_))
+        ));
+    }
+
+    #[test]
+    fn short_hex_address() {
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        assert_eq!(one, Chain::Aptos.parse_address("0x1").unwrap());
+        assert_eq!(
+            ["0x", &"0".repeat(63), "1"].concat(),
+            Chain::Aptos.format_address(&one).unwrap()
+        );
+
+        let mut odd = [0u8; 32];
+        odd[30..].copy_from_slice(&[0x0a, 0xbc]);
+        assert_eq!(odd, Chain::Sui.parse_address("abc").unwrap());
+    }
+
+    #[test]
+    fn unsupported() {
+        #[cfg(not(feature = "base58"))]
+        assert_eq!(
+            AddressError::UnsupportedEncoding(AddressEncoding::Base58),
+            Chain::Solana.format_address(&[1; 32]).unwrap_err()
+        );
+        assert_eq!(
+            AddressError::UnknownChain(Chain::Any),
+            Chain::Any.parse_address("0x1").unwrap_err()
+        );
+    }
+}
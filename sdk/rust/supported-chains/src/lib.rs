@@ -1,10 +1,20 @@
 //! Provide Types and Data about Wormhole's supported chains.
 
-use std::{fmt, str::FromStr};
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+use core::{fmt, str::FromStr};
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
+pub use info::{AddressEncoding, AddressError, ChainInfo, Finality, Network, Platform, CHAINS};
+
+mod address;
+mod info;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Chain {
     /// In the wormhole wire format, 0 indicates that a message is for any destination chain, it is
@@ -0,0 +1,355 @@
+//! Derive macros for Wormhole governance packets.
+//!
+//! These macros are re-exported from the `governance` module of `wormhole-vaas-serde` and should
+//! be used from there. See the documentation of that module for details.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, spanned::Spanned, Attribute, Data, DeriveInput, Error, Fields,
+    LitInt, LitStr, Path,
+};
+
+/// Implements `Serialize` and `Deserialize` for a governance packet struct with `chain` and
+/// `action` fields, along with an associated `MODULE` constant.
+///
+/// The path to the `wormhole_sdk` crate can be overridden with `#[governance(crate = "...")]`.
+#[proc_macro_derive(GovernancePacket, attributes(governance))]
+pub fn derive_governance_packet(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    governance_packet(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Implements `GovernanceAction` for an enum of governance actions.
+///
+/// The path to the `wormhole_sdk` crate can be overridden with `#[governance(crate = "...")]`.
+#[proc_macro_derive(GovernanceAction, attributes(governance))]
+pub fn derive_governance_action(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    governance_action(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn governance_packet(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let attrs = GovernanceAttrs::parse(&input.attrs)?;
+    let krate = attrs.krate;
+    let module = attrs.module.ok_or_else(|| {
+        Error::new(
+            Span::call_site(),
+            "missing `#[governance(module = \"...\")]` attribute",
+        )
+    })?;
+    if module.value().len() > 32 {
+        return Err(Error::new(
+            module.span(),
+            "governance module names cannot be longer than 32 bytes",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(s) => &s.fields,
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "`GovernancePacket` can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut names = fields
+        .iter()
+        .filter_map(|f| f.ident.as_ref())
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    names.sort();
+    if names != ["action", "chain"] {
+        return Err(Error::new(
+            fields.span(),
+            "governance packets must have exactly two named fields: `chain` and `action`",
+        ));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut de_generics = input.generics.clone();
+    de_generics.params.insert(0, parse_quote!('de));
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The governance module for this packet, left-padded with zeros to 32 bytes.
+            pub const MODULE: [u8; 32] = #krate::governance::module(#module);
+        }
+
+        impl #impl_generics #krate::governance::__private::serde::Serialize
+            for #name #ty_generics #where_clause
+        {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: #krate::governance::__private::serde::Serializer,
+            {
+                #krate::governance::__private::serialize_packet(
+                    &Self::MODULE,
+                    &self.chain,
+                    &self.action,
+                    serializer,
+                )
+            }
+        }
+
+        impl #de_impl_generics #krate::governance::__private::serde::Deserialize<'de>
+            for #name #ty_generics #where_clause
+        {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: #krate::governance::__private::serde::Deserializer<'de>,
+            {
+                let (chain, action) = #krate::governance::__private::deserialize_packet(
+                    &Self::MODULE,
+                    #module,
+                    deserializer,
+                )?;
+
+                Ok(#name { chain, action })
+            }
+        }
+    })
+}
+
+fn governance_action(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let attrs = GovernanceAttrs::parse(&input.attrs)?;
+    if let Some(module) = attrs.module {
+        return Err(Error::new(
+            module.span(),
+            "`module` is only supported on governance packets",
+        ));
+    }
+    let krate = attrs.krate;
+    let serde_crate = LitStr::new(
+        &quote!(#krate::governance::__private::serde).to_string(),
+        Span::call_site(),
+    );
+
+    let variants = match &input.data {
+        Data::Enum(e) => &e.variants,
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "`GovernanceAction` can only be derived for enums",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let mut ids = Vec::with_capacity(variants.len());
+    let mut action_arms = Vec::with_capacity(variants.len());
+    let mut ser_arms = Vec::with_capacity(variants.len());
+    let mut de_arms = Vec::with_capacity(variants.len());
+
+    for v in variants {
+        let id = action_id(&v.attrs)?.ok_or_else(|| {
+            Error::new(
+                v.ident.span(),
+                "governance actions must have a `#[serde(rename = \"<action>\")]` attribute",
+            )
+        })?;
+        if ids.iter().any(|(i, _)| i == &id) {
+            return Err(Error::new(v.ident.span(), "duplicate governance action"));
+        }
+
+        let variant = &v.ident;
+        let payload = variant;
+        let lit = LitInt::new(&id.to_string(), Span::call_site());
+
+        match &v.fields {
+            Fields::Named(fields) => {
+                let idents = fields.named.iter().map(|f| &f.ident).collect::<Vec<_>>();
+                let attrs = fields
+                    .named
+                    .iter()
+                    .map(|f| {
+                        f.attrs
+                            .iter()
+                            .filter(|a| a.path().is_ident("serde"))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>();
+                let tys = fields.named.iter().map(|f| &f.ty).collect::<Vec<_>>();
+
+                // The payload is serialized from references to the fields of the variant so that
+                // the action doesn't need to be cloned.
+                let ser_payload_def = quote! {
+                    #[derive(#krate::governance::__private::serde::Serialize)]
+                    #[serde(crate = #serde_crate)]
+                    struct #payload<'__payload> {
+                        #(#(#attrs)* #idents: &'__payload #tys,)*
+                    }
+                };
+                let de_payload_def = quote! {
+                    #[derive(#krate::governance::__private::serde::Deserialize)]
+                    #[serde(crate = #serde_crate)]
+                    struct #payload {
+                        #(#(#attrs)* #idents: #tys,)*
+                    }
+                };
+
+                action_arms.push(quote! { #name::#variant { .. } => #lit });
+                ser_arms.push(quote! {
+                    #name::#variant { #(#idents),* } => {
+                        #ser_payload_def
+                        #krate::governance::__private::serde::Serialize::serialize(
+                            &#payload { #(#idents),* },
+                            serializer,
+                        )
+                    }
+                });
+                de_arms.push(quote! {
+                    #lit => {
+                        #de_payload_def
+                        let #payload { #(#idents),* } =
+                            #krate::governance::__private::serde::Deserialize::deserialize(
+                                deserializer,
+                            )?;
+                        Ok(#name::#variant { #(#idents),* })
+                    }
+                });
+            }
+            Fields::Unit => {
+                action_arms.push(quote! { #name::#variant => #lit });
+                ser_arms.push(quote! {
+                    #name::#variant => {
+                        #krate::governance::__private::serde::Serializer::serialize_unit_struct(
+                            serializer,
+                            stringify!(#variant),
+                        )
+                    }
+                });
+                de_arms.push(quote! {
+                    #lit => {
+                        #[derive(#krate::governance::__private::serde::Deserialize)]
+                        #[serde(crate = #serde_crate)]
+                        struct #payload;
+
+                        let #payload = #krate::governance::__private::serde::Deserialize::deserialize(
+                            deserializer,
+                        )?;
+                        Ok(#name::#variant)
+                    }
+                });
+            }
+            Fields::Unnamed(_) => {
+                return Err(Error::new(
+                    v.ident.span(),
+                    "governance actions must be unit or struct variants",
+                ))
+            }
+        }
+
+        ids.push((id, lit));
+    }
+
+    let lits = ids.iter().map(|(_, l)| l);
+    let expected = format!(
+        "expected one of: {}",
+        ids.iter()
+            .map(|(i, _)| i.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    Ok(quote! {
+        impl #krate::governance::GovernanceAction for #name {
+            const ACTIONS: &'static [u8] = &[#(#lits),*];
+
+            fn action(&self) -> u8 {
+                match self {
+                    #(#action_arms,)*
+                }
+            }
+
+            fn serialize_payload<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: #krate::governance::__private::serde::Serializer,
+            {
+                match self {
+                    #(#ser_arms,)*
+                }
+            }
+
+            fn deserialize_payload<'de, D>(
+                action: u8,
+                deserializer: D,
+            ) -> ::core::result::Result<Self, D::Error>
+            where
+                D: #krate::governance::__private::serde::Deserializer<'de>,
+            {
+                match action {
+                    #(#de_arms,)*
+                    v => Err(<D::Error as #krate::governance::__private::serde::de::Error>::custom(
+                        format_args!("invalid action: {v}, {}", #expected),
+                    )),
+                }
+            }
+        }
+    })
+}
+
+// Options from `#[governance(...)]` attributes.
+struct GovernanceAttrs {
+    module: Option<LitStr>,
+    krate: Path,
+}
+
+impl GovernanceAttrs {
+    // Parses `#[governance(module = "...", crate = "...")]`.
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut module = None;
+        let mut krate = None;
+        for attr in attrs.iter().filter(|a| a.path().is_ident("governance")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("module") {
+                    module = Some(meta.value()?.parse::<LitStr>()?);
+                    Ok(())
+                } else if meta.path.is_ident("crate") {
+                    krate = Some(meta.value()?.parse::<LitStr>()?.parse::<Path>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported governance attribute"))
+                }
+            })?;
+        }
+
+        Ok(GovernanceAttrs {
+            module,
+            krate: krate.unwrap_or_else(|| parse_quote!(::wormhole_sdk)),
+        })
+    }
+}
+
+// Extracts the action id from `#[serde(rename = "<id>")]`.
+fn action_id(attrs: &[Attribute]) -> syn::Result<Option<u8>> {
+    let mut id = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let s = meta.value()?.parse::<LitStr>()?;
+                id = Some(
+                    s.value()
+                        .parse::<u8>()
+                        .map_err(|e| Error::new(s.span(), format!("invalid action: {e}")))?,
+                );
+            } else if meta.input.peek(syn::Token![=]) {
+                // Skip over other `key = value` attributes.
+                let _: syn::Expr = meta.value()?.parse()?;
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(id)
+}
@@ -1,15 +1,24 @@
 //! Parsers for Accountant Actions..
 //!
 //! Accountant is a security mechanism for the token bridge.
-//! It needs a modify_balance message to be able to correct for unforeseen events.
+//! It needs a modify_balance message to be able to correct for unforeseen events, a
+//! revert_transfer message to undo a transfer that should never have been committed, a
+//! set_outflow_limit message to cap how much of a token can leave a chain in a given window, and a
+//! release_transfer message to commit a transfer that is being held back by that cap.
 
 use bstr::BString;
 use serde::{Deserialize, Serialize};
 
-use crate::{accountant_modification::ModificationKind, Address, Amount, Chain};
+use crate::{
+    accountant_modification::ModificationKind,
+    governance::{GovernanceAction, GovernancePacket},
+    Address, Amount, Chain,
+};
 
 /// Represents a governance action targeted at the Accountant.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Serialize, Deserialize, GovernanceAction, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
 pub enum Action {
     // Modify balance for accountant
     #[serde(rename = "1")]
@@ -23,10 +32,37 @@ pub enum Action {
         #[serde(with = "crate::arraystring")]
         reason: BString,
     },
+    // Undo the balance changes made by a previously committed transfer
+    #[serde(rename = "2")]
+    RevertTransfer {
+        emitter_chain: u16,
+        emitter_address: Address,
+        sequence: u64,
+        #[serde(with = "crate::arraystring")]
+        reason: BString,
+    },
+    // Limit the amount of a token that can flow out of a chain within a rolling window of
+    // `window` seconds.  A `window` of 0 removes the limit.
+    #[serde(rename = "3")]
+    SetOutflowLimit {
+        chain_id: u16,
+        token_chain: u16,
+        token_address: Address,
+        limit: Amount,
+        window: u64,
+    },
+    // Commit a transfer that reached quorum but is being held back by an outflow limit
+    #[serde(rename = "4")]
+    ReleaseTransfer {
+        emitter_chain: u16,
+        emitter_address: Address,
+        sequence: u64,
+    },
 }
 
 /// Represents the payload for a governance VAA targeted at the Accountant.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(GovernancePacket, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[governance(module = "GlobalAccountant")]
 pub struct GovernancePacket {
     /// The chain on which the governance action should be carried out.
     pub chain: Chain,
@@ -35,267 +71,8 @@ pub struct GovernancePacket {
     pub action: Action,
 }
 
-// MODULE = "GlobalAccountant"
-pub const MODULE: [u8; 32] =
-    *b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00GlobalAccountant";
-
-// The wire format for GovernancePackets is wonky and doesn't lend itself well to auto-deriving
-// Serialize / Deserialize so we implement it manually here.
-mod governance_packet_impl {
-    use std::fmt;
-
-    use serde::{
-        de::{Error, MapAccess, SeqAccess, Visitor},
-        ser::SerializeStruct,
-        Deserialize, Deserializer, Serialize, Serializer,
-    };
-
-    use crate::{
-        accountant::{Action, GovernancePacket, MODULE},
-        Address, Amount,
-    };
-
-    struct Module;
-
-    impl Serialize for Module {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: Serializer,
-        {
-            MODULE.serialize(serializer)
-        }
-    }
-
-    impl<'de> Deserialize<'de> for Module {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            let arr = <[u8; 32]>::deserialize(deserializer)?;
-
-            if arr == MODULE {
-                Ok(Module)
-            } else {
-                Err(Error::custom(
-                    "invalid governance module, expected \"GlobalAccountant\"",
-                ))
-            }
-        }
-    }
-
-    #[derive(Serialize, Deserialize)]
-    struct ModifyBalance {
-        sequence: u64,
-        chain_id: u16,
-        token_chain: u16,
-        token_address: Address,
-        kind: super::ModificationKind,
-        amount: Amount,
-        #[serde(with = "crate::arraystring")]
-        reason: bstr::BString,
-    }
-
-    impl Serialize for GovernancePacket {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: Serializer,
-        {
-            let mut seq = serializer.serialize_struct("GovernancePacket", 4)?;
-            seq.serialize_field("module", &Module)?;
-
-            // The wire format encodes the action before the chain and then appends the actual
-            // action payload.
-            match self.action.clone() {
-                Action::ModifyBalance {
-                    sequence,
-                    chain_id,
-                    token_chain,
-                    token_address,
-                    kind,
-                    amount,
-                    reason,
-                } => {
-                    seq.serialize_field("action", &1u8)?;
-                    seq.serialize_field("chain", &self.chain)?;
-                    seq.serialize_field(
-                        "payload",
-                        &ModifyBalance {
-                            sequence,
-                            chain_id,
-                            token_chain,
-                            token_address,
-                            kind,
-                            amount,
-                            reason,
-                        },
-                    )?;
-                }
-            }
-
-            seq.end()
-        }
-    }
-
-    struct GovernancePacketVisitor;
-
-    impl<'de> Visitor<'de> for GovernancePacketVisitor {
-        type Value = GovernancePacket;
-
-        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            f.write_str("struct GovernancePacket")
-        }
-
-        #[inline]
-        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-        where
-            A: SeqAccess<'de>,
-        {
-            static EXPECTING: &str = "struct GovernancePacket with 4 elements";
-
-            let _: Module = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(0, &EXPECTING))?;
-            let act: u8 = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(1, &EXPECTING))?;
-            let chain = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(2, &EXPECTING))?;
-
-            let action = match act {
-                1 => {
-                    let ModifyBalance {
-                        sequence,
-                        chain_id,
-                        token_chain,
-                        token_address,
-                        kind,
-                        amount,
-                        reason,
-                    } = seq
-                        .next_element()?
-                        .ok_or_else(|| Error::invalid_length(3, &EXPECTING))?;
-                    Action::ModifyBalance {
-                        sequence,
-                        chain_id,
-                        token_chain,
-                        token_address,
-                        kind,
-                        amount,
-                        reason,
-                    }
-                }
-                v => {
-                    return Err(Error::custom(format_args!(
-                        "invalid value {v}, expected one of 1"
-                    )))
-                }
-            };
-
-            Ok(GovernancePacket { chain, action })
-        }
-
-        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
-        where
-            A: MapAccess<'de>,
-        {
-            #[derive(Serialize, Deserialize)]
-            #[serde(rename_all = "snake_case")]
-            enum Field {
-                Module,
-                Action,
-                Chain,
-                Payload,
-            }
-
-            let mut module = None;
-            let mut chain = None;
-            let mut action = None;
-            let mut payload = None;
-
-            while let Some(key) = map.next_key::<Field>()? {
-                match key {
-                    Field::Module => {
-                        if module.is_some() {
-                            return Err(Error::duplicate_field("module"));
-                        }
-
-                        module = map.next_value::<Module>().map(Some)?;
-                    }
-                    Field::Action => {
-                        if action.is_some() {
-                            return Err(Error::duplicate_field("action"));
-                        }
-
-                        action = map.next_value::<u8>().map(Some)?;
-                    }
-                    Field::Chain => {
-                        if chain.is_some() {
-                            return Err(Error::duplicate_field("chain"));
-                        }
-
-                        chain = map.next_value().map(Some)?;
-                    }
-                    Field::Payload => {
-                        if payload.is_some() {
-                            return Err(Error::duplicate_field("payload"));
-                        }
-
-                        let a = action.as_ref().copied().ok_or_else(|| {
-                            Error::custom("`action` must be known before deserializing `payload`")
-                        })?;
-
-                        let p = match a {
-                            1 => {
-                                let ModifyBalance {
-                                    sequence,
-                                    chain_id,
-                                    token_chain,
-                                    token_address,
-                                    kind,
-                                    amount,
-                                    reason,
-                                } = map.next_value()?;
-                                Action::ModifyBalance {
-                                    sequence,
-                                    chain_id,
-                                    token_chain,
-                                    token_address,
-                                    kind,
-                                    amount,
-                                    reason,
-                                }
-                            }
-                            v => {
-                                return Err(Error::custom(format_args!(
-                                    "invalid action: {v}, expected one of: 1"
-                                )))
-                            }
-                        };
-
-                        payload = Some(p);
-                    }
-                }
-            }
-
-            let _ = module.ok_or_else(|| Error::missing_field("module"))?;
-            let chain = chain.ok_or_else(|| Error::missing_field("chain"))?;
-            let action = payload.ok_or_else(|| Error::missing_field("payload"))?;
-
-            Ok(GovernancePacket { chain, action })
-        }
-    }
-
-    impl<'de> Deserialize<'de> for GovernancePacket {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            const FIELDS: &[&str] = &["module", "action", "chain", "payload"];
-            deserializer.deserialize_struct("GovernancePacket", FIELDS, GovernancePacketVisitor)
-        }
-    }
-}
+/// The governance module for this contract, left-padded with zeros to 32 bytes.
+pub const MODULE: [u8; 32] = GovernancePacket::MODULE;
 
 #[cfg(test)]
 mod test {
@@ -375,4 +152,107 @@ mod test {
         let encoded = serde_json::to_string(&vaa).unwrap();
         assert_eq!(vaa, serde_json::from_str(&encoded).unwrap());
     }
+
+    #[test]
+    fn revert_transfer() {
+        let buf = [
+            //  module = "GlobalAccountant"
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x47, 0x6c, 0x6f, 0x62, 0x61, 0x6c, 0x41, 0x63, 0x63, 0x6f, 0x75, 0x6e,
+            0x74, 0x61, 0x6e, 0x74, // action
+            0x02, // chain
+            0x0c, 0x20, // emitter_chain
+            0x00, 0x02, // emitter_address
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3e, 0xe1,
+            0x8b, 0x22, 0x14, 0xaf, 0xf9, 0x70, 0x00, 0xd9, 0x74, 0xcf, 0x64, 0x7e, 0x7c, 0x34,
+            0x7e, 0x8f, 0xa5, 0x85, // sequence
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x86, 0xa0, // reason
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x52,
+            0x45, 0x4f, 0x52, 0x47,
+        ];
+
+        let mut emitter_address = [0u8; 32];
+        emitter_address[12..].copy_from_slice(&buf[49..69]);
+        let packet = GovernancePacket {
+            chain: Chain::Wormchain,
+            action: Action::RevertTransfer {
+                emitter_chain: 2,
+                emitter_address: Address(emitter_address),
+                sequence: 100_000,
+                reason: "REORG".into(),
+            },
+        };
+
+        assert_eq!(buf.as_ref(), &serde_wormhole::to_vec(&packet).unwrap());
+        assert_eq!(packet, serde_wormhole::from_slice(&buf).unwrap());
+    }
+
+    #[test]
+    fn set_outflow_limit() {
+        let buf = [
+            //  module = "GlobalAccountant"
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x47, 0x6c, 0x6f, 0x62, 0x61, 0x6c, 0x41, 0x63, 0x63, 0x6f, 0x75, 0x6e,
+            0x74, 0x61, 0x6e, 0x74, // action
+            0x03, // chain
+            0x0c, 0x20, // chain_id
+            0x00, 0x02, // token_chain
+            0x00, 0x02, // token_address
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xc0, 0x2a,
+            0xaa, 0x39, 0xb2, 0x23, 0xfe, 0x8d, 0x0a, 0x0e, 0x5c, 0x4f, 0x27, 0xea, 0xd9, 0x08,
+            0x3c, 0x75, 0x6c, 0xc2, // limit
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x3b, 0x9a, 0xca, 0x00, // window
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x51, 0x80,
+        ];
+
+        let mut token_address = [0u8; 32];
+        token_address[12..].copy_from_slice(&buf[51..71]);
+        let packet = GovernancePacket {
+            chain: Chain::Wormchain,
+            action: Action::SetOutflowLimit {
+                chain_id: 2,
+                token_chain: 2,
+                token_address: Address(token_address),
+                limit: Amount::from(1_000_000_000u64),
+                window: 86_400,
+            },
+        };
+
+        assert_eq!(buf.as_ref(), &serde_wormhole::to_vec(&packet).unwrap());
+        assert_eq!(packet, serde_wormhole::from_slice(&buf).unwrap());
+    }
+
+    #[test]
+    fn release_transfer() {
+        let buf = [
+            //  module = "GlobalAccountant"
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x47, 0x6c, 0x6f, 0x62, 0x61, 0x6c, 0x41, 0x63, 0x63, 0x6f, 0x75, 0x6e,
+            0x74, 0x61, 0x6e, 0x74, // action
+            0x04, // chain
+            0x0c, 0x20, // emitter_chain
+            0x00, 0x02, // emitter_address
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3e, 0xe1,
+            0x8b, 0x22, 0x14, 0xaf, 0xf9, 0x70, 0x00, 0xd9, 0x74, 0xcf, 0x64, 0x7e, 0x7c, 0x34,
+            0x7e, 0x8f, 0xa5, 0x85, // sequence
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x86, 0xa0,
+        ];
+
+        let mut emitter_address = [0u8; 32];
+        emitter_address[12..].copy_from_slice(&buf[49..69]);
+        let packet = GovernancePacket {
+            chain: Chain::Wormchain,
+            action: Action::ReleaseTransfer {
+                emitter_chain: 2,
+                emitter_address: Address(emitter_address),
+                sequence: 100_000,
+            },
+        };
+
+        assert_eq!(buf.as_ref(), &serde_wormhole::to_vec(&packet).unwrap());
+        assert_eq!(packet, serde_wormhole::from_slice(&buf).unwrap());
+    }
 }
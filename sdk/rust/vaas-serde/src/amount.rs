@@ -0,0 +1,313 @@
+//! Arithmetic and token bridge decimal conversions for [`Amount`].
+
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The number of decimals that token bridge transfer amounts are normalized to.
+pub const NORMALIZED_DECIMALS: u8 = 8;
+
+/// Wormhole specifies an amount as a uint256 encoded in big-endian order.
+///
+/// The byte representation matches cosmwasm's `Uint256`. With the `cosmwasm` feature, `Amount`
+/// converts to and from `Uint256` and `Uint128` directly.
+#[derive(
+    Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub struct Amount(pub [u8; 32]);
+
+/// The error returned when an `Amount` does not fit in a smaller integer type.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("amount is too large for the target integer type")]
+pub struct TryFromAmountError(());
+
+impl Amount {
+    pub const ZERO: Amount = Amount([0; 32]);
+    pub const MAX: Amount = Amount([0xff; 32]);
+
+    pub const fn from_be_bytes(bytes: [u8; 32]) -> Amount {
+        Amount(bytes)
+    }
+
+    pub const fn to_be_bytes(self) -> [u8; 32] {
+        self.0
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Amount::ZERO
+    }
+
+    pub fn checked_add(self, rhs: Amount) -> Option<Amount> {
+        self.to_u256()
+            .checked_add(rhs.to_u256())
+            .map(Amount::from_u256)
+    }
+
+    pub fn checked_sub(self, rhs: Amount) -> Option<Amount> {
+        self.to_u256()
+            .checked_sub(rhs.to_u256())
+            .map(Amount::from_u256)
+    }
+
+    pub fn checked_mul(self, rhs: Amount) -> Option<Amount> {
+        self.to_u256()
+            .checked_mul(rhs.to_u256())
+            .map(Amount::from_u256)
+    }
+
+    /// Returns `None` if `rhs` is zero.
+    pub fn checked_div(self, rhs: Amount) -> Option<Amount> {
+        self.to_u256()
+            .checked_div(rhs.to_u256())
+            .map(Amount::from_u256)
+    }
+
+    /// Returns `None` if `rhs` is zero.
+    pub fn checked_rem(self, rhs: Amount) -> Option<Amount> {
+        self.to_u256()
+            .checked_rem(rhs.to_u256())
+            .map(Amount::from_u256)
+    }
+
+    /// Converts an amount of a token with `decimals` decimals into the amount carried by a
+    /// token bridge transfer. Tokens with more than 8 decimals are truncated to 8 decimals and
+    /// any dust is discarded; all other amounts are unchanged.
+    pub fn normalize(self, decimals: u8) -> Amount {
+        match scale(decimals) {
+            Some(s) => Amount::from_u256(self.to_u256() / s),
+            // Every U256 is smaller than the divisor.
+            None => Amount::ZERO,
+        }
+    }
+
+    /// The inverse of [`Amount::normalize`]: converts a token bridge transfer amount back into
+    /// an amount of a token with `decimals` decimals. Returns `None` if the result overflows.
+    pub fn denormalize(self, decimals: u8) -> Option<Amount> {
+        match scale(decimals) {
+            Some(s) => self.to_u256().checked_mul(s).map(Amount::from_u256),
+            None if self.is_zero() => Some(Amount::ZERO),
+            None => None,
+        }
+    }
+
+    /// Removes the dust that [`Amount::normalize`] would discard, i.e. the largest amount not
+    /// greater than `self` that survives a round trip through the token bridge.
+    pub fn trim_dust(self, decimals: u8) -> Amount {
+        match scale(decimals) {
+            Some(s) => {
+                let v = self.to_u256();
+                Amount::from_u256(v - v % s)
+            }
+            None => Amount::ZERO,
+        }
+    }
+
+    fn to_u256(self) -> U256 {
+        U256::from_big_endian(&self.0)
+    }
+
+    fn from_u256(v: U256) -> Amount {
+        let mut buf = [0; 32];
+        v.to_big_endian(&mut buf);
+        Amount(buf)
+    }
+}
+
+// Returns the factor between an amount with `decimals` decimals and its normalized form, or
+// `None` if it doesn't fit in a U256.
+fn scale(decimals: u8) -> Option<U256> {
+    match decimals.checked_sub(NORMALIZED_DECIMALS) {
+        None | Some(0) => Some(U256::one()),
+        Some(exp) => U256::from(10).checked_pow(exp.into()),
+    }
+}
+
+macro_rules! impl_from_uint {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Amount {
+            fn from(v: $t) -> Amount {
+                let mut buf = [0; 32];
+                buf[32 - size_of::<$t>()..].copy_from_slice(&v.to_be_bytes());
+                Amount(buf)
+            }
+        }
+
+        impl TryFrom<Amount> for $t {
+            type Error = TryFromAmountError;
+
+            fn try_from(a: Amount) -> Result<$t, Self::Error> {
+                let (high, low) = a.0.split_at(32 - size_of::<$t>());
+                if high.iter().any(|&b| b != 0) {
+                    return Err(TryFromAmountError(()));
+                }
+
+                let mut buf = [0; size_of::<$t>()];
+                buf.copy_from_slice(low);
+                Ok(<$t>::from_be_bytes(buf))
+            }
+        }
+    )*};
+}
+
+impl_from_uint!(u8, u16, u32, u64, u128);
+
+#[cfg(feature = "cosmwasm")]
+mod cosmwasm {
+    use cosmwasm_std::{Uint128, Uint256};
+
+    use super::{Amount, TryFromAmountError};
+
+    impl From<Uint256> for Amount {
+        fn from(v: Uint256) -> Amount {
+            Amount(v.to_be_bytes())
+        }
+    }
+
+    impl From<Amount> for Uint256 {
+        fn from(a: Amount) -> Uint256 {
+            Uint256::from_be_bytes(a.0)
+        }
+    }
+
+    impl From<Uint128> for Amount {
+        fn from(v: Uint128) -> Amount {
+            Amount::from(v.u128())
+        }
+    }
+
+    impl TryFrom<Amount> for Uint128 {
+        type Error = TryFromAmountError;
+
+        fn try_from(a: Amount) -> Result<Uint128, Self::Error> {
+            u128::try_from(a).map(Uint128::new)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    #[test]
+    fn conversions() {
+        let a = Amount::from(0x0102_0304_0506_0708u64);
+        assert_eq!([1, 2, 3, 4, 5, 6, 7, 8], a.0[24..]);
+        assert!(a.0[..24].iter().all(|&b| b == 0));
+        assert_eq!(Ok(0x0102_0304_0506_0708u64), u64::try_from(a));
+
+        assert_eq!(Ok(u128::MAX), u128::try_from(Amount::from(u128::MAX)));
+        let _ = u64::try_from(Amount::from(u128::MAX)).unwrap_err();
+        let _ = u128::try_from(Amount::MAX).unwrap_err();
+    }
+
+    #[test]
+    #[cfg(feature = "cosmwasm")]
+    fn cosmwasm() {
+        use cosmwasm_std::{Uint128, Uint256};
+
+        let v = Uint256::from(u128::MAX) * Uint256::from(3u8);
+        assert_eq!(v, Uint256::from(Amount::from(v)));
+        assert_eq!(Amount::MAX, Amount::from(Uint256::MAX));
+
+        let a = Amount::from(Uint128::new(1_234_567));
+        assert_eq!(Amount::from(1_234_567u64), a);
+        assert_eq!(Ok(Uint128::new(1_234_567)), Uint128::try_from(a));
+        let _ = Uint128::try_from(Amount::from(v)).unwrap_err();
+    }
+
+    #[test]
+    fn checked() {
+        assert_eq!(None, Amount::MAX.checked_add(Amount::from(1u8)));
+        assert_eq!(None, Amount::ZERO.checked_sub(Amount::from(1u8)));
+        assert_eq!(None, Amount::MAX.checked_mul(Amount::from(2u8)));
+        assert_eq!(None, Amount::MAX.checked_div(Amount::ZERO));
+        assert_eq!(None, Amount::MAX.checked_rem(Amount::ZERO));
+        assert_eq!(
+            Some(Amount::from(1u8)),
+            Amount::MAX.checked_div(Amount::MAX)
+        );
+    }
+
+    #[test]
+    fn normalize() {
+        let eth = Amount::from(1_234_567_890_123_456_789u64);
+        assert_eq!(Amount::from(123_456_789u64), eth.normalize(18));
+        assert_eq!(
+            Some(Amount::from(1_234_567_890_000_000_000u64)),
+            eth.normalize(18).denormalize(18)
+        );
+        assert_eq!(
+            Amount::from(1_234_567_890_000_000_000u64),
+            eth.trim_dust(18)
+        );
+
+        let usdc = Amount::from(1_234_567u64);
+        assert_eq!(usdc, usdc.normalize(6));
+        assert_eq!(Some(usdc), usdc.denormalize(6));
+        assert_eq!(usdc, usdc.trim_dust(6));
+
+        assert_eq!(Amount::ZERO, Amount::MAX.normalize(u8::MAX));
+        assert_eq!(None, Amount::from(1u8).denormalize(u8::MAX));
+        assert_eq!(Some(Amount::ZERO), Amount::ZERO.denormalize(u8::MAX));
+        assert_eq!(None, Amount::MAX.denormalize(9));
+    }
+
+    fn amount() -> impl Strategy<Value = Amount> {
+        any::<[u8; 32]>().prop_map(Amount)
+    }
+
+    proptest! {
+        #[test]
+        fn matches_u128(a: u64, b: u64) {
+            let (x, y) = (Amount::from(a), Amount::from(b));
+            let (a, b) = (u128::from(a), u128::from(b));
+
+            prop_assert_eq!(Some(Amount::from(a + b)), x.checked_add(y));
+            prop_assert_eq!(a.checked_sub(b).map(Amount::from), x.checked_sub(y));
+            prop_assert_eq!(Some(Amount::from(a * b)), x.checked_mul(y));
+            prop_assert_eq!(a.checked_div(b).map(Amount::from), x.checked_div(y));
+            prop_assert_eq!(a.checked_rem(b).map(Amount::from), x.checked_rem(y));
+        }
+
+        #[test]
+        fn add_sub_round_trip(a in amount(), b in amount()) {
+            if let Some(sum) = a.checked_add(b) {
+                prop_assert_eq!(Some(a), sum.checked_sub(b));
+                prop_assert_eq!(Some(b), sum.checked_sub(a));
+            } else {
+                prop_assert!(Amount::MAX.checked_sub(a).unwrap() < b);
+            }
+        }
+
+        #[test]
+        fn u128_round_trip(v: u128) {
+            prop_assert_eq!(Ok(v), u128::try_from(Amount::from(v)));
+        }
+
+        #[test]
+        fn normalize_round_trip(a in amount(), decimals in 0u8..=90) {
+            let n = a.normalize(decimals);
+            prop_assert!(n <= a);
+
+            let d = n.denormalize(decimals).unwrap();
+            prop_assert_eq!(a.trim_dust(decimals), d);
+            prop_assert!(d <= a);
+            prop_assert_eq!(n, d.normalize(decimals));
+            if decimals <= NORMALIZED_DECIMALS {
+                prop_assert_eq!(a, d);
+            } else if let Some(s) = scale(decimals) {
+                prop_assert!(Amount::from_u256(s) > a.checked_sub(d).unwrap());
+            }
+        }
+
+        #[test]
+        fn denormalize_round_trip(a in amount(), decimals in 0u8..=90) {
+            if let Some(d) = a.denormalize(decimals) {
+                prop_assert_eq!(a, d.normalize(decimals));
+                prop_assert_eq!(d, d.trim_dust(decimals));
+            }
+        }
+    }
+}
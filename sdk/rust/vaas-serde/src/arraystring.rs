@@ -1,6 +1,7 @@
 //! A module for serializing/deserializing a `BString` as a fixed-width 32 byte array.
 
-use std::{convert::identity, fmt, iter::repeat};
+use alloc::vec::Vec;
+use core::{convert::identity, fmt, iter::repeat};
 
 use bstr::BString;
 use serde::{
@@ -5,12 +5,17 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::{Address, Amount, Chain, GuardianSetInfo};
+use crate::{
+    governance::{GovernanceAction, GovernancePacket},
+    Address, Amount, Chain, GuardianSetInfo,
+};
 
 pub type Vaa = crate::Vaa<GovernancePacket>;
 
 /// Represents a governance action targeted at the core bridge itself.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Serialize, Deserialize, GovernanceAction, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
 pub enum Action {
     #[serde(rename = "1")]
     ContractUpgrade { new_contract: Address },
@@ -23,10 +28,15 @@ pub enum Action {
     SetFee { amount: Amount },
     #[serde(rename = "4")]
     TransferFee { amount: Amount, recipient: Address },
+    #[serde(rename = "6")]
+    SetEmitterFee { emitter: Address, amount: Amount },
+    #[serde(rename = "7")]
+    SetCloseAge { age: u32 },
 }
 
 /// Represents the payload for a governance VAA targeted at the core bridge.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(GovernancePacket, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[governance(module = "Core")]
 pub struct GovernancePacket {
     /// Describes the chain on which the governance action should be carried out.
     pub chain: Chain,
@@ -35,302 +45,8 @@ pub struct GovernancePacket {
     pub action: Action,
 }
 
-// The wire format for GovernancePackets is wonky and doesn't lend itself well to auto-deriving
-// Serialize / Deserialize so we implement it manually here.
-mod governance_packet_impl {
-    use std::fmt;
-
-    use serde::{
-        de::{Error, MapAccess, SeqAccess, Visitor},
-        ser::SerializeStruct,
-        Deserialize, Deserializer, Serialize, Serializer,
-    };
-
-    use crate::{
-        core::{Action, GovernancePacket},
-        Address, Amount, GuardianSetInfo,
-    };
-
-    // MODULE = "Core"
-    const MODULE: [u8; 32] = [
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x43, 0x6f,
-        0x72, 0x65,
-    ];
-
-    struct Module;
-
-    impl Serialize for Module {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: Serializer,
-        {
-            MODULE.serialize(serializer)
-        }
-    }
-
-    impl<'de> Deserialize<'de> for Module {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            let arr = <[u8; 32]>::deserialize(deserializer)?;
-
-            if arr == MODULE {
-                Ok(Module)
-            } else {
-                Err(Error::custom(
-                    "invalid governance module, expected \"Core\"",
-                ))
-            }
-        }
-    }
-
-    #[derive(Serialize, Deserialize)]
-    struct ContractUpgrade {
-        new_contract: Address,
-    }
-
-    #[derive(Serialize, Deserialize)]
-    struct GuardianSetUpgrade {
-        new_guardian_set_index: u32,
-        new_guardian_set: GuardianSetInfo,
-    }
-
-    #[derive(Serialize, Deserialize)]
-    struct SetFee {
-        amount: Amount,
-    }
-
-    #[derive(Serialize, Deserialize)]
-    struct TransferFee {
-        amount: Amount,
-        recipient: Address,
-    }
-
-    impl Serialize for GovernancePacket {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: Serializer,
-        {
-            let mut seq = serializer.serialize_struct("GovernancePacket", 4)?;
-            seq.serialize_field("module", &Module)?;
-
-            // The wire format encodes the action before the chain and then appends the actual
-            // action payload.
-            match self.action {
-                Action::ContractUpgrade { new_contract } => {
-                    seq.serialize_field("action", &1u8)?;
-                    seq.serialize_field("chain", &self.chain)?;
-                    seq.serialize_field("payload", &ContractUpgrade { new_contract })?;
-                }
-                Action::GuardianSetUpgrade {
-                    new_guardian_set_index,
-                    ref new_guardian_set,
-                } => {
-                    seq.serialize_field("action", &2u8)?;
-                    seq.serialize_field("chain", &self.chain)?;
-                    seq.serialize_field(
-                        "payload",
-                        &GuardianSetUpgrade {
-                            new_guardian_set_index,
-                            new_guardian_set: new_guardian_set.clone(),
-                        },
-                    )?;
-                }
-                Action::SetFee { amount } => {
-                    seq.serialize_field("action", &3u8)?;
-                    seq.serialize_field("chain", &self.chain)?;
-                    seq.serialize_field("payload", &SetFee { amount })?;
-                }
-                Action::TransferFee { amount, recipient } => {
-                    seq.serialize_field("action", &4u8)?;
-                    seq.serialize_field("chain", &self.chain)?;
-                    seq.serialize_field("payload", &TransferFee { amount, recipient })?;
-                }
-            }
-
-            seq.end()
-        }
-    }
-
-    struct GovernancePacketVisitor;
-
-    impl<'de> Visitor<'de> for GovernancePacketVisitor {
-        type Value = GovernancePacket;
-
-        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            f.write_str("struct GovernancePacket")
-        }
-
-        #[inline]
-        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-        where
-            A: SeqAccess<'de>,
-        {
-            static EXPECTING: &str = "struct GovernancePacket with 4 elements";
-
-            let _: Module = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(0, &EXPECTING))?;
-            let act: u8 = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(1, &EXPECTING))?;
-            let chain = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(2, &EXPECTING))?;
-
-            let action = match act {
-                1 => {
-                    let ContractUpgrade { new_contract } = seq
-                        .next_element()?
-                        .ok_or_else(|| Error::invalid_length(3, &EXPECTING))?;
-
-                    Action::ContractUpgrade { new_contract }
-                }
-                2 => {
-                    let GuardianSetUpgrade {
-                        new_guardian_set_index,
-                        new_guardian_set,
-                    } = seq
-                        .next_element()?
-                        .ok_or_else(|| Error::invalid_length(3, &EXPECTING))?;
-
-                    Action::GuardianSetUpgrade {
-                        new_guardian_set_index,
-                        new_guardian_set,
-                    }
-                }
-                3 => {
-                    let SetFee { amount } = seq
-                        .next_element()?
-                        .ok_or_else(|| Error::invalid_length(3, &EXPECTING))?;
-
-                    Action::SetFee { amount }
-                }
-                4 => {
-                    let TransferFee { amount, recipient } = seq
-                        .next_element()?
-                        .ok_or_else(|| Error::invalid_length(3, &EXPECTING))?;
-
-                    Action::TransferFee { amount, recipient }
-                }
-                v => {
-                    return Err(Error::custom(format_args!(
-                        "invaliid value {v}, expected one of 1, 2, 3, 4"
-                    )))
-                }
-            };
-
-            Ok(GovernancePacket { chain, action })
-        }
-
-        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
-        where
-            A: MapAccess<'de>,
-        {
-            #[derive(Serialize, Deserialize)]
-            #[serde(rename_all = "snake_case")]
-            enum Field {
-                Module,
-                Action,
-                Chain,
-                Payload,
-            }
-
-            let mut module = None;
-            let mut chain = None;
-            let mut action = None;
-            let mut payload = None;
-
-            while let Some(key) = map.next_key::<Field>()? {
-                match key {
-                    Field::Module => {
-                        if module.is_some() {
-                            return Err(Error::duplicate_field("module"));
-                        }
-
-                        module = map.next_value::<Module>().map(Some)?;
-                    }
-                    Field::Action => {
-                        if action.is_some() {
-                            return Err(Error::duplicate_field("action"));
-                        }
-
-                        action = map.next_value::<u8>().map(Some)?;
-                    }
-                    Field::Chain => {
-                        if chain.is_some() {
-                            return Err(Error::duplicate_field("chain"));
-                        }
-
-                        chain = map.next_value().map(Some)?;
-                    }
-                    Field::Payload => {
-                        if payload.is_some() {
-                            return Err(Error::duplicate_field("payload"));
-                        }
-
-                        let a = action.as_ref().copied().ok_or_else(|| {
-                            Error::custom("`action` must be known before deserializing `payload`")
-                        })?;
-
-                        let p = match a {
-                            1 => {
-                                let ContractUpgrade { new_contract } = map.next_value()?;
-
-                                Action::ContractUpgrade { new_contract }
-                            }
-                            2 => {
-                                let GuardianSetUpgrade {
-                                    new_guardian_set_index,
-                                    new_guardian_set,
-                                } = map.next_value()?;
-
-                                Action::GuardianSetUpgrade {
-                                    new_guardian_set_index,
-                                    new_guardian_set,
-                                }
-                            }
-                            3 => {
-                                let SetFee { amount } = map.next_value()?;
-
-                                Action::SetFee { amount }
-                            }
-                            4 => {
-                                let TransferFee { amount, recipient } = map.next_value()?;
-
-                                Action::TransferFee { amount, recipient }
-                            }
-                            v => {
-                                return Err(Error::custom(format_args!(
-                                    "invalid action: {v}, expected one of: 1, 2, 3, 4"
-                                )))
-                            }
-                        };
-
-                        payload = Some(p);
-                    }
-                }
-            }
-
-            let chain = chain.ok_or_else(|| Error::missing_field("chain"))?;
-            let action = payload.ok_or_else(|| Error::missing_field("payload"))?;
-
-            Ok(GovernancePacket { chain, action })
-        }
-    }
-
-    impl<'de> Deserialize<'de> for GovernancePacket {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            const FIELDS: &[&str] = &["module", "action", "chain", "payload"];
-            deserializer.deserialize_struct("GovernancePacket", FIELDS, GovernancePacketVisitor)
-        }
-    }
-}
+/// The governance module for this contract, left-padded with zeros to 32 bytes.
+pub const MODULE: [u8; 32] = GovernancePacket::MODULE;
 
 #[cfg(test)]
 mod test {
@@ -552,4 +268,56 @@ mod test {
         let encoded = serde_json::to_string(&vaa).unwrap();
         assert_eq!(vaa, serde_json::from_str(&encoded).unwrap());
     }
+
+    #[test]
+    fn set_emitter_fee() {
+        let buf = [
+            // module = "Core"
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x43, 0x6f, 0x72, 0x65, // action
+            0x06, // chain
+            0x00, 0x01, // emitter
+            0xc6, 0x9a, 0x1b, 0x1a, 0x65, 0xdd, 0x33, 0x6b, 0xf1, 0xdf, 0x6a, 0x77, 0xaf, 0xb5,
+            0x01, 0xfc, 0x25, 0xdb, 0x7f, 0xc0, 0x93, 0x8c, 0xb0, 0x85, 0x95, 0xa9, 0xef, 0x47,
+            0x32, 0x65, 0xcb, 0x4f, // amount
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00,
+        ];
+
+        let mut emitter = [0u8; 32];
+        emitter.copy_from_slice(&buf[35..67]);
+        let packet = GovernancePacket {
+            chain: Chain::Solana,
+            action: Action::SetEmitterFee {
+                emitter: Address(emitter),
+                amount: Amount([0; 32]),
+            },
+        };
+
+        assert_eq!(buf.as_ref(), &serde_wormhole::to_vec(&packet).unwrap());
+        assert_eq!(packet, serde_wormhole::from_slice(&buf).unwrap());
+    }
+
+    #[test]
+    fn set_close_age() {
+        let buf = [
+            // module = "Core"
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x43, 0x6f, 0x72, 0x65, // action
+            0x07, // chain
+            0x00, 0x01, // age
+            0x00, 0x09, 0x3a, 0x80,
+        ];
+
+        let packet = GovernancePacket {
+            chain: Chain::Solana,
+            action: Action::SetCloseAge { age: 604_800 },
+        };
+
+        assert_eq!(buf.as_ref(), &serde_wormhole::to_vec(&packet).unwrap());
+        assert_eq!(packet, serde_wormhole::from_slice(&buf).unwrap());
+    }
 }
@@ -0,0 +1,445 @@
+//! Support for defining Wormhole governance packets.
+//!
+//! A governance packet has the following wire format:
+//!
+//! ```markdown
+//! 0  .. 32: Module  (left-zero-padded name of the module)
+//! 32 .. 33: Action  (u8)
+//! 33 .. 35: Chain   (u16)
+//! 35 ..   : Payload (depends on the action)
+//! ```
+//!
+//! Since the chain is encoded between the action and its payload, governance packets cannot be
+//! described with a regular serde derive. Instead, define an enum of actions where each variant
+//! has a `#[serde(rename = "<action>")]` attribute and derive [`GovernanceAction`] for it. Then
+//! derive [`GovernancePacket`] for a struct with `chain` and `action` fields:
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use wormhole_sdk::{
+//!     governance::{GovernanceAction, GovernancePacket},
+//!     Address, Chain,
+//! };
+//!
+//! #[derive(Serialize, Deserialize, GovernanceAction, Debug, Clone, PartialEq, Eq)]
+//! pub enum Action {
+//!     #[serde(rename = "1")]
+//!     RegisterChain {
+//!         chain: Chain,
+//!         emitter_address: Address,
+//!     },
+//!     #[serde(rename = "2")]
+//!     Pause,
+//! }
+//!
+//! #[derive(GovernancePacket, Debug, Clone, PartialEq, Eq)]
+//! #[governance(module = "MyContract")]
+//! pub struct GovernancePacket {
+//!     pub chain: Chain,
+//!     pub action: Action,
+//! }
+//!
+//! let packet = GovernancePacket {
+//!     chain: Chain::Ethereum,
+//!     action: Action::Pause,
+//! };
+//! let data = serde_wormhole::to_vec(&packet).unwrap();
+//!
+//! assert_eq!(&data[..32], &GovernancePacket::MODULE);
+//! assert_eq!(&data[32..], &[2, 0, 2]);
+//! assert_eq!(packet, serde_wormhole::from_slice(&data).unwrap());
+//! ```
+//!
+//! The action payload is encoded like a struct containing the fields of the variant, so `serde`
+//! attributes on those fields are respected. Unit variants have an empty payload.
+//!
+//! Both derives refer to this crate as `::wormhole_sdk`. Crates that depend on it under a different
+//! name can point the derives at it with `#[governance(crate = "path::to::wormhole_sdk")]`.
+
+use serde::{Deserializer, Serializer};
+
+pub use wormhole_vaas_serde_derive::{GovernanceAction, GovernancePacket};
+
+/// An action carried by a governance packet.
+///
+/// This trait should normally be implemented with `#[derive(GovernanceAction)]`.
+pub trait GovernanceAction: Sized {
+    /// All the action ids supported by this type.
+    const ACTIONS: &'static [u8];
+
+    /// Returns the action id for this action.
+    fn action(&self) -> u8;
+
+    /// Serializes the payload for this action, without the action id.
+    fn serialize_payload<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+    /// Deserializes the payload for the action with id `action`.
+    fn deserialize_payload<'de, D: Deserializer<'de>>(
+        action: u8,
+        deserializer: D,
+    ) -> Result<Self, D::Error>;
+}
+
+/// Returns `name` left-padded with zeros to 32 bytes, which is how governance modules are encoded
+/// on the wire. Panics if `name` is longer than 32 bytes.
+pub const fn module(name: &str) -> [u8; 32] {
+    let name = name.as_bytes();
+    assert!(name.len() <= 32, "governance module name is too long");
+
+    let mut m = [0u8; 32];
+    let offset = 32 - name.len();
+    let mut i = 0;
+    while i < name.len() {
+        m[offset + i] = name[i];
+        i += 1;
+    }
+
+    m
+}
+
+// Implementation details of the derive macros. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    use core::{fmt, marker::PhantomData};
+
+    pub use serde;
+
+    use serde::{
+        de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
+        ser::SerializeStruct,
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use super::GovernanceAction;
+    use crate::Chain;
+
+    struct Payload<'a, A>(&'a A);
+
+    impl<A: GovernanceAction> Serialize for Payload<'_, A> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            self.0.serialize_payload(serializer)
+        }
+    }
+
+    struct PayloadSeed<A> {
+        action: u8,
+        _marker: PhantomData<A>,
+    }
+
+    impl<'de, A: GovernanceAction> DeserializeSeed<'de> for PayloadSeed<A> {
+        type Value = A;
+
+        fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            A::deserialize_payload(self.action, deserializer)
+        }
+    }
+
+    pub fn serialize_packet<A, S>(
+        module: &[u8; 32],
+        chain: &Chain,
+        action: &A,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        A: GovernanceAction,
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_struct("GovernancePacket", 4)?;
+        seq.serialize_field("module", module)?;
+
+        // The wire format encodes the action before the chain and then appends the actual action
+        // payload.
+        seq.serialize_field("action", &action.action())?;
+        seq.serialize_field("chain", chain)?;
+        seq.serialize_field("payload", &Payload(action))?;
+
+        seq.end()
+    }
+
+    pub fn deserialize_packet<'de, A, D>(
+        module: &'static [u8; 32],
+        name: &'static str,
+        deserializer: D,
+    ) -> Result<(Chain, A), D::Error>
+    where
+        A: GovernanceAction,
+        D: Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &["module", "action", "chain", "payload"];
+        deserializer.deserialize_struct(
+            "GovernancePacket",
+            FIELDS,
+            GovernancePacketVisitor {
+                module,
+                name,
+                _marker: PhantomData,
+            },
+        )
+    }
+
+    struct GovernancePacketVisitor<A> {
+        module: &'static [u8; 32],
+        name: &'static str,
+        _marker: PhantomData<A>,
+    }
+
+    impl<A> GovernancePacketVisitor<A> {
+        fn check_module<E: Error>(&self, m: [u8; 32]) -> Result<(), E> {
+            if &m == self.module {
+                Ok(())
+            } else {
+                Err(E::custom(format_args!(
+                    "invalid governance module, expected \"{}\"",
+                    self.name
+                )))
+            }
+        }
+    }
+
+    impl<'de, A: GovernanceAction> Visitor<'de> for GovernancePacketVisitor<A> {
+        type Value = (Chain, A);
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("struct GovernancePacket")
+        }
+
+        #[inline]
+        fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+        where
+            S: SeqAccess<'de>,
+        {
+            static EXPECTING: &str = "struct GovernancePacket with 4 elements";
+
+            let module = seq
+                .next_element()?
+                .ok_or_else(|| Error::invalid_length(0, &EXPECTING))?;
+            self.check_module(module)?;
+
+            let action: u8 = seq
+                .next_element()?
+                .ok_or_else(|| Error::invalid_length(1, &EXPECTING))?;
+            let chain = seq
+                .next_element()?
+                .ok_or_else(|| Error::invalid_length(2, &EXPECTING))?;
+            let action = seq
+                .next_element_seed(PayloadSeed {
+                    action,
+                    _marker: PhantomData,
+                })?
+                .ok_or_else(|| Error::invalid_length(3, &EXPECTING))?;
+
+            Ok((chain, action))
+        }
+
+        fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
+        where
+            M: MapAccess<'de>,
+        {
+            #[derive(Serialize, Deserialize)]
+            #[serde(rename_all = "snake_case")]
+            enum Field {
+                Module,
+                Action,
+                Chain,
+                Payload,
+            }
+
+            let mut module = None;
+            let mut chain = None;
+            let mut action = None;
+            let mut payload = None;
+
+            while let Some(key) = map.next_key::<Field>()? {
+                match key {
+                    Field::Module => {
+                        if module.is_some() {
+                            return Err(Error::duplicate_field("module"));
+                        }
+
+                        let m = map.next_value()?;
+                        self.check_module(m)?;
+                        module = Some(m);
+                    }
+                    Field::Action => {
+                        if action.is_some() {
+                            return Err(Error::duplicate_field("action"));
+                        }
+
+                        action = map.next_value::<u8>().map(Some)?;
+                    }
+                    Field::Chain => {
+                        if chain.is_some() {
+                            return Err(Error::duplicate_field("chain"));
+                        }
+
+                        chain = map.next_value().map(Some)?;
+                    }
+                    Field::Payload => {
+                        if payload.is_some() {
+                            return Err(Error::duplicate_field("payload"));
+                        }
+
+                        let a = action.as_ref().copied().ok_or_else(|| {
+                            Error::custom("`action` must be known before deserializing `payload`")
+                        })?;
+
+                        payload = map
+                            .next_value_seed(PayloadSeed {
+                                action: a,
+                                _marker: PhantomData,
+                            })
+                            .map(Some)?;
+                    }
+                }
+            }
+
+            let _ = module.ok_or_else(|| Error::missing_field("module"))?;
+            let chain = chain.ok_or_else(|| Error::missing_field("chain"))?;
+            let action = payload.ok_or_else(|| Error::missing_field("payload"))?;
+
+            Ok((chain, action))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bstr::BString;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{Address, Chain};
+
+    #[derive(Serialize, Deserialize, GovernanceAction, Debug, Clone, PartialEq, Eq)]
+    enum Action {
+        #[serde(rename = "3")]
+        SetName {
+            target: Address,
+            #[serde(with = "crate::arraystring")]
+            name: BString,
+        },
+        #[serde(rename = "7")]
+        Pause,
+    }
+
+    #[derive(GovernancePacket, Debug, Clone, PartialEq, Eq)]
+    #[governance(module = "TestModule")]
+    struct GovernancePacket {
+        chain: Chain,
+        action: Action,
+    }
+
+    #[test]
+    fn custom_module() {
+        assert_eq!(&[3, 7], Action::ACTIONS);
+        assert_eq!(module("TestModule"), GovernancePacket::MODULE);
+
+        let packet = GovernancePacket {
+            chain: Chain::Solana,
+            action: Action::SetName {
+                target: Address([0xab; 32]),
+                name: BString::from("test"),
+            },
+        };
+        let buf = serde_wormhole::to_vec(&packet).unwrap();
+
+        let mut expected = GovernancePacket::MODULE.to_vec();
+        expected.extend_from_slice(&[3, 0, 1]);
+        expected.extend_from_slice(&[0xab; 32]);
+        expected.extend_from_slice(&[0; 28]);
+        expected.extend_from_slice(b"test");
+        assert_eq!(expected, buf);
+        assert_eq!(packet, serde_wormhole::from_slice(&buf).unwrap());
+
+        let encoded = serde_json::to_string(&packet).unwrap();
+        assert_eq!(packet, serde_json::from_str(&encoded).unwrap());
+    }
+
+    #[test]
+    fn unit_action() {
+        let packet = GovernancePacket {
+            chain: Chain::Any,
+            action: Action::Pause,
+        };
+        let buf = serde_wormhole::to_vec(&packet).unwrap();
+        assert_eq!(&[7, 0, 0], &buf[32..]);
+        assert_eq!(packet, serde_wormhole::from_slice(&buf).unwrap());
+
+        let encoded = serde_json::to_string(&packet).unwrap();
+        assert_eq!(packet, serde_json::from_str(&encoded).unwrap());
+    }
+
+    #[test]
+    fn invalid_packets() {
+        let mut buf = module("OtherModule").to_vec();
+        buf.extend_from_slice(&[7, 0, 0]);
+        let err = serde_wormhole::from_slice::<GovernancePacket>(&buf).unwrap_err();
+        assert!(err.to_string().contains("expected \"TestModule\""));
+
+        let mut buf = GovernancePacket::MODULE.to_vec();
+        buf.extend_from_slice(&[4, 0, 0]);
+        let err = serde_wormhole::from_slice::<GovernancePacket>(&buf).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid action: 4, expected one of: 3, 7"));
+    }
+
+    // Doesn't implement `Clone` and names this crate through the `crate` override.
+    #[derive(Serialize, Deserialize, GovernanceAction, Debug, PartialEq, Eq)]
+    #[governance(crate = "crate")]
+    enum UncloneableAction {
+        #[serde(rename = "1")]
+        Upgrade { new_contract: Address },
+    }
+
+    #[derive(GovernancePacket, Debug, PartialEq, Eq)]
+    #[governance(module = "TestModule", crate = "crate")]
+    struct GenericPacket<A>
+    where
+        A: GovernanceAction,
+    {
+        chain: Chain,
+        action: A,
+    }
+
+    #[test]
+    fn generic_packet() {
+        let packet = GenericPacket {
+            chain: Chain::Ethereum,
+            action: UncloneableAction::Upgrade {
+                new_contract: Address([0x11; 32]),
+            },
+        };
+        let buf = serde_wormhole::to_vec(&packet).unwrap();
+        assert_eq!(&buf[..32], &GenericPacket::<UncloneableAction>::MODULE);
+        assert_eq!(&[1, 0, 2], &buf[32..35]);
+        assert_eq!(packet, serde_wormhole::from_slice(&buf).unwrap());
+
+        let packet = GenericPacket {
+            chain: Chain::Any,
+            action: Action::Pause,
+        };
+        let buf = serde_wormhole::to_vec(&packet).unwrap();
+        assert_eq!(packet, serde_wormhole::from_slice(&buf).unwrap());
+    }
+
+    #[test]
+    fn module_padding() {
+        assert_eq!(crate::core::MODULE, module("Core"));
+        assert_eq!(
+            *b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00GlobalAccountant",
+            module("GlobalAccountant")
+        );
+        assert_eq!([0; 32], module(""));
+        assert_eq!(
+            *b"0123456789abcdef0123456789abcdef",
+            module("0123456789abcdef0123456789abcdef")
+        );
+    }
+}
@@ -0,0 +1,414 @@
+//! Tracking of guardian sets over time.
+//!
+//! Every VAA names the guardian set that signed it with its `guardian_set_index`. When the
+//! guardians rotate to a new set via a core `GuardianSetUpgrade` governance VAA, the previous set
+//! is not revoked immediately. Instead it remains valid for a grace period so that VAAs signed just
+//! before the upgrade can still be redeemed. The current guardian set never expires.
+//!
+//! The `GuardianSetRegistry` implements these rules so that off-chain verifiers can track the
+//! guardian set without relying on a chain to do it for them.
+
+use alloc::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    core::{Action, GovernancePacket},
+    vaa::{Digest, Header, VerifyError},
+    Chain, GuardianSetInfo,
+};
+
+/// The default number of seconds for which a guardian set remains valid after being replaced.
+/// This matches the value used by the core bridge contracts on mainnet.
+pub const DEFAULT_EXPIRATION: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    #[error("unknown guardian set {0}")]
+    UnknownGuardianSet(u32),
+    #[error("guardian set {index} expired at {expiration_time}")]
+    Expired { index: u32, expiration_time: u64 },
+    #[error("new guardian set index must be {expected} (got {actual})")]
+    InvalidIndex { expected: u32, actual: u32 },
+    #[error("guardian set must not be empty")]
+    EmptyGuardianSet,
+    #[error("guardian set upgrades must target all chains, not {0}")]
+    InvalidChain(Chain),
+    #[error("governance action is not a guardian set upgrade")]
+    NotGuardianSetUpgrade,
+    #[error(transparent)]
+    Verify(#[from] VerifyError),
+}
+
+/// A guardian set along with the time at which it stops being valid.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GuardianSet {
+    pub info: GuardianSetInfo,
+
+    /// Seconds since UNIX epoch after which the guardian set may no longer be used. A value of 0
+    /// means that the guardian set does not expire.
+    pub expiration_time: u64,
+}
+
+impl GuardianSet {
+    /// Returns true if the guardian set may still be used at `time`.
+    pub fn is_active(&self, time: u64) -> bool {
+        self.expiration_time == 0 || time <= self.expiration_time
+    }
+}
+
+/// A collection of indexed guardian sets with expiration semantics matching the core bridge.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(try_from = "RawGuardianSetRegistry")]
+pub struct GuardianSetRegistry {
+    sets: BTreeMap<u32, GuardianSet>,
+    current: u32,
+
+    /// The number of seconds a guardian set remains valid after it has been replaced.
+    expiration: u64,
+}
+
+/// The serialized form of a `GuardianSetRegistry`, which may name a current guardian set that it
+/// doesn't contain.
+#[derive(Deserialize)]
+struct RawGuardianSetRegistry {
+    sets: BTreeMap<u32, GuardianSet>,
+    current: u32,
+    expiration: u64,
+}
+
+impl TryFrom<RawGuardianSetRegistry> for GuardianSetRegistry {
+    type Error = RegistryError;
+
+    fn try_from(raw: RawGuardianSetRegistry) -> Result<Self, Self::Error> {
+        if !raw.sets.contains_key(&raw.current) {
+            return Err(RegistryError::UnknownGuardianSet(raw.current));
+        }
+
+        Ok(GuardianSetRegistry {
+            sets: raw.sets,
+            current: raw.current,
+            expiration: raw.expiration,
+        })
+    }
+}
+
+impl GuardianSetRegistry {
+    /// Creates a new registry where `info` is the current guardian set with index `index`.
+    /// Replaced guardian sets remain valid for `expiration` seconds.
+    pub fn new(index: u32, info: GuardianSetInfo, expiration: u64) -> Self {
+        GuardianSetRegistry {
+            sets: BTreeMap::from([(
+                index,
+                GuardianSet {
+                    info,
+                    expiration_time: 0,
+                },
+            )]),
+            current: index,
+            expiration,
+        }
+    }
+
+    /// Returns the index of the current guardian set.
+    pub fn current_index(&self) -> u32 {
+        self.current
+    }
+
+    /// Returns the current guardian set.
+    pub fn current(&self) -> &GuardianSetInfo {
+        &self.sets[&self.current].info
+    }
+
+    /// Returns the guardian set with index `index`, regardless of whether it has expired.
+    pub fn get(&self, index: u32) -> Option<&GuardianSet> {
+        self.sets.get(&index)
+    }
+
+    /// Returns an iterator over all known guardian sets, ordered by index.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &GuardianSet)> {
+        self.sets.iter().map(|(&k, v)| (k, v))
+    }
+
+    /// Returns the guardian set with index `index` if it may be used to verify a VAA at `time`
+    /// (seconds since UNIX epoch).
+    pub fn active(&self, index: u32, time: u64) -> Result<&GuardianSetInfo, RegistryError> {
+        let set = self
+            .sets
+            .get(&index)
+            .ok_or(RegistryError::UnknownGuardianSet(index))?;
+
+        if set.is_active(time) {
+            Ok(&set.info)
+        } else {
+            Err(RegistryError::Expired {
+                index,
+                expiration_time: set.expiration_time,
+            })
+        }
+    }
+
+    /// Verifies the signatures in `header` over `body` using the guardian set named by the header,
+    /// provided that set may be used at `time`.
+    pub fn verify(&self, header: &Header, body: &[u8], time: u64) -> Result<Digest, RegistryError> {
+        let set = self.active(header.guardian_set_index, time)?;
+        header.verify(body, &set.addresses).map_err(From::from)
+    }
+
+    /// Makes `info` the current guardian set with index `index`. The previous guardian set expires
+    /// `expiration` seconds after `time`.
+    pub fn upgrade(
+        &mut self,
+        index: u32,
+        info: GuardianSetInfo,
+        time: u64,
+    ) -> Result<(), RegistryError> {
+        let expected = self
+            .current
+            .checked_add(1)
+            .ok_or(RegistryError::InvalidIndex {
+                expected: self.current,
+                actual: index,
+            })?;
+        if index != expected {
+            return Err(RegistryError::InvalidIndex {
+                expected,
+                actual: index,
+            });
+        }
+
+        if info.addresses.is_empty() {
+            return Err(RegistryError::EmptyGuardianSet);
+        }
+
+        if let Some(prev) = self.sets.get_mut(&self.current) {
+            prev.expiration_time = time.saturating_add(self.expiration);
+        }
+
+        let _ = self.sets.insert(
+            index,
+            GuardianSet {
+                info,
+                expiration_time: 0,
+            },
+        );
+        self.current = index;
+
+        Ok(())
+    }
+
+    /// Applies a core bridge `GuardianSetUpgrade` governance packet observed at `time`.
+    ///
+    /// NOTE: this does not verify the VAA carrying `packet`. Callers should check it with
+    /// [`GuardianSetRegistry::verify`] and confirm that it was emitted by the governance emitter
+    /// before applying it.
+    pub fn apply(&mut self, packet: &GovernancePacket, time: u64) -> Result<(), RegistryError> {
+        if packet.chain != Chain::Any {
+            return Err(RegistryError::InvalidChain(packet.chain));
+        }
+
+        match &packet.action {
+            Action::GuardianSetUpgrade {
+                new_guardian_set_index,
+                new_guardian_set,
+            } => self.upgrade(*new_guardian_set_index, new_guardian_set.clone(), time),
+            _ => Err(RegistryError::NotGuardianSetUpgrade),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_wormhole::RawMessage;
+
+    use super::*;
+
+    use crate::{
+        vaa::{Body, SigningKey, VaaBuilder, VaaView},
+        Address, Amount, GuardianAddress, GOVERNANCE_EMITTER,
+    };
+
+    fn keys(seed: u8, count: u8) -> Vec<SigningKey> {
+        (0..count)
+            .map(|i| SigningKey::from_bytes(&[seed + i; 32]).unwrap())
+            .collect()
+    }
+
+    fn set(keys: &[SigningKey]) -> GuardianSetInfo {
+        GuardianSetInfo {
+            addresses: keys
+                .iter()
+                .map(|k| GuardianAddress::from(&k.verifying_key()))
+                .collect(),
+        }
+    }
+
+    fn upgrade_packet(index: u32, keys: &[SigningKey]) -> GovernancePacket {
+        GovernancePacket {
+            chain: Chain::Any,
+            action: Action::GuardianSetUpgrade {
+                new_guardian_set_index: index,
+                new_guardian_set: set(keys),
+            },
+        }
+    }
+
+    #[test]
+    fn expiry() {
+        let mut reg = GuardianSetRegistry::new(0, set(&keys(1, 3)), 100);
+        assert_eq!(0, reg.current_index());
+        assert!(reg.active(0, u64::MAX).is_ok());
+        assert!(matches!(
+            reg.active(1, 0),
+            Err(RegistryError::UnknownGuardianSet(1))
+        ));
+
+        reg.apply(&upgrade_packet(1, &keys(7, 5)), 1_000).unwrap();
+        assert_eq!(1, reg.current_index());
+        assert_eq!(&set(&keys(7, 5)), reg.current());
+
+        assert_eq!(1_100, reg.get(0).unwrap().expiration_time);
+        assert!(reg.active(0, 1_100).is_ok());
+        assert!(matches!(
+            reg.active(0, 1_101),
+            Err(RegistryError::Expired {
+                index: 0,
+                expiration_time: 1_100
+            })
+        ));
+        assert!(reg.active(1, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn invalid_upgrades() {
+        let mut reg = GuardianSetRegistry::new(3, set(&keys(1, 3)), DEFAULT_EXPIRATION);
+
+        assert!(matches!(
+            reg.apply(&upgrade_packet(5, &keys(7, 5)), 0),
+            Err(RegistryError::InvalidIndex {
+                expected: 4,
+                actual: 5
+            })
+        ));
+        assert!(matches!(
+            reg.apply(&upgrade_packet(3, &keys(7, 5)), 0),
+            Err(RegistryError::InvalidIndex {
+                expected: 4,
+                actual: 3
+            })
+        ));
+        assert!(matches!(
+            reg.apply(&upgrade_packet(4, &[]), 0),
+            Err(RegistryError::EmptyGuardianSet)
+        ));
+
+        let mut p = upgrade_packet(4, &keys(7, 5));
+        p.chain = Chain::Ethereum;
+        assert!(matches!(
+            reg.apply(&p, 0),
+            Err(RegistryError::InvalidChain(Chain::Ethereum))
+        ));
+
+        let p = GovernancePacket {
+            chain: Chain::Any,
+            action: Action::SetFee {
+                amount: Amount([0; 32]),
+            },
+        };
+        assert!(matches!(
+            reg.apply(&p, 0),
+            Err(RegistryError::NotGuardianSetUpgrade)
+        ));
+
+        // None of the failed upgrades should have modified the registry.
+        assert_eq!(3, reg.current_index());
+        assert_eq!(0, reg.get(3).unwrap().expiration_time);
+        assert_eq!(1, reg.iter().count());
+    }
+
+    #[test]
+    fn verify() {
+        let old = keys(1, 4);
+        let new = keys(11, 7);
+        let mut reg = GuardianSetRegistry::new(0, set(&old), 100);
+
+        // The upgrade itself is signed by the old guardian set.
+        let (vaa, data) = VaaBuilder::new(Body {
+            timestamp: 40,
+            nonce: 0,
+            emitter_chain: Chain::Solana,
+            emitter_address: GOVERNANCE_EMITTER,
+            sequence: 9,
+            consistency_level: 0,
+            payload: upgrade_packet(1, &new),
+        })
+        .guardians(old.iter().cloned())
+        .build_bytes()
+        .unwrap();
+
+        let (header, body) = serde_wormhole::from_slice::<(Header, &RawMessage)>(&data).unwrap();
+        assert_eq!(
+            VaaView::new(&data).unwrap().digest(),
+            reg.verify(&header, body, 50).unwrap()
+        );
+        reg.apply(&vaa.payload, 50).unwrap();
+
+        let msg = Body {
+            emitter_chain: Chain::Ethereum,
+            emitter_address: Address([0x42; 32]),
+            payload: "hello",
+            ..Default::default()
+        };
+        let body = serde_wormhole::to_vec(&msg).unwrap();
+        let d = msg.digest().unwrap();
+
+        let (old_vaa, _) = VaaBuilder::new(msg.clone())
+            .guardians(old.iter().cloned())
+            .build_bytes()
+            .unwrap();
+        let (old_header, _) = old_vaa.into();
+
+        let (new_vaa, _) = VaaBuilder::new(msg)
+            .guardian_set_index(1)
+            .guardians(new.iter().cloned())
+            .build_bytes()
+            .unwrap();
+        let (new_header, _) = new_vaa.into();
+
+        // The old guardian set is still usable until it expires.
+        assert_eq!(d, reg.verify(&old_header, &body, 150).unwrap());
+        assert!(matches!(
+            reg.verify(&old_header, &body, 151),
+            Err(RegistryError::Expired { index: 0, .. })
+        ));
+        assert_eq!(d, reg.verify(&new_header, &body, 151).unwrap());
+
+        // Signatures from the wrong guardian set are rejected.
+        let mut bad_header = old_header;
+        bad_header.guardian_set_index = 1;
+        assert!(matches!(
+            reg.verify(&bad_header, &body, 0),
+            Err(RegistryError::Verify(_))
+        ));
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let mut reg = GuardianSetRegistry::new(0, set(&keys(1, 3)), 100);
+        reg.upgrade(1, set(&keys(9, 2)), 10).unwrap();
+
+        let encoded = serde_json::to_string(&reg).unwrap();
+        assert_eq!(reg, serde_json::from_str(&encoded).unwrap());
+    }
+
+    #[test]
+    fn deserialize_unknown_current() {
+        let mut value =
+            serde_json::to_value(GuardianSetRegistry::new(0, set(&keys(1, 3)), 100)).unwrap();
+        value["current"] = 1.into();
+
+        let err = serde_json::from_value::<GuardianSetRegistry>(value).unwrap_err();
+        assert!(err.to_string().contains("unknown guardian set 1"));
+    }
+}
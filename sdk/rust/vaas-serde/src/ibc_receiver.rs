@@ -1,9 +1,14 @@
 use serde::{Deserialize, Serialize};
 
-use crate::Chain;
+use crate::{
+    governance::{GovernanceAction, GovernancePacket},
+    Chain,
+};
 
 /// Represents a governance action targeted at the wormchain ibc receiver contract.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Serialize, Deserialize, GovernanceAction, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
 pub enum Action {
     #[serde(rename = "1")]
     UpdateChannelChain {
@@ -15,11 +20,12 @@ pub enum Action {
     },
 }
 
-// MODULE = "IbcReceiver"
-pub const MODULE: [u8; 32] = *b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00IbcReceiver";
+/// The governance module for this contract, left-padded with zeros to 32 bytes.
+pub const MODULE: [u8; 32] = GovernancePacket::MODULE;
 
 /// Represents the payload for a governance VAA targeted at the wormchain ibc receiver contract.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(GovernancePacket, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[governance(module = "IbcReceiver")]
 pub struct GovernancePacket {
     /// Describes the chain on which the governance action should be carried out.
     pub chain: Chain,
@@ -28,230 +34,6 @@ pub struct GovernancePacket {
     pub action: Action,
 }
 
-mod governance_packet_impl {
-    use std::fmt;
-
-    use serde::{
-        de::{Error, MapAccess, SeqAccess, Visitor},
-        ser::SerializeStruct,
-        Deserialize, Deserializer, Serialize, Serializer,
-    };
-
-    use crate::{
-        ibc_receiver::{Action, GovernancePacket, MODULE},
-        Chain,
-    };
-
-    struct Module;
-
-    impl Serialize for Module {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: Serializer,
-        {
-            MODULE.serialize(serializer)
-        }
-    }
-
-    impl<'de> Deserialize<'de> for Module {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            let arr = <[u8; 32]>::deserialize(deserializer)?;
-
-            if arr == MODULE {
-                Ok(Module)
-            } else {
-                Err(Error::custom(
-                    "invalid governance module, expected \"IbcReceiver\"",
-                ))
-            }
-        }
-    }
-
-    // governance actions
-    #[derive(Serialize, Deserialize)]
-    struct UpdateChannelChain {
-        #[serde(with = "crate::serde_array")]
-        channel_id: [u8; 64],
-        chain_id: Chain,
-    }
-
-    impl Serialize for GovernancePacket {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: Serializer,
-        {
-            let mut seq = serializer.serialize_struct("GovernancePacket", 4)?;
-            seq.serialize_field("module", &Module)?;
-
-            // The wire format encodes the action before the chain and then appends the actual
-            // action payload.
-            match self.action.clone() {
-                Action::UpdateChannelChain {
-                    channel_id,
-                    chain_id,
-                } => {
-                    seq.serialize_field("action", &1u8)?;
-                    seq.serialize_field("chain", &self.chain)?;
-                    seq.serialize_field(
-                        "payload",
-                        &UpdateChannelChain {
-                            channel_id,
-                            chain_id,
-                        },
-                    )?;
-                }
-            }
-
-            seq.end()
-        }
-    }
-
-    struct GovernancePacketVisitor;
-
-    impl<'de> Visitor<'de> for GovernancePacketVisitor {
-        type Value = GovernancePacket;
-
-        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            f.write_str("struct GovernancePacket")
-        }
-
-        #[inline]
-        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-        where
-            A: SeqAccess<'de>,
-        {
-            static EXPECTING: &str = "struct GovernancePacket with 4 elements";
-
-            let _: Module = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(0, &EXPECTING))?;
-            let act: u8 = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(1, &EXPECTING))?;
-            let chain = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(2, &EXPECTING))?;
-
-            let action = match act {
-                1 => {
-                    let UpdateChannelChain {
-                        channel_id,
-                        chain_id,
-                    } = seq
-                        .next_element()?
-                        .ok_or_else(|| Error::invalid_length(3, &EXPECTING))?;
-
-                    Action::UpdateChannelChain {
-                        channel_id,
-                        chain_id,
-                    }
-                }
-                v => {
-                    return Err(Error::custom(format_args!(
-                        "invalid value: {v}, expected 1"
-                    )))
-                }
-            };
-
-            Ok(GovernancePacket { chain, action })
-        }
-
-        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
-        where
-            A: MapAccess<'de>,
-        {
-            #[derive(Serialize, Deserialize)]
-            #[serde(rename_all = "snake_case")]
-            enum Field {
-                Module,
-                Action,
-                Chain,
-                Payload,
-            }
-
-            let mut module = None;
-            let mut chain = None;
-            let mut action = None;
-            let mut payload = None;
-
-            while let Some(key) = map.next_key::<Field>()? {
-                match key {
-                    Field::Module => {
-                        if module.is_some() {
-                            return Err(Error::duplicate_field("module"));
-                        }
-
-                        module = map.next_value::<Module>().map(Some)?;
-                    }
-                    Field::Action => {
-                        if action.is_some() {
-                            return Err(Error::duplicate_field("action"));
-                        }
-
-                        action = map.next_value::<u8>().map(Some)?;
-                    }
-                    Field::Chain => {
-                        if chain.is_some() {
-                            return Err(Error::duplicate_field("chain"));
-                        }
-
-                        chain = map.next_value().map(Some)?;
-                    }
-                    Field::Payload => {
-                        if payload.is_some() {
-                            return Err(Error::duplicate_field("payload"));
-                        }
-
-                        let a = action.as_ref().copied().ok_or_else(|| {
-                            Error::custom("`action` must be known before deserializing `payload`")
-                        })?;
-
-                        let p = match a {
-                            1 => {
-                                let UpdateChannelChain {
-                                    channel_id,
-                                    chain_id,
-                                } = map.next_value()?;
-
-                                Action::UpdateChannelChain {
-                                    channel_id,
-                                    chain_id,
-                                }
-                            }
-                            v => {
-                                return Err(Error::custom(format_args!(
-                                    "invalid action: {v}, expected one of: 1, 2"
-                                )))
-                            }
-                        };
-
-                        payload = Some(p);
-                    }
-                }
-            }
-
-            let _ = module.ok_or_else(|| Error::missing_field("module"))?;
-            let chain = chain.ok_or_else(|| Error::missing_field("chain"))?;
-            let action = payload.ok_or_else(|| Error::missing_field("payload"))?;
-
-            Ok(GovernancePacket { chain, action })
-        }
-    }
-
-    impl<'de> Deserialize<'de> for GovernancePacket {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            const FIELDS: &[&str] = &["module", "action", "chain", "payload"];
-            deserializer.deserialize_struct("GovernancePacket", FIELDS, GovernancePacketVisitor)
-        }
-    }
-}
-
 #[cfg(test)]
 mod test {
     use crate::{vaa::Signature, Chain, Vaa, GOVERNANCE_EMITTER};
@@ -7,18 +7,36 @@
 //! - Parsers for VAA's and Payloads.
 //! - Data types for Wormhole primitives such as GuardianSets and signatures.
 //! - Verification Primitives for securely checking payloads.
+//!
+//! The crate only requires `alloc`. The default `std` feature enables the parts that need the
+//! standard library: `vaa::digest`, the thread-safe `vaa::SignatureCache`, and the functions that
+//! use it.
+//!
+//! `Address::to_native` and `Address::from_native` always handle hex addresses. The `eip55`,
+//! `base58`, `bech32`, `near`, and `algorand` features enable the other native address encodings
+//! and `native-addresses` enables all of them.
 
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 #![deny(warnings)]
 #![deny(unused_results)]
 
-use std::fmt;
+extern crate alloc;
+// Allows the governance derive macros to refer to this crate by name.
+extern crate self as wormhole_sdk;
+
+use ::core::fmt;
+
+use alloc::{string::String, vec::Vec};
 
 use serde::{Deserialize, Serialize};
 
 pub mod accountant;
 pub mod accountant_modification;
+pub mod amount;
 mod arraystring;
 pub mod core;
+pub mod governance;
+pub mod guardian_set;
 pub mod ibc_receiver;
 pub mod ibc_translator;
 pub mod nft;
@@ -28,8 +46,9 @@ mod serde_array;
 pub mod token;
 pub mod vaa;
 
+pub use amount::Amount;
 pub use vaa::Vaa;
-pub use wormhole_supported_chains::Chain;
+pub use wormhole_supported_chains::{AddressError, Chain};
 
 /// The `GOVERNANCE_EMITTER` is a special address Wormhole guardians trust to observe governance
 /// actions from. The value is "0000000000000000000000000000000000000000000000000000000000000004".
@@ -60,11 +79,18 @@ impl fmt::Display for Address {
     }
 }
 
-/// Wormhole specifies an amount as a uint256 encoded in big-endian order.
-#[derive(
-    Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
-)]
-pub struct Amount(pub [u8; 32]);
+impl Address {
+    /// Converts this address into the native string form used by `chain`, e.g. a checksummed
+    /// Ethereum address or a base58 Solana pubkey.
+    pub fn to_native(&self, chain: Chain) -> Result<String, AddressError> {
+        chain.format_address(&self.0)
+    }
+
+    /// Parses a native address string for `chain` into a wormhole address.
+    pub fn from_native(chain: Chain, s: &str) -> Result<Address, AddressError> {
+        chain.parse_address(s).map(Address)
+    }
+}
 
 /// A `GuardianSet` is a versioned set of keys that can sign Wormhole messages.
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -75,10 +101,16 @@ pub struct GuardianSetInfo {
 
 impl GuardianSetInfo {
     pub fn quorum(&self) -> usize {
-        (self.addresses.len() * 2) / 3 + 1
+        quorum(self.addresses.len())
     }
 }
 
+/// Returns the number of signatures needed to reach a super-majority in a guardian set with
+/// `num_guardians` members.
+pub fn quorum(num_guardians: usize) -> usize {
+    (num_guardians * 2) / 3 + 1
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -119,4 +151,30 @@ mod test {
             assert_eq!(quorum, gs.quorum());
         }
     }
+
+    #[test]
+    fn native_address() {
+        let s = "0x3ee18b2214aff97000d974cf647e7c347e8fa585";
+        let addr = Address::from_native(Chain::Ethereum, s).unwrap();
+        assert_eq!(
+            "0000000000000000000000003ee18b2214aff97000d974cf647e7c347e8fa585",
+            addr.to_string()
+        );
+        assert_eq!(
+            s,
+            addr.to_native(Chain::Ethereum)
+                .unwrap()
+                .to_ascii_lowercase()
+        );
+
+        let mut aptos = [0; 32];
+        aptos[31] = 1;
+        assert_eq!(
+            Address(aptos),
+            Address::from_native(Chain::Aptos, "0x1").unwrap()
+        );
+
+        let err = Address::from_native(Chain::Ethereum, &format!("{s}00")).unwrap_err();
+        assert!(matches!(err, AddressError::TooLong(Chain::Ethereum, _)));
+    }
 }
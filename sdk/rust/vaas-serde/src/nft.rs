@@ -7,7 +7,10 @@
 use bstr::BString;
 use serde::{Deserialize, Serialize};
 
-use crate::{Address, Chain};
+use crate::{
+    governance::{GovernanceAction, GovernancePacket},
+    Address, Chain,
+};
 
 /// The ID of the token, encoded as a big-endian uint256.
 #[derive(
@@ -52,7 +55,9 @@ pub enum Message {
 }
 
 /// Represents a governance action targeted at the NFT bridge.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Serialize, Deserialize, GovernanceAction, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
 pub enum Action {
     /// Registers an emitter address for a particular chain on a different chain.  An emitter
     /// address must be registered for a chain and must match the emitter address in the VAA before
@@ -69,7 +74,8 @@ pub enum Action {
 }
 
 /// Represents the payload for a governance VAA targeted at the NFT bridge.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(GovernancePacket, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[governance(module = "NFTBridge")]
 pub struct GovernancePacket {
     /// The chain on which the governance action should be carried out.
     pub chain: Chain,
@@ -78,254 +84,8 @@ pub struct GovernancePacket {
     pub action: Action,
 }
 
-// MODULE = "NFTBridge"
-pub const MODULE: [u8; 32] = *b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00NFTBridge";
-
-// The wire format for GovernancePackets is wonky and doesn't lend itself well to auto-deriving
-// Serialize / Deserialize so we implement it manually here.
-mod governance_packet_impl {
-    use std::fmt;
-
-    use serde::{
-        de::{Error, MapAccess, SeqAccess, Visitor},
-        ser::SerializeStruct,
-        Deserialize, Deserializer, Serialize, Serializer,
-    };
-
-    use crate::{
-        nft::{Action, GovernancePacket, MODULE},
-        Address, Chain,
-    };
-
-    struct Module;
-
-    impl Serialize for Module {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: Serializer,
-        {
-            MODULE.serialize(serializer)
-        }
-    }
-
-    impl<'de> Deserialize<'de> for Module {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            let arr = <[u8; 32]>::deserialize(deserializer)?;
-
-            if arr == MODULE {
-                Ok(Module)
-            } else {
-                Err(Error::custom(
-                    "invalid governance module, expected \"NFTBridge\"",
-                ))
-            }
-        }
-    }
-
-    #[derive(Serialize, Deserialize)]
-    struct ContractUpgrade {
-        new_contract: Address,
-    }
-
-    #[derive(Serialize, Deserialize)]
-    struct RegisterChain {
-        chain: Chain,
-        emitter_address: Address,
-    }
-
-    impl Serialize for GovernancePacket {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: Serializer,
-        {
-            let mut seq = serializer.serialize_struct("GovernancePacket", 4)?;
-            seq.serialize_field("module", &Module)?;
-
-            // The wire format encodes the action before the chain and then appends the actual
-            // action payload.
-            match self.action {
-                Action::RegisterChain {
-                    chain,
-                    emitter_address,
-                } => {
-                    seq.serialize_field("action", &1u8)?;
-                    seq.serialize_field("chain", &self.chain)?;
-                    seq.serialize_field(
-                        "payload",
-                        &RegisterChain {
-                            chain,
-                            emitter_address,
-                        },
-                    )?;
-                }
-                Action::ContractUpgrade { new_contract } => {
-                    seq.serialize_field("action", &2u8)?;
-                    seq.serialize_field("chain", &self.chain)?;
-                    seq.serialize_field("payload", &ContractUpgrade { new_contract })?;
-                }
-            }
-
-            seq.end()
-        }
-    }
-
-    struct GovernancePacketVisitor;
-
-    impl<'de> Visitor<'de> for GovernancePacketVisitor {
-        type Value = GovernancePacket;
-
-        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            f.write_str("struct GovernancePacket")
-        }
-
-        #[inline]
-        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-        where
-            A: SeqAccess<'de>,
-        {
-            static EXPECTING: &str = "struct GovernancePacket with 4 elements";
-
-            let _: Module = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(0, &EXPECTING))?;
-            let act: u8 = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(1, &EXPECTING))?;
-            let chain = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(2, &EXPECTING))?;
-
-            let action = match act {
-                1 => {
-                    let RegisterChain {
-                        chain,
-                        emitter_address,
-                    } = seq
-                        .next_element()?
-                        .ok_or_else(|| Error::invalid_length(3, &EXPECTING))?;
-
-                    Action::RegisterChain {
-                        chain,
-                        emitter_address,
-                    }
-                }
-                2 => {
-                    let ContractUpgrade { new_contract } = seq
-                        .next_element()?
-                        .ok_or_else(|| Error::invalid_length(3, &EXPECTING))?;
-
-                    Action::ContractUpgrade { new_contract }
-                }
-                v => {
-                    return Err(Error::custom(format_args!(
-                        "invalid value {v}, expected one of 1, 2"
-                    )))
-                }
-            };
-
-            Ok(GovernancePacket { chain, action })
-        }
-
-        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
-        where
-            A: MapAccess<'de>,
-        {
-            #[derive(Serialize, Deserialize)]
-            #[serde(rename_all = "snake_case")]
-            enum Field {
-                Module,
-                Action,
-                Chain,
-                Payload,
-            }
-
-            let mut module = None;
-            let mut chain = None;
-            let mut action = None;
-            let mut payload = None;
-
-            while let Some(key) = map.next_key::<Field>()? {
-                match key {
-                    Field::Module => {
-                        if module.is_some() {
-                            return Err(Error::duplicate_field("module"));
-                        }
-
-                        module = map.next_value::<Module>().map(Some)?;
-                    }
-                    Field::Action => {
-                        if action.is_some() {
-                            return Err(Error::duplicate_field("action"));
-                        }
-
-                        action = map.next_value::<u8>().map(Some)?;
-                    }
-                    Field::Chain => {
-                        if chain.is_some() {
-                            return Err(Error::duplicate_field("chain"));
-                        }
-
-                        chain = map.next_value().map(Some)?;
-                    }
-                    Field::Payload => {
-                        if payload.is_some() {
-                            return Err(Error::duplicate_field("payload"));
-                        }
-
-                        let a = action.as_ref().copied().ok_or_else(|| {
-                            Error::custom("`action` must be known before deserializing `payload`")
-                        })?;
-
-                        let p = match a {
-                            1 => {
-                                let RegisterChain {
-                                    chain,
-                                    emitter_address,
-                                } = map.next_value()?;
-
-                                Action::RegisterChain {
-                                    chain,
-                                    emitter_address,
-                                }
-                            }
-                            2 => {
-                                let ContractUpgrade { new_contract } = map.next_value()?;
-
-                                Action::ContractUpgrade { new_contract }
-                            }
-                            v => {
-                                return Err(Error::custom(format_args!(
-                                    "invalid action: {v}, expected one of: 1, 2"
-                                )))
-                            }
-                        };
-
-                        payload = Some(p);
-                    }
-                }
-            }
-
-            let _ = module.ok_or_else(|| Error::missing_field("module"))?;
-            let chain = chain.ok_or_else(|| Error::missing_field("chain"))?;
-            let action = payload.ok_or_else(|| Error::missing_field("payload"))?;
-
-            Ok(GovernancePacket { chain, action })
-        }
-    }
-
-    impl<'de> Deserialize<'de> for GovernancePacket {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            const FIELDS: &[&str] = &["module", "action", "chain", "payload"];
-            deserializer.deserialize_struct("GovernancePacket", FIELDS, GovernancePacketVisitor)
-        }
-    }
-}
+/// The governance module for this contract, left-padded with zeros to 32 bytes.
+pub const MODULE: [u8; 32] = GovernancePacket::MODULE;
 
 #[cfg(test)]
 mod test {
@@ -6,10 +6,16 @@
 use bstr::BString;
 use serde::{Deserialize, Serialize};
 
-use crate::{accountant_modification::ModificationKind, Address, Amount, Chain};
+use crate::{
+    accountant_modification::ModificationKind,
+    governance::{GovernanceAction, GovernancePacket},
+    Address, Amount, Chain,
+};
 
 /// Represents a governance action targeted at the Accountant.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Serialize, Deserialize, GovernanceAction, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
 pub enum Action {
     // Modify balance for accountant
     #[serde(rename = "1")]
@@ -26,7 +32,8 @@ pub enum Action {
 }
 
 /// Represents the payload for a governance VAA targeted at the Accountant.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(GovernancePacket, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[governance(module = "NTTGlobalAccountant")]
 pub struct GovernancePacket {
     /// The chain on which the governance action should be carried out.
     pub chain: Chain,
@@ -35,267 +42,8 @@ pub struct GovernancePacket {
     pub action: Action,
 }
 
-// MODULE = "NTTGlobalAccountant"
-pub const MODULE: [u8; 32] =
-    *b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00NTTGlobalAccountant";
-
-// The wire format for GovernancePackets is wonky and doesn't lend itself well to auto-deriving
-// Serialize / Deserialize so we implement it manually here.
-mod governance_packet_impl {
-    use std::fmt;
-
-    use serde::{
-        de::{Error, MapAccess, SeqAccess, Visitor},
-        ser::SerializeStruct,
-        Deserialize, Deserializer, Serialize, Serializer,
-    };
-
-    use crate::{
-        ntt_accountant::{Action, GovernancePacket, MODULE},
-        Address, Amount,
-    };
-
-    struct Module;
-
-    impl Serialize for Module {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: Serializer,
-        {
-            MODULE.serialize(serializer)
-        }
-    }
-
-    impl<'de> Deserialize<'de> for Module {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            let arr = <[u8; 32]>::deserialize(deserializer)?;
-
-            if arr == MODULE {
-                Ok(Module)
-            } else {
-                Err(Error::custom(
-                    "invalid governance module, expected \"NTTGlobalAccountant\"",
-                ))
-            }
-        }
-    }
-
-    #[derive(Serialize, Deserialize)]
-    struct ModifyBalance {
-        sequence: u64,
-        chain_id: u16,
-        token_chain: u16,
-        token_address: Address,
-        kind: super::ModificationKind,
-        amount: Amount,
-        #[serde(with = "crate::arraystring")]
-        reason: bstr::BString,
-    }
-
-    impl Serialize for GovernancePacket {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: Serializer,
-        {
-            let mut seq = serializer.serialize_struct("GovernancePacket", 4)?;
-            seq.serialize_field("module", &Module)?;
-
-            // The wire format encodes the action before the chain and then appends the actual
-            // action payload.
-            match self.action.clone() {
-                Action::ModifyBalance {
-                    sequence,
-                    chain_id,
-                    token_chain,
-                    token_address,
-                    kind,
-                    amount,
-                    reason,
-                } => {
-                    seq.serialize_field("action", &1u8)?;
-                    seq.serialize_field("chain", &self.chain)?;
-                    seq.serialize_field(
-                        "payload",
-                        &ModifyBalance {
-                            sequence,
-                            chain_id,
-                            token_chain,
-                            token_address,
-                            kind,
-                            amount,
-                            reason,
-                        },
-                    )?;
-                }
-            }
-
-            seq.end()
-        }
-    }
-
-    struct GovernancePacketVisitor;
-
-    impl<'de> Visitor<'de> for GovernancePacketVisitor {
-        type Value = GovernancePacket;
-
-        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            f.write_str("struct GovernancePacket")
-        }
-
-        #[inline]
-        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-        where
-            A: SeqAccess<'de>,
-        {
-            static EXPECTING: &str = "struct GovernancePacket with 4 elements";
-
-            let _: Module = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(0, &EXPECTING))?;
-            let act: u8 = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(1, &EXPECTING))?;
-            let chain = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(2, &EXPECTING))?;
-
-            let action = match act {
-                1 => {
-                    let ModifyBalance {
-                        sequence,
-                        chain_id,
-                        token_chain,
-                        token_address,
-                        kind,
-                        amount,
-                        reason,
-                    } = seq
-                        .next_element()?
-                        .ok_or_else(|| Error::invalid_length(3, &EXPECTING))?;
-                    Action::ModifyBalance {
-                        sequence,
-                        chain_id,
-                        token_chain,
-                        token_address,
-                        kind,
-                        amount,
-                        reason,
-                    }
-                }
-                v => {
-                    return Err(Error::custom(format_args!(
-                        "invalid value {v}, expected one of 1"
-                    )))
-                }
-            };
-
-            Ok(GovernancePacket { chain, action })
-        }
-
-        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
-        where
-            A: MapAccess<'de>,
-        {
-            #[derive(Serialize, Deserialize)]
-            #[serde(rename_all = "snake_case")]
-            enum Field {
-                Module,
-                Action,
-                Chain,
-                Payload,
-            }
-
-            let mut module = None;
-            let mut chain = None;
-            let mut action = None;
-            let mut payload = None;
-
-            while let Some(key) = map.next_key::<Field>()? {
-                match key {
-                    Field::Module => {
-                        if module.is_some() {
-                            return Err(Error::duplicate_field("module"));
-                        }
-
-                        module = map.next_value::<Module>().map(Some)?;
-                    }
-                    Field::Action => {
-                        if action.is_some() {
-                            return Err(Error::duplicate_field("action"));
-                        }
-
-                        action = map.next_value::<u8>().map(Some)?;
-                    }
-                    Field::Chain => {
-                        if chain.is_some() {
-                            return Err(Error::duplicate_field("chain"));
-                        }
-
-                        chain = map.next_value().map(Some)?;
-                    }
-                    Field::Payload => {
-                        if payload.is_some() {
-                            return Err(Error::duplicate_field("payload"));
-                        }
-
-                        let a = action.as_ref().copied().ok_or_else(|| {
-                            Error::custom("`action` must be known before deserializing `payload`")
-                        })?;
-
-                        let p = match a {
-                            1 => {
-                                let ModifyBalance {
-                                    sequence,
-                                    chain_id,
-                                    token_chain,
-                                    token_address,
-                                    kind,
-                                    amount,
-                                    reason,
-                                } = map.next_value()?;
-                                Action::ModifyBalance {
-                                    sequence,
-                                    chain_id,
-                                    token_chain,
-                                    token_address,
-                                    kind,
-                                    amount,
-                                    reason,
-                                }
-                            }
-                            v => {
-                                return Err(Error::custom(format_args!(
-                                    "invalid action: {v}, expected one of: 1"
-                                )))
-                            }
-                        };
-
-                        payload = Some(p);
-                    }
-                }
-            }
-
-            let _ = module.ok_or_else(|| Error::missing_field("module"))?;
-            let chain = chain.ok_or_else(|| Error::missing_field("chain"))?;
-            let action = payload.ok_or_else(|| Error::missing_field("payload"))?;
-
-            Ok(GovernancePacket { chain, action })
-        }
-    }
-
-    impl<'de> Deserialize<'de> for GovernancePacket {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            const FIELDS: &[&str] = &["module", "action", "chain", "payload"];
-            deserializer.deserialize_struct("GovernancePacket", FIELDS, GovernancePacketVisitor)
-        }
-    }
-}
+/// The governance module for this contract, left-padded with zeros to 32 bytes.
+pub const MODULE: [u8; 32] = GovernancePacket::MODULE;
 
 #[cfg(test)]
 mod test {
@@ -2,292 +2,247 @@
 //!
 //! The Standardized Relayer uses VAAs for delivery and redelivery instructions as well as governance.
 //! The delivery VAA contain the sender and may contain an optional payload.
+//!
+//! Delivery and redelivery instructions are encoded as a [`Message`]. A delivery instruction may
+//! also reference other messages, identified by a [`MessageKey`], that should be delivered to the
+//! target contract along with the payload.
 
 // Example devnet registration governance VAAs
 // 010000000001007349c3b9b892432e4d371b37085547eea089533667cd16c0237f4012bc4a011562731487b93c31e9a9b7ab37df1e39ecad3a0a249f019759ef4afe66500db5180165d760ad00000001000100000000000000000000000000000000000000000000000000000000000000040000000000000001010000000000000000000000000000000000576f726d686f6c6552656c61796572010000000200000000000000000000000053855d4b64e9a3cf59a84bc768ada716b5536bc5
 // 01000000000100e4e0dd18bf7a1867027a6f4f9d53c07cab84950de5150e05d48ac7ba84e18f90394d4e825faac2b76b0ce95e34b0e3f91da75d457fc1dfae4720b1fbedc2e6540165d7609e00000001000100000000000000000000000000000000000000000000000000000000000000040000000000000001010000000000000000000000000000000000576f726d686f6c6552656c61796572010000000400000000000000000000000053855d4b64e9a3cf59a84bc768ada716b5536bc5
 
-use serde::{Deserialize, Serialize};
+use alloc::vec::Vec;
+use core::fmt;
 
-use crate::{Address, Chain};
+use bstr::BString;
+use serde::{
+    de::{Error as DeError, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_wormhole::LenPrefixed;
 
-/// Represents a governance action targeted at the standardized relayer.
+use crate::{
+    governance::{GovernanceAction, GovernancePacket},
+    Address, Amount, Chain,
+};
+
+/// Represents a non-governance message emitted by the standardized relayer.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum Action {
-    /// Registers an emitter address for a particular chain on a different chain.  An emitter
-    /// address must be registered for a chain and must match the emitter address in the VAA before
-    /// the standardized relayer will accept VAAs from that chain.
+pub enum Message {
+    /// Requests the delivery of a payload to a contract on another chain.
     #[serde(rename = "1")]
-    RegisterChain {
-        chain: Chain,
-        emitter_address: Address,
-    },
+    Delivery(DeliveryInstruction),
 
-    /// Upgrades the standardized relayer contract to a new address.
+    /// Requests that a previous delivery be attempted again with new execution parameters.
     #[serde(rename = "2")]
-    ContractUpgrade { new_contract: Address },
+    Redelivery(RedeliveryInstruction),
 }
 
-/// Represents the payload for a governance VAA targeted at the standardized relayer.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct GovernancePacket {
-    /// The chain on which the governance action should be carried out.
+/// The instructions for delivering a payload to a contract on another chain.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeliveryInstruction {
+    /// The chain on which the target contract lives.
+    pub target_chain: Chain,
+
+    /// The address of the target contract. Left-zero-padded if shorter than 32 bytes.
+    pub target_address: Address,
+
+    /// The payload that should be delivered to the target contract.
+    #[serde(with = "serde_wormhole::len_prefixed::u32")]
+    pub payload: BString,
+
+    /// The amount of the target chain's native currency that should be passed to the target
+    /// contract, as requested by the sender.
+    pub requested_receiver_value: Amount,
+
+    /// Additional native currency that should be passed to the target contract, paid for by the
+    /// sender on the source chain.
+    pub extra_receiver_value: Amount,
+
+    /// The execution parameters for the delivery, like the gas limit. The encoding depends on the
+    /// runtime of the target chain.
+    #[serde(with = "serde_wormhole::len_prefixed::u32")]
+    pub encoded_execution_info: BString,
+
+    /// The chain to which any refund should be sent.
+    pub refund_chain: Chain,
+
+    /// The address that should receive any refund. Left-zero-padded if shorter than 32 bytes.
+    pub refund_address: Address,
+
+    /// The delivery provider on the refund chain that should relay the refund.
+    pub refund_delivery_provider: Address,
+
+    /// The delivery provider that was paid to perform the delivery.
+    pub source_delivery_provider: Address,
+
+    /// The address of the contract that requested the delivery.
+    pub sender_address: Address,
+
+    /// Additional messages that should be delivered to the target contract along with the
+    /// payload.
+    pub message_keys: Vec<MessageKey>,
+}
+
+/// The instructions for retrying a previous delivery.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RedeliveryInstruction {
+    /// Identifies the VAA containing the original delivery instruction.
+    pub delivery_vaa_key: VaaKey,
+
+    /// The chain on which the target contract lives.
+    pub target_chain: Chain,
+
+    /// Replaces the `requested_receiver_value` of the original delivery.
+    pub new_requested_receiver_value: Amount,
+
+    /// Replaces the `encoded_execution_info` of the original delivery.
+    #[serde(with = "serde_wormhole::len_prefixed::u32")]
+    pub new_encoded_execution_info: BString,
+
+    /// The delivery provider that was paid to perform the redelivery.
+    pub new_source_delivery_provider: Address,
+
+    /// The address of the contract that requested the redelivery.
+    pub new_sender_address: Address,
+}
+
+/// Uniquely identifies a VAA by its emitter and sequence number.
+#[derive(
+    Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub struct VaaKey {
     pub chain: Chain,
+    pub emitter_address: Address,
+    pub sequence: u64,
+}
 
-    /// The actual governance action to be carried out.
-    pub action: Action,
+/// Identifies a message that should be delivered along with a [`DeliveryInstruction`].
+///
+/// On the wire, a message key is a single byte indicating the type of the key followed by the key
+/// itself. VAA keys have a fixed size while all other keys are prefixed with their length as a
+/// big-endian `u32`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MessageKey {
+    /// A VAA emitted by the core bridge.
+    Vaa(VaaKey),
+
+    /// A message with a key type that is not known to this crate, like a CCTP message. `key` is
+    /// the opaque, type-specific encoding of the message key. `key_type` must not be
+    /// [`MessageKey::VAA_KEY_TYPE`].
+    Other { key_type: u8, key: BString },
 }
 
-// MODULE = "WormholeRelayer"
-pub const MODULE: [u8; 32] =
-    *b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00WormholeRelayer";
-
-// The wire format for GovernancePackets is wonky and doesn't lend itself well to auto-deriving
-// Serialize / Deserialize so we implement it manually here.
-mod governance_packet_impl {
-    use std::fmt;
-
-    use serde::{
-        de::{Error, MapAccess, SeqAccess, Visitor},
-        ser::SerializeStruct,
-        Deserialize, Deserializer, Serialize, Serializer,
-    };
-
-    use crate::{
-        relayer::{Action, GovernancePacket, MODULE},
-        Address, Chain,
-    };
-
-    struct Module;
-
-    impl Serialize for Module {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: Serializer,
-        {
-            MODULE.serialize(serializer)
-        }
-    }
+impl MessageKey {
+    /// The key type of [`MessageKey::Vaa`].
+    pub const VAA_KEY_TYPE: u8 = 1;
 
-    impl<'de> Deserialize<'de> for Module {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            let arr = <[u8; 32]>::deserialize(deserializer)?;
-
-            if arr == MODULE {
-                Ok(Module)
-            } else {
-                Err(Error::custom(
-                    "invalid governance module, expected \"WormholeRelayer\"",
-                ))
-            }
+    /// Returns the type of this key.
+    pub fn key_type(&self) -> u8 {
+        match self {
+            MessageKey::Vaa(_) => Self::VAA_KEY_TYPE,
+            MessageKey::Other { key_type, .. } => *key_type,
         }
     }
+}
 
-    #[derive(Serialize, Deserialize)]
-    struct ContractUpgrade {
-        new_contract: Address,
-    }
-
-    #[derive(Serialize, Deserialize)]
-    struct RegisterChain {
-        chain: Chain,
-        emitter_address: Address,
+impl From<VaaKey> for MessageKey {
+    fn from(key: VaaKey) -> Self {
+        MessageKey::Vaa(key)
     }
+}
 
-    impl Serialize for GovernancePacket {
-        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where
-            S: Serializer,
-        {
-            let mut seq = serializer.serialize_struct("GovernancePacket", 4)?;
-            seq.serialize_field("module", &Module)?;
-
-            // The wire format encodes the action before the chain and then appends the actual
-            // action payload.
-            match self.action.clone() {
-                Action::RegisterChain {
-                    chain,
-                    emitter_address,
-                } => {
-                    seq.serialize_field("action", &1u8)?;
-                    seq.serialize_field("chain", &self.chain)?;
-                    seq.serialize_field(
-                        "payload",
-                        &RegisterChain {
-                            chain,
-                            emitter_address,
-                        },
-                    )?;
-                }
-                Action::ContractUpgrade { new_contract } => {
-                    seq.serialize_field("action", &2u8)?;
-                    seq.serialize_field("chain", &self.chain)?;
-                    seq.serialize_field("payload", &ContractUpgrade { new_contract })?;
-                }
+impl Serialize for MessageKey {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut tup = serializer.serialize_tuple(2)?;
+        tup.serialize_element(&self.key_type())?;
+        match self {
+            MessageKey::Vaa(key) => tup.serialize_element(key)?,
+            MessageKey::Other { key, .. } => {
+                tup.serialize_element(&LenPrefixed::<u32, _>::new(key))?
             }
-
-            seq.end()
         }
+        tup.end()
     }
+}
 
-    struct GovernancePacketVisitor;
+struct MessageKeyVisitor;
 
-    impl<'de> Visitor<'de> for GovernancePacketVisitor {
-        type Value = GovernancePacket;
+impl<'de> Visitor<'de> for MessageKeyVisitor {
+    type Value = MessageKey;
 
-        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            f.write_str("struct GovernancePacket")
-        }
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a key type followed by a message key")
+    }
 
-        #[inline]
-        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-        where
-            A: SeqAccess<'de>,
-        {
-            static EXPECTING: &str = "struct GovernancePacket with 4 elements";
-
-            let _: Module = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(0, &EXPECTING))?;
-            let act: u8 = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(1, &EXPECTING))?;
-            let chain = seq
-                .next_element()?
-                .ok_or_else(|| Error::invalid_length(2, &EXPECTING))?;
-
-            let action = match act {
-                1 => {
-                    let RegisterChain {
-                        chain,
-                        emitter_address,
-                    } = seq
-                        .next_element()?
-                        .ok_or_else(|| Error::invalid_length(3, &EXPECTING))?;
-
-                    Action::RegisterChain {
-                        chain,
-                        emitter_address,
-                    }
-                }
-                2 => {
-                    let ContractUpgrade { new_contract } = seq
-                        .next_element()?
-                        .ok_or_else(|| Error::invalid_length(3, &EXPECTING))?;
-
-                    Action::ContractUpgrade { new_contract }
-                }
-                v => {
-                    return Err(Error::custom(format_args!(
-                        "invalid value: {v}, expected one of 1, 2"
-                    )))
-                }
-            };
-
-            Ok(GovernancePacket { chain, action })
-        }
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let key_type: u8 = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+
+        let key = if key_type == MessageKey::VAA_KEY_TYPE {
+            seq.next_element()?.map(MessageKey::Vaa)
+        } else {
+            seq.next_element::<LenPrefixed<u32, BString>>()?
+                .map(|key| MessageKey::Other {
+                    key_type,
+                    key: key.into_inner(),
+                })
+        };
 
-        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
-        where
-            A: MapAccess<'de>,
-        {
-            #[derive(Serialize, Deserialize)]
-            #[serde(rename_all = "snake_case")]
-            enum Field {
-                Module,
-                Action,
-                Chain,
-                Payload,
-            }
+        key.ok_or_else(|| A::Error::invalid_length(1, &self))
+    }
+}
 
-            let mut module = None;
-            let mut chain = None;
-            let mut action = None;
-            let mut payload = None;
-
-            while let Some(key) = map.next_key::<Field>()? {
-                match key {
-                    Field::Module => {
-                        if module.is_some() {
-                            return Err(Error::duplicate_field("module"));
-                        }
-
-                        module = map.next_value::<Module>().map(Some)?;
-                    }
-                    Field::Action => {
-                        if action.is_some() {
-                            return Err(Error::duplicate_field("action"));
-                        }
-
-                        action = map.next_value::<u8>().map(Some)?;
-                    }
-                    Field::Chain => {
-                        if chain.is_some() {
-                            return Err(Error::duplicate_field("chain"));
-                        }
-
-                        chain = map.next_value().map(Some)?;
-                    }
-                    Field::Payload => {
-                        if payload.is_some() {
-                            return Err(Error::duplicate_field("payload"));
-                        }
-
-                        let a = action.as_ref().copied().ok_or_else(|| {
-                            Error::custom("`action` must be known before deserializing `payload`")
-                        })?;
-
-                        let p = match a {
-                            1 => {
-                                let RegisterChain {
-                                    chain,
-                                    emitter_address,
-                                } = map.next_value()?;
-
-                                Action::RegisterChain {
-                                    chain,
-                                    emitter_address,
-                                }
-                            }
-                            2 => {
-                                let ContractUpgrade { new_contract } = map.next_value()?;
-
-                                Action::ContractUpgrade { new_contract }
-                            }
-                            v => {
-                                return Err(Error::custom(format_args!(
-                                    "invalid action: {v}, expected one of: 1, 2"
-                                )))
-                            }
-                        };
-
-                        payload = Some(p);
-                    }
-                }
-            }
+impl<'de> Deserialize<'de> for MessageKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, MessageKeyVisitor)
+    }
+}
 
-            let _ = module.ok_or_else(|| Error::missing_field("module"))?;
-            let chain = chain.ok_or_else(|| Error::missing_field("chain"))?;
-            let action = payload.ok_or_else(|| Error::missing_field("payload"))?;
+/// Represents a governance action targeted at the standardized relayer.
+#[derive(
+    Serialize, Deserialize, GovernanceAction, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum Action {
+    /// Registers an emitter address for a particular chain on a different chain.  An emitter
+    /// address must be registered for a chain and must match the emitter address in the VAA before
+    /// the standardized relayer will accept VAAs from that chain.
+    #[serde(rename = "1")]
+    RegisterChain {
+        chain: Chain,
+        emitter_address: Address,
+    },
 
-            Ok(GovernancePacket { chain, action })
-        }
-    }
+    /// Upgrades the standardized relayer contract to a new address.
+    #[serde(rename = "2")]
+    ContractUpgrade { new_contract: Address },
+}
 
-    impl<'de> Deserialize<'de> for GovernancePacket {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            const FIELDS: &[&str] = &["module", "action", "chain", "payload"];
-            deserializer.deserialize_struct("GovernancePacket", FIELDS, GovernancePacketVisitor)
-        }
-    }
+/// Represents the payload for a governance VAA targeted at the standardized relayer.
+#[derive(GovernancePacket, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[governance(module = "WormholeRelayer")]
+pub struct GovernancePacket {
+    /// The chain on which the governance action should be carried out.
+    pub chain: Chain,
+
+    /// The actual governance action to be carried out.
+    pub action: Action,
 }
 
+/// The governance module for this contract, left-padded with zeros to 32 bytes.
+pub const MODULE: [u8; 32] = GovernancePacket::MODULE;
+
 #[cfg(test)]
 mod test {
     use crate::{vaa::Signature, Vaa, GOVERNANCE_EMITTER};
@@ -351,4 +306,141 @@ mod test {
         let encoded = serde_json::to_string(&vaa).unwrap();
         assert_eq!(vaa, serde_json::from_str(&encoded).unwrap());
     }
+
+    #[test]
+    fn delivery() {
+        // Payload of testnet delivery VAA
+        // 0xb6de172a31d41e0aff2c928c4601d5328b05ccfd382ad853d0b1a61c3bfed869.
+        let buf = [
+            0x01, 0x27, 0x12, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x79, 0x68, 0x9c, 0xe6, 0x00, 0xd3, 0xfd, 0x35, 0x24, 0xec, 0x2b, 0x4b, 0xed,
+            0xcc, 0x70, 0x13, 0x1e, 0xda, 0x67, 0xb6, 0x00, 0x00, 0x00, 0x9f, 0x99, 0x45, 0xff,
+            0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xe4,
+            0x93, 0xcc, 0x4f, 0x06, 0x98, 0x21, 0x40, 0x4d, 0x27, 0x2b, 0x99, 0x4b, 0xb8, 0x0b,
+            0x1b, 0xa1, 0x63, 0x19, 0x14, 0x00, 0x79, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x8f,
+            0x26, 0xa0, 0x02, 0x5d, 0xcc, 0xc6, 0xcf, 0xc0, 0x7a, 0x7d, 0x38, 0x75, 0x62, 0x80,
+            0xa1, 0x0e, 0x29, 0x5a, 0xd7, 0x00, 0x4f, 0x99, 0x4e, 0x54, 0x54, 0x08, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x03, 0xe8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0xa8, 0x80, 0x85, 0xe6, 0x37, 0x0a, 0x55, 0x1c, 0xc0, 0x46,
+            0xfb, 0x6b, 0x1e, 0x3f, 0xb9, 0xbe, 0x23, 0xac, 0x3a, 0x21, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x8f, 0x26, 0xa0, 0x02, 0x5d, 0xcc,
+            0xc6, 0xcf, 0xc0, 0x7a, 0x7d, 0x38, 0x75, 0x62, 0x80, 0xa1, 0x0e, 0x29, 0x5a, 0xd7,
+            0x27, 0x12, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x60,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0xa1, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x6f, 0x53, 0x99, 0xe7, 0x27, 0x12,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x7a, 0x0a, 0x53, 0x84, 0x77, 0x76, 0xf7, 0xe9, 0x4c, 0xc3, 0x57, 0x42,
+            0x97, 0x1a, 0xcb, 0x22, 0x17, 0xb0, 0xdb, 0x81, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7a, 0x0a, 0x53, 0x84, 0x77, 0x76, 0xf7, 0xe9,
+            0x4c, 0xc3, 0x57, 0x42, 0x97, 0x1a, 0xcb, 0x22, 0x17, 0xb0, 0xdb, 0x81, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xe4, 0x93, 0xcc, 0x4f,
+            0x06, 0x98, 0x21, 0x40, 0x4d, 0x27, 0x2b, 0x99, 0x4b, 0xb8, 0x0b, 0x1b, 0xa1, 0x63,
+            0x19, 0x14, 0x00,
+        ];
+
+        let msg = serde_wormhole::from_slice::<Message>(&buf).unwrap();
+        let Message::Delivery(delivery) = &msg else {
+            panic!("unexpected message: {msg:?}");
+        };
+
+        assert_eq!(Chain::from(10002), delivery.target_chain);
+        assert_eq!(&buf[3..35], &delivery.target_address.0[..]);
+        assert_eq!(&buf[39..198], &delivery.payload[..]);
+        assert_eq!(Amount([0; 32]), delivery.requested_receiver_value);
+        assert_eq!(Amount([0; 32]), delivery.extra_receiver_value);
+        assert_eq!(&buf[266..362], &delivery.encoded_execution_info[..]);
+        assert_eq!(Chain::from(10002), delivery.refund_chain);
+        assert_eq!(Address([0; 32]), delivery.refund_address);
+        assert_eq!(
+            delivery.refund_delivery_provider,
+            delivery.source_delivery_provider
+        );
+        assert_eq!(&buf[460..492], &delivery.sender_address.0[..]);
+        assert!(delivery.message_keys.is_empty());
+
+        assert_eq!(&buf[..], &serde_wormhole::to_vec(&msg).unwrap());
+
+        let encoded = serde_json::to_string(&msg).unwrap();
+        assert_eq!(msg, serde_json::from_str(&encoded).unwrap());
+    }
+
+    #[test]
+    fn message_keys() {
+        let vaa_key = VaaKey {
+            chain: Chain::Ethereum,
+            emitter_address: Address([0x3c; 32]),
+            sequence: 0x0102_0304_0506_0708,
+        };
+        let keys = vec![
+            MessageKey::from(vaa_key),
+            MessageKey::Other {
+                key_type: 2,
+                key: BString::from(vec![0xaa, 0xbb, 0xcc]),
+            },
+        ];
+
+        let mut expected = vec![2, 1, 0, 2];
+        expected.extend_from_slice(&[0x3c; 32]);
+        expected.extend_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        expected.extend_from_slice(&[2, 0, 0, 0, 3, 0xaa, 0xbb, 0xcc]);
+
+        assert_eq!(expected, serde_wormhole::to_vec(&keys).unwrap());
+        assert_eq!(
+            keys,
+            serde_wormhole::from_slice::<Vec<MessageKey>>(&expected).unwrap()
+        );
+
+        let encoded = serde_json::to_string(&keys).unwrap();
+        assert_eq!(
+            keys,
+            serde_json::from_str::<Vec<MessageKey>>(&encoded).unwrap()
+        );
+
+        // Truncated key.
+        assert!(serde_wormhole::from_slice::<MessageKey>(&expected[1..40]).is_err());
+        assert!(serde_wormhole::from_slice::<MessageKey>(&expected[44..50]).is_err());
+    }
+
+    #[test]
+    fn redelivery() {
+        let msg = Message::Redelivery(RedeliveryInstruction {
+            delivery_vaa_key: VaaKey {
+                chain: Chain::Solana,
+                emitter_address: Address([0x11; 32]),
+                sequence: 7,
+            },
+            target_chain: Chain::Ethereum,
+            new_requested_receiver_value: Amount([0x22; 32]),
+            new_encoded_execution_info: BString::from(vec![0x33; 5]),
+            new_source_delivery_provider: Address([0x44; 32]),
+            new_sender_address: Address([0x55; 32]),
+        });
+
+        let mut expected = vec![2, 0, 1];
+        expected.extend_from_slice(&[0x11; 32]);
+        expected.extend_from_slice(&7u64.to_be_bytes());
+        expected.extend_from_slice(&[0, 2]);
+        expected.extend_from_slice(&[0x22; 32]);
+        expected.extend_from_slice(&[0, 0, 0, 5, 0x33, 0x33, 0x33, 0x33, 0x33]);
+        expected.extend_from_slice(&[0x44; 32]);
+        expected.extend_from_slice(&[0x55; 32]);
+
+        assert_eq!(expected, serde_wormhole::to_vec(&msg).unwrap());
+        assert_eq!(msg, serde_wormhole::from_slice(&expected).unwrap());
+
+        let encoded = serde_json::to_string(&msg).unwrap();
+        assert_eq!(msg, serde_json::from_str(&encoded).unwrap());
+    }
 }
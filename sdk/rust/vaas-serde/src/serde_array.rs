@@ -1,4 +1,4 @@
-use std::{fmt, mem::MaybeUninit};
+use core::{fmt, mem::MaybeUninit};
 
 use serde::{
     de::{Error, SeqAccess, Visitor},
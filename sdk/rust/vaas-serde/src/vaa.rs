@@ -9,14 +9,31 @@
 //! includes parsers for the core VAA type. Programs targetting wormhole can use this module to
 //! parse and verify incoming VAA's securely.
 
-use std::io::{self, Write};
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::{
+    collections::HashMap,
+    io,
+    sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
+use k256::{
+    ecdsa::{recoverable, signature::hazmat::PrehashSigner},
+    elliptic_curve::sec1::ToEncodedPoint,
+};
 use serde::{Deserialize, Serialize};
+use serde_wormhole::RawMessage;
 use sha3::Digest as Sha3Digest;
+use thiserror::Error;
 
 use crate::{Address, Chain, GuardianAddress};
 
+pub use k256::ecdsa::{SigningKey, VerifyingKey};
+pub use view::{Signatures, VaaView, ViewError};
+
+mod view;
+
 /// Signatures are typical ECDSA signatures prefixed with a Guardian position. These have the
 /// following byte layout:
 /// ```markdown
@@ -28,7 +45,7 @@ use crate::{Address, Chain, GuardianAddress};
 pub struct Signature {
     pub index: u8,
     #[serde(with = "crate::serde_array")]
-    #[schemars(with = "schemars_array::Array<u8, 65>")]
+    #[cfg_attr(feature = "schemars", schemars(with = "schemars_array::Array<u8, 65>"))]
     pub signature: [u8; 65],
 }
 
@@ -146,25 +163,23 @@ pub struct Digest {
 /// NOTE: This function uses a library to do Keccak256 hashing, but on-chain this may not be
 /// efficient. If efficiency is needed, consider calling `body()` instead and hashing the
 /// result using on-chain primitives.
+#[cfg(feature = "std")]
 pub fn digest(body: &[u8]) -> io::Result<Digest> {
+    Ok(body_digest(body))
+}
+
+// Infallible version of `digest` for callers that already have the body bytes.
+fn body_digest(body: &[u8]) -> Digest {
     // The `body` of the VAA is hashed to produce a `digest` of the VAA.
-    let hash: [u8; 32] = {
-        let mut h = sha3::Keccak256::default();
-        h.write_all(body)?;
-        h.finalize().into()
-    };
+    let hash: [u8; 32] = sha3::Keccak256::digest(body).into();
 
     // Hash `hash` again to get the secp256k internal hash, see `Digest` for more details.
-    let secp256k_hash: [u8; 32] = {
-        let mut h = sha3::Keccak256::default();
-        h.write_all(&hash)?;
-        h.finalize().into()
-    };
+    let secp256k_hash: [u8; 32] = sha3::Keccak256::digest(hash).into();
 
-    Ok(Digest {
+    Digest {
         hash,
         secp256k_hash,
-    })
+    }
 }
 
 impl<P> Vaa<P> {
@@ -212,9 +227,367 @@ impl<P> From<(Header, Body<P>)> for Vaa<P> {
     }
 }
 
+/// The reason a VAA failed signature verification.
+#[derive(Debug, Error)]
+pub enum VerifyError {
+    #[error("insufficient signatures: got {signatures}, need {quorum}")]
+    NoQuorum { signatures: usize, quorum: usize },
+    #[error("duplicate signature for guardian index {0}")]
+    DuplicateGuardianIndex(u8),
+    #[error("guardian index {index} is out of order (previous index was {previous})")]
+    GuardianIndexOrder { index: u8, previous: u8 },
+    #[error("guardian index {index} is out of range for a guardian set of size {len}")]
+    GuardianIndexOutOfRange { index: u8, len: usize },
+    #[error("failed to recover public key from signature for guardian index {0}")]
+    InvalidSignature(u8),
+    #[error(
+        "signature for guardian index {index} was produced by {recovered:?}, not {expected:?}"
+    )]
+    GuardianSignatureMismatch {
+        index: u8,
+        expected: GuardianAddress,
+        recovered: GuardianAddress,
+    },
+}
+
 impl Header {
-    pub fn verify(&self, _body: &[u8], _addrs: &[GuardianAddress]) -> anyhow::Result<Digest> {
-        todo!("VAA body verification")
+    /// Verifies that the signatures in this header were produced over `body` by a quorum of the
+    /// guardians in `addrs`, where `addrs` is the guardian set identified by
+    /// `self.guardian_set_index`.
+    ///
+    /// Signatures must be sorted by strictly increasing guardian index, as required by the on-chain
+    /// verifiers. On success, returns the digest of `body`.
+    ///
+    /// NOTE: this does not check the guardian set index or whether the guardian set has expired.
+    /// Callers are responsible for looking up the correct set of addresses.
+    pub fn verify(&self, body: &[u8], addrs: &[GuardianAddress]) -> Result<Digest, VerifyError> {
+        self.verify_with(body, addrs, Signature::recover)
+    }
+
+    /// Like [`Header::verify`] but looks up recovered guardian addresses in `cache` before falling
+    /// back to public key recovery. This is useful when the same signatures are verified many
+    /// times, for example when a VAA is observed from several sources.
+    #[cfg(feature = "std")]
+    pub fn verify_cached(
+        &self,
+        body: &[u8],
+        addrs: &[GuardianAddress],
+        cache: &SignatureCache,
+    ) -> Result<Digest, VerifyError> {
+        self.verify_with(body, addrs, |sig, d| cache.recover(sig, d))
+    }
+
+    fn verify_with<F>(
+        &self,
+        body: &[u8],
+        addrs: &[GuardianAddress],
+        recover: F,
+    ) -> Result<Digest, VerifyError>
+    where
+        F: FnMut(&Signature, &Digest) -> Result<GuardianAddress, VerifyError>,
+    {
+        verify_signatures(self.signatures.iter().copied(), body, addrs, recover)
+    }
+}
+
+// Checks that `signatures` were produced over `body` by a quorum of `addrs`, using `recover` to
+// recover the guardian address for each signature.
+fn verify_signatures<I, F>(
+    signatures: I,
+    body: &[u8],
+    addrs: &[GuardianAddress],
+    mut recover: F,
+) -> Result<Digest, VerifyError>
+where
+    I: ExactSizeIterator<Item = Signature>,
+    F: FnMut(&Signature, &Digest) -> Result<GuardianAddress, VerifyError>,
+{
+    let quorum = crate::quorum(addrs.len());
+    if signatures.len() < quorum {
+        return Err(VerifyError::NoQuorum {
+            signatures: signatures.len(),
+            quorum,
+        });
+    }
+
+    let d = body_digest(body);
+
+    let mut previous: Option<u8> = None;
+    for sig in signatures {
+        match previous {
+            Some(p) if p == sig.index => {
+                return Err(VerifyError::DuplicateGuardianIndex(sig.index))
+            }
+            Some(p) if p > sig.index => {
+                return Err(VerifyError::GuardianIndexOrder {
+                    index: sig.index,
+                    previous: p,
+                })
+            }
+            _ => previous = Some(sig.index),
+        }
+
+        let expected =
+            *addrs
+                .get(usize::from(sig.index))
+                .ok_or(VerifyError::GuardianIndexOutOfRange {
+                    index: sig.index,
+                    len: addrs.len(),
+                })?;
+
+        let recovered = recover(&sig, &d)?;
+        if recovered != expected {
+            return Err(VerifyError::GuardianSignatureMismatch {
+                index: sig.index,
+                expected,
+                recovered,
+            });
+        }
+    }
+
+    Ok(d)
+}
+
+/// Verifies a batch of `(header, body)` pairs against the guardian set `addrs`, returning the
+/// result for each pair in the same order. A failure to verify one VAA does not affect the
+/// others.
+///
+/// Recovered guardian addresses are memoized in `cache`, which can be reused across batches. When
+/// the `rayon` feature is enabled, the VAAs in the batch are verified in parallel.
+#[cfg(feature = "std")]
+pub fn verify_batch<B>(
+    vaas: &[(Header, B)],
+    addrs: &[GuardianAddress],
+    cache: &SignatureCache,
+) -> Vec<Result<Digest, VerifyError>>
+where
+    B: AsRef<[u8]> + Sync,
+{
+    #[cfg(feature = "rayon")]
+    use rayon::prelude::*;
+
+    #[cfg(feature = "rayon")]
+    let iter = vaas.par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let iter = vaas.iter();
+
+    iter.map(|(header, body)| header.verify_cached(body.as_ref(), addrs, cache))
+        .collect()
+}
+
+#[cfg(feature = "std")]
+type RecoveryMap = HashMap<([u8; 32], [u8; 65]), Option<GuardianAddress>>;
+
+/// A thread-safe memo of public key recovery results, keyed by the digest that was signed and the
+/// signature itself. The guardian index is not part of the key since it does not affect recovery.
+///
+/// The cache holds at most `capacity` entries. Once it is full, it is cleared before new entries
+/// are added.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct SignatureCache {
+    entries: RwLock<RecoveryMap>,
+    capacity: usize,
+}
+
+#[cfg(feature = "std")]
+impl SignatureCache {
+    /// The default capacity of a `SignatureCache`.
+    pub const DEFAULT_CAPACITY: usize = 1 << 16;
+
+    /// Creates an empty cache that holds at most `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> Self {
+        SignatureCache {
+            entries: RwLock::new(HashMap::new()),
+            capacity,
+        }
+    }
+
+    /// Returns the number of cached recovery results.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Returns true if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Removes all cached recovery results.
+    pub fn clear(&self) {
+        self.write().clear()
+    }
+
+    /// Recovers the address of the guardian that produced `sig` over `digest`, using a cached
+    /// result if one is available.
+    pub fn recover(
+        &self,
+        sig: &Signature,
+        digest: &Digest,
+    ) -> Result<GuardianAddress, VerifyError> {
+        let key = (digest.secp256k_hash, sig.signature);
+        let cached = self.read().get(&key).copied();
+
+        let addr = match cached {
+            Some(addr) => addr,
+            None => {
+                let addr = sig.recover(digest).ok();
+
+                let mut entries = self.write();
+                if entries.len() >= self.capacity {
+                    entries.clear();
+                }
+                let _ = entries.insert(key, addr);
+
+                addr
+            }
+        };
+
+        addr.ok_or(VerifyError::InvalidSignature(sig.index))
+    }
+
+    // A panic while holding the lock cannot leave the map in an inconsistent state so it's safe
+    // to ignore poisoning.
+    fn read(&self) -> RwLockReadGuard<'_, RecoveryMap> {
+        self.entries.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, RecoveryMap> {
+        self.entries.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for SignatureCache {
+    fn default() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl Signature {
+    /// Recovers the address of the guardian that produced this signature over `digest`.
+    pub fn recover(&self, digest: &Digest) -> Result<GuardianAddress, VerifyError> {
+        recoverable::Signature::try_from(&self.signature[..])
+            .and_then(|s| s.recover_verifying_key_from_digest_bytes(&digest.secp256k_hash.into()))
+            .map(|k| GuardianAddress::from(&k))
+            .map_err(|_| VerifyError::InvalidSignature(self.index))
+    }
+}
+
+impl From<&VerifyingKey> for GuardianAddress {
+    /// Derives the guardian address for a public key. Like Ethereum addresses, this is the last 20
+    /// bytes of the Keccak256 hash of the uncompressed public key (without the leading 0x04 tag).
+    fn from(key: &VerifyingKey) -> Self {
+        let point = key.to_encoded_point(false);
+        let hash: [u8; 32] = sha3::Keccak256::digest(&point.as_bytes()[1..]).into();
+
+        let mut addr = [0u8; 20];
+        addr.copy_from_slice(&hash[12..]);
+        GuardianAddress(addr)
+    }
+}
+
+/// Builds signed VAAs from a `Body` and a set of guardian keys. This is mainly useful for tests
+/// and devnets, where the guardian keys are known.
+///
+/// ```
+/// use serde_wormhole::RawMessage;
+/// use wormhole_sdk::{
+///     vaa::{Body, Header, SigningKey, VaaBuilder},
+///     Chain, GuardianAddress,
+/// };
+///
+/// let key = SigningKey::from_bytes(&[0x5a; 32]).unwrap();
+/// let guardians = [GuardianAddress::from(&key.verifying_key())];
+///
+/// let body = Body {
+///     emitter_chain: Chain::Ethereum,
+///     payload: "hello",
+///     ..Default::default()
+/// };
+/// let (vaa, data) = VaaBuilder::new(body)
+///     .guardian_set_index(3)
+///     .guardians([key])
+///     .build_bytes()
+///     .unwrap();
+/// assert_eq!(3, vaa.guardian_set_index);
+///
+/// let (header, body) = serde_wormhole::from_slice::<(Header, &RawMessage)>(&data).unwrap();
+/// header.verify(body, &guardians).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct VaaBuilder<P> {
+    body: Body<P>,
+    guardian_set_index: u32,
+    keys: BTreeMap<u8, SigningKey>,
+}
+
+impl<P> VaaBuilder<P> {
+    /// Creates a new builder for a VAA with the given `body`, guardian set index 0, and no
+    /// signatures.
+    pub fn new(body: Body<P>) -> Self {
+        VaaBuilder {
+            body,
+            guardian_set_index: 0,
+            keys: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the index of the guardian set that signs the VAA.
+    pub fn guardian_set_index(mut self, index: u32) -> Self {
+        self.guardian_set_index = index;
+        self
+    }
+
+    /// Adds a signature from the guardian at position `index` in the guardian set, replacing any
+    /// key previously set for that index.
+    pub fn guardian(mut self, index: u8, key: SigningKey) -> Self {
+        let _ = self.keys.insert(index, key);
+        self
+    }
+
+    /// Adds signatures from `keys`, where the guardian at position `i` in the guardian set has
+    /// the `i`-th key.
+    pub fn guardians<I: IntoIterator<Item = SigningKey>>(mut self, keys: I) -> Self {
+        self.keys.extend((0..=u8::MAX).zip(keys));
+        self
+    }
+}
+
+impl<P: Serialize> VaaBuilder<P> {
+    /// Signs the body and returns the resulting VAA. Signatures are ordered by guardian index.
+    pub fn build(self) -> anyhow::Result<Vaa<P>> {
+        let d = self.body.digest()?;
+
+        let signatures = self
+            .keys
+            .iter()
+            .map(|(&index, key)| {
+                let sig: recoverable::Signature = key
+                    .sign_prehash(&d.secp256k_hash)
+                    .map_err(|_| anyhow!("failed to sign VAA digest for guardian {index}"))?;
+
+                let mut signature = [0u8; 65];
+                signature.copy_from_slice(sig.as_ref());
+                Ok(Signature { index, signature })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let header = Header {
+            version: 1,
+            guardian_set_index: self.guardian_set_index,
+            signatures,
+        };
+
+        Ok((header, self.body).into())
+    }
+
+    /// Signs the body and returns the resulting VAA along with its wire format encoding.
+    pub fn build_bytes(self) -> anyhow::Result<(Vaa<P>, Vec<u8>)> {
+        let vaa = self.build()?;
+        let data = serde_wormhole::to_vec(&vaa).context("failed to serialize VAA")?;
+
+        Ok((vaa, data))
     }
 }
 
@@ -242,32 +615,157 @@ impl<P: Serialize> Body<P> {
     /// each field.
     ///
     /// NOTE: This function uses a library to do Keccak256 hashing, but on-chain this may not be
-    /// efficient. If efficiency is needed, consider calling `serde_wormhole::to_writer` instead
+    /// efficient. If efficiency is needed, consider calling `serde_wormhole::to_vec` instead
     /// and hashing the result using on-chain primitives.
     #[inline]
     pub fn digest(&self) -> anyhow::Result<Digest> {
-        // The `body` of the VAA is hashed to produce a `digest` of the VAA.
-        let hash: [u8; 32] = {
-            let mut h = sha3::Keccak256::default();
-            serde_wormhole::to_writer(&mut h, self).context("failed to serialize body")?;
-            h.finalize().into()
-        };
+        let body = serde_wormhole::to_vec(self).context("failed to serialize body")?;
+        Ok(body_digest(&body))
+    }
+}
+
+/// Identifies the kind of contract behind a registered emitter.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EmitterKind {
+    TokenBridge,
+    NftBridge,
+}
+
+/// A set of known emitters, used by [`parse_any`] to decide how to decode non-governance payloads.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct EmitterRegistry {
+    emitters: BTreeMap<(Chain, Address), EmitterKind>,
+}
 
-        // Hash `hash` again to get the secp256k internal hash, see `Digest` for detail.
-        let secp256k_hash: [u8; 32] = {
-            let mut h = sha3::Keccak256::default();
-            h.write_all(&hash)
-                .context("failed to compute second hash")?;
-            h.finalize().into()
+impl EmitterRegistry {
+    /// Registers `address` on `chain` as an emitter of kind `kind`, replacing any previous
+    /// registration for that emitter.
+    pub fn register(&mut self, chain: Chain, address: Address, kind: EmitterKind) -> &mut Self {
+        let _ = self.emitters.insert((chain, address), kind);
+        self
+    }
+
+    /// Returns the kind of the emitter `address` on `chain`, if it is registered.
+    pub fn get(&self, chain: Chain, address: &Address) -> Option<EmitterKind> {
+        self.emitters.get(&(chain, *address)).copied()
+    }
+}
+
+/// A VAA payload whose type was determined from the emitter of the VAA and, for governance VAAs,
+/// the governance module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnyPayload {
+    CoreGovernance(crate::core::GovernancePacket),
+    TokenGovernance(crate::token::GovernancePacket),
+    NftGovernance(crate::nft::GovernancePacket),
+    RelayerGovernance(crate::relayer::GovernancePacket),
+    AccountantGovernance(crate::accountant::GovernancePacket),
+    NttAccountantGovernance(crate::ntt_accountant::GovernancePacket),
+    IbcReceiverGovernance(crate::ibc_receiver::GovernancePacket),
+    IbcTranslatorGovernance(crate::ibc_translator::GovernancePacket),
+    Token(crate::token::Message),
+    Nft(crate::nft::Message),
+
+    /// The payload was not emitted by a known emitter or could not be decoded as the expected
+    /// type.
+    Unknown(Box<RawMessage>),
+}
+
+impl AnyPayload {
+    /// Decodes `payload`, which was emitted by `emitter_address` on `emitter_chain`.
+    ///
+    /// Governance payloads are identified by their module. Other payloads are decoded according
+    /// to the kind of the emitter in `emitters`. Payloads that cannot be identified, or that fail
+    /// to decode as the identified type, are returned as `AnyPayload::Unknown`.
+    pub fn decode(
+        emitter_chain: Chain,
+        emitter_address: &Address,
+        payload: &[u8],
+        emitters: &EmitterRegistry,
+    ) -> AnyPayload {
+        fn parse<'a, T: Deserialize<'a>>(
+            payload: &'a [u8],
+            f: impl FnOnce(T) -> AnyPayload,
+        ) -> Option<AnyPayload> {
+            serde_wormhole::from_slice(payload).ok().map(f)
+        }
+
+        let decoded = if emitter_chain == Chain::Solana
+            && *emitter_address == crate::GOVERNANCE_EMITTER
+        {
+            match payload.get(..32) {
+                Some(m) if m == crate::core::MODULE => parse(payload, Self::CoreGovernance),
+                Some(m) if m == crate::token::MODULE => parse(payload, Self::TokenGovernance),
+                Some(m) if m == crate::nft::MODULE => parse(payload, Self::NftGovernance),
+                Some(m) if m == crate::relayer::MODULE => parse(payload, Self::RelayerGovernance),
+                Some(m) if m == crate::accountant::MODULE => {
+                    parse(payload, Self::AccountantGovernance)
+                }
+                Some(m) if m == crate::ntt_accountant::MODULE => {
+                    parse(payload, Self::NttAccountantGovernance)
+                }
+                Some(m) if m == crate::ibc_receiver::MODULE => {
+                    parse(payload, Self::IbcReceiverGovernance)
+                }
+                Some(m) if m == crate::ibc_translator::MODULE => {
+                    parse(payload, Self::IbcTranslatorGovernance)
+                }
+                _ => None,
+            }
+        } else {
+            match emitters.get(emitter_chain, emitter_address) {
+                Some(EmitterKind::TokenBridge) => parse(payload, Self::Token),
+                Some(EmitterKind::NftBridge) => parse(payload, Self::Nft),
+                None => None,
+            }
         };
 
-        Ok(Digest {
-            hash,
-            secp256k_hash,
-        })
+        decoded.unwrap_or_else(|| Self::Unknown(payload.to_vec().into()))
+    }
+}
+
+// Serializes the inner payload directly so that a `Vaa<AnyPayload>` has the same wire format as the
+// VAA it was parsed from.
+impl Serialize for AnyPayload {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Self::CoreGovernance(p) => p.serialize(serializer),
+            Self::TokenGovernance(p) => p.serialize(serializer),
+            Self::NftGovernance(p) => p.serialize(serializer),
+            Self::RelayerGovernance(p) => p.serialize(serializer),
+            Self::AccountantGovernance(p) => p.serialize(serializer),
+            Self::NttAccountantGovernance(p) => p.serialize(serializer),
+            Self::IbcReceiverGovernance(p) => p.serialize(serializer),
+            Self::IbcTranslatorGovernance(p) => p.serialize(serializer),
+            Self::Token(p) => p.serialize(serializer),
+            Self::Nft(p) => p.serialize(serializer),
+            Self::Unknown(p) => p.serialize(serializer),
+        }
     }
 }
 
+/// Parses a VAA in the wire format and decodes its payload with [`AnyPayload::decode`].
+///
+/// This only returns an error if the VAA itself is malformed. Payloads that cannot be decoded are
+/// returned as `AnyPayload::Unknown`.
+pub fn parse_any(
+    data: &[u8],
+    emitters: &EmitterRegistry,
+) -> Result<Vaa<AnyPayload>, serde_wormhole::Error> {
+    let (header, body) = serde_wormhole::from_slice::<(Header, Body<&RawMessage>)>(data)?;
+    let payload = AnyPayload::decode(
+        body.emitter_chain,
+        &body.emitter_address,
+        body.payload.get(),
+        emitters,
+    );
+
+    Ok((header, body.with_payload(payload)).into())
+}
+
 #[cfg(test)]
 mod test {
     use serde_wormhole::RawMessage;
@@ -325,6 +823,470 @@ mod test {
         assert_eq!(&buf[..], &serde_wormhole::to_vec(&vaa).unwrap());
     }
 
+    // A devnet relayer `RegisterChain` governance VAA signed by the devnet guardian.
+    const DEVNET_VAA: &str = "010000000001007349c3b9b892432e4d371b37085547eea089533667cd16c0237f4012bc4a011562731487b93c31e9a9b7ab37df1e39ecad3a0a249f019759ef4afe66500db5180165d760ad00000001000100000000000000000000000000000000000000000000000000000000000000040000000000000001010000000000000000000000000000000000576f726d686f6c6552656c61796572010000000200000000000000000000000053855d4b64e9a3cf59a84bc768ada716b5536bc5";
+
+    pub(super) const DEVNET_GUARDIAN: GuardianAddress = GuardianAddress([
+        0xbe, 0xfa, 0x42, 0x9d, 0x57, 0xcd, 0x18, 0xb7, 0xf8, 0xa4, 0xd9, 0x1a, 0x2d, 0xa9, 0xab,
+        0x4a, 0xf0, 0x5d, 0x0f, 0xbe,
+    ]);
+
+    pub(super) fn devnet_vaa() -> Vec<u8> {
+        (0..DEVNET_VAA.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&DEVNET_VAA[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn verify() {
+        let buf = devnet_vaa();
+        let (header, body) = serde_wormhole::from_slice::<(Header, &RawMessage)>(&buf).unwrap();
+
+        let d = header.verify(body, &[DEVNET_GUARDIAN]).unwrap();
+        assert_eq!(body_digest(body), d);
+    }
+
+    #[test]
+    fn verify_wrong_guardian() {
+        let buf = devnet_vaa();
+        let (header, body) = serde_wormhole::from_slice::<(Header, &RawMessage)>(&buf).unwrap();
+
+        let err = header
+            .verify(body, &[GuardianAddress([0x5a; 20])])
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            VerifyError::GuardianSignatureMismatch {
+                index: 0,
+                recovered: DEVNET_GUARDIAN,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn verify_modified_body() {
+        let mut buf = devnet_vaa();
+        *buf.last_mut().unwrap() ^= 0xff;
+        let (header, body) = serde_wormhole::from_slice::<(Header, &RawMessage)>(&buf).unwrap();
+
+        let err = header.verify(body, &[DEVNET_GUARDIAN]).unwrap_err();
+        assert!(matches!(
+            err,
+            VerifyError::GuardianSignatureMismatch { index: 0, .. }
+                | VerifyError::InvalidSignature(0)
+        ));
+    }
+
+    #[test]
+    fn verify_no_quorum() {
+        let buf = devnet_vaa();
+        let (header, body) = serde_wormhole::from_slice::<(Header, &RawMessage)>(&buf).unwrap();
+
+        let err = header
+            .verify(body, &[DEVNET_GUARDIAN, DEVNET_GUARDIAN])
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            VerifyError::NoQuorum {
+                signatures: 1,
+                quorum: 2
+            }
+        ));
+    }
+
+    #[test]
+    fn verify_duplicate_index() {
+        let buf = devnet_vaa();
+        let (mut header, body) = serde_wormhole::from_slice::<(Header, &RawMessage)>(&buf).unwrap();
+        header.signatures.push(header.signatures[0]);
+
+        let err = header
+            .verify(body, &[DEVNET_GUARDIAN, DEVNET_GUARDIAN])
+            .unwrap_err();
+        assert!(matches!(err, VerifyError::DuplicateGuardianIndex(0)));
+    }
+
+    #[test]
+    fn verify_index_order() {
+        let buf = devnet_vaa();
+        let (mut header, body) = serde_wormhole::from_slice::<(Header, &RawMessage)>(&buf).unwrap();
+        let mut first = header.signatures[0];
+        first.index = 1;
+        header.signatures.insert(0, first);
+
+        let err = header
+            .verify(body, &[DEVNET_GUARDIAN, DEVNET_GUARDIAN])
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            VerifyError::GuardianIndexOrder {
+                index: 0,
+                previous: 1
+            }
+        ));
+    }
+
+    #[test]
+    fn verify_index_out_of_range() {
+        let buf = devnet_vaa();
+        let (mut header, body) = serde_wormhole::from_slice::<(Header, &RawMessage)>(&buf).unwrap();
+        header.signatures[0].index = 1;
+
+        let err = header.verify(body, &[DEVNET_GUARDIAN]).unwrap_err();
+        assert!(matches!(
+            err,
+            VerifyError::GuardianIndexOutOfRange { index: 1, len: 1 }
+        ));
+    }
+
+    #[test]
+    fn build_devnet_vaa() {
+        let key = SigningKey::from_bytes(&[
+            0xcf, 0xb1, 0x23, 0x03, 0xa1, 0x9c, 0xde, 0x58, 0x0b, 0xb4, 0xdd, 0x77, 0x16, 0x39,
+            0xb0, 0xd2, 0x6b, 0xc6, 0x83, 0x53, 0x64, 0x55, 0x71, 0xa8, 0xcf, 0xf5, 0x16, 0xab,
+            0x2e, 0xe1, 0x13, 0xa0,
+        ])
+        .unwrap();
+        assert_eq!(DEVNET_GUARDIAN, GuardianAddress::from(&key.verifying_key()));
+
+        let buf = devnet_vaa();
+        let (header, body) =
+            serde_wormhole::from_slice::<(Header, Body<&RawMessage>)>(&buf).unwrap();
+
+        let (vaa, data) = VaaBuilder::new(body)
+            .guardian_set_index(header.guardian_set_index)
+            .guardian(0, key)
+            .build_bytes()
+            .unwrap();
+
+        assert_eq!(header.signatures, vaa.signatures);
+        assert_eq!(buf, data);
+    }
+
+    #[test]
+    fn build_and_verify() {
+        let keys = (1..=19u8)
+            .map(|i| SigningKey::from_bytes(&[i; 32]).unwrap())
+            .collect::<Vec<_>>();
+        let addrs = keys
+            .iter()
+            .map(|k| GuardianAddress::from(&k.verifying_key()))
+            .collect::<Vec<_>>();
+
+        let body = Body {
+            timestamp: 1_656_354_705,
+            nonce: 7,
+            emitter_chain: Chain::Ethereum,
+            emitter_address: Address([0xa3; 32]),
+            sequence: 42,
+            consistency_level: 1,
+            payload: "From: evm0\\nMsg: Hello World!",
+        };
+
+        // Sign with a quorum of guardians, added out of order.
+        let mut builder = VaaBuilder::new(body.clone()).guardian_set_index(4);
+        for i in (0..13).rev() {
+            builder = builder.guardian(i, keys[usize::from(i)].clone());
+        }
+        let (vaa, data) = builder.build_bytes().unwrap();
+
+        assert_eq!(1, vaa.version);
+        assert_eq!(4, vaa.guardian_set_index);
+        assert_eq!(13, vaa.signatures.len());
+        assert!(vaa.signatures.windows(2).all(|w| w[0].index < w[1].index));
+        assert_eq!(vaa, serde_wormhole::from_slice(&data).unwrap());
+
+        let (header, parsed) = serde_wormhole::from_slice::<(Header, &RawMessage)>(&data).unwrap();
+        let d = header.verify(parsed, &addrs).unwrap();
+        assert_eq!(body.digest().unwrap(), d);
+
+        // One signature short of quorum.
+        let (vaa, _) = VaaBuilder::new(body.clone())
+            .guardians(keys[..12].iter().cloned())
+            .build_bytes()
+            .unwrap();
+        let (header, _): (Header, Body<&str>) = vaa.into();
+        let data = serde_wormhole::to_vec(&body).unwrap();
+        assert!(matches!(
+            header.verify(&data, &addrs),
+            Err(VerifyError::NoQuorum {
+                signatures: 12,
+                quorum: 13
+            })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn batch() {
+        let keys = (1..=7u8)
+            .map(|i| SigningKey::from_bytes(&[i; 32]).unwrap())
+            .collect::<Vec<_>>();
+        let addrs = keys
+            .iter()
+            .map(|k| GuardianAddress::from(&k.verifying_key()))
+            .collect::<Vec<_>>();
+
+        let mut vaas = (0..16u64)
+            .map(|sequence| {
+                let body = Body {
+                    timestamp: 1_656_354_705,
+                    nonce: 0,
+                    emitter_chain: Chain::Solana,
+                    emitter_address: Address([0x17; 32]),
+                    sequence,
+                    consistency_level: 32,
+                    payload: sequence.to_be_bytes(),
+                };
+                let (vaa, _) = VaaBuilder::new(body)
+                    .guardians(keys.iter().cloned())
+                    .build_bytes()
+                    .unwrap();
+                let (header, body): (Header, Body<_>) = vaa.into();
+                (header, serde_wormhole::to_vec(&body).unwrap())
+            })
+            .collect::<Vec<_>>();
+
+        // Corrupt a couple of the VAAs.
+        vaas[3].0.signatures[2].signature[7] ^= 0x01;
+        vaas[11].0.signatures.truncate(4);
+
+        let cache = SignatureCache::default();
+        let results = verify_batch(&vaas, &addrs, &cache);
+        assert_eq!(vaas.len(), results.len());
+        for (i, (res, (_, body))) in results.iter().zip(&vaas).enumerate() {
+            match i {
+                3 => assert!(matches!(
+                    res,
+                    Err(VerifyError::GuardianSignatureMismatch { index: 2, .. })
+                        | Err(VerifyError::InvalidSignature(2))
+                )),
+                11 => assert!(matches!(res, Err(VerifyError::NoQuorum { .. }))),
+                _ => assert_eq!(&digest(body).unwrap(), res.as_ref().unwrap()),
+            }
+        }
+
+        // Verification stops at the first bad signature in VAA 3 and never recovers any key for
+        // VAA 11.
+        assert_eq!(14 * keys.len() + 3, cache.len());
+
+        // Verifying the same batch again should be served entirely from the cache.
+        let again = verify_batch(&vaas, &addrs, &cache);
+        assert_eq!(14 * keys.len() + 3, cache.len());
+        for (a, b) in results.iter().zip(&again) {
+            assert_eq!(a.as_ref().ok(), b.as_ref().ok());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn signature_cache_capacity() {
+        let buf = devnet_vaa();
+        let (header, body) = serde_wormhole::from_slice::<(Header, &RawMessage)>(&buf).unwrap();
+        let d = digest(body).unwrap();
+
+        let cache = SignatureCache::with_capacity(2);
+        let mut sig = header.signatures[0];
+        assert_eq!(DEVNET_GUARDIAN, cache.recover(&sig, &d).unwrap());
+        assert_eq!(1, cache.len());
+
+        // Recovering the same signature with a different index should hit the cache.
+        sig.index = 5;
+        assert_eq!(DEVNET_GUARDIAN, cache.recover(&sig, &d).unwrap());
+        assert_eq!(1, cache.len());
+
+        // Failed recoveries are cached too.
+        let mut bad = sig;
+        bad.signature[64] = 0xff;
+        assert!(matches!(
+            cache.recover(&bad, &d),
+            Err(VerifyError::InvalidSignature(5))
+        ));
+        assert_eq!(2, cache.len());
+
+        // The cache is full so adding another entry clears it first.
+        let d2 = digest(b"something else").unwrap();
+        let _ = cache.recover(&sig, &d2).unwrap();
+        assert_eq!(1, cache.len());
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn parse_any_governance() {
+        let buf = devnet_vaa();
+        let vaa = parse_any(&buf, &EmitterRegistry::default()).unwrap();
+        assert_eq!(
+            AnyPayload::RelayerGovernance(crate::relayer::GovernancePacket {
+                chain: Chain::Any,
+                action: crate::relayer::Action::RegisterChain {
+                    chain: Chain::Ethereum,
+                    emitter_address: Address([
+                        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                        0x53, 0x85, 0x5d, 0x4b, 0x64, 0xe9, 0xa3, 0xcf, 0x59, 0xa8, 0x4b, 0xc7,
+                        0x68, 0xad, 0xa7, 0x16, 0xb5, 0x53, 0x6b, 0xc5,
+                    ]),
+                },
+            }),
+            vaa.payload
+        );
+        assert_eq!(buf, serde_wormhole::to_vec(&vaa).unwrap());
+
+        let packets = [
+            AnyPayload::CoreGovernance(crate::core::GovernancePacket {
+                chain: Chain::Any,
+                action: crate::core::Action::SetFee {
+                    amount: crate::Amount([0x01; 32]),
+                },
+            }),
+            AnyPayload::TokenGovernance(crate::token::GovernancePacket {
+                chain: Chain::Sui,
+                action: crate::token::Action::ContractUpgrade {
+                    new_contract: Address([0x02; 32]),
+                },
+            }),
+            AnyPayload::NftGovernance(crate::nft::GovernancePacket {
+                chain: Chain::Aptos,
+                action: crate::nft::Action::ContractUpgrade {
+                    new_contract: Address([0x03; 32]),
+                },
+            }),
+            AnyPayload::IbcReceiverGovernance(crate::ibc_receiver::GovernancePacket {
+                chain: Chain::Wormchain,
+                action: crate::ibc_receiver::Action::UpdateChannelChain {
+                    channel_id: [0x04; 64],
+                    chain_id: Chain::Osmosis,
+                },
+            }),
+        ];
+
+        for p in packets {
+            let (_, data) = VaaBuilder::new(Body {
+                timestamp: 1,
+                nonce: 2,
+                emitter_chain: Chain::Solana,
+                emitter_address: GOVERNANCE_EMITTER,
+                sequence: 3,
+                consistency_level: 0,
+                payload: p.clone(),
+            })
+            .build_bytes()
+            .unwrap();
+
+            let vaa = parse_any(&data, &EmitterRegistry::default()).unwrap();
+            assert_eq!(p, vaa.payload);
+            assert_eq!(data, serde_wormhole::to_vec(&vaa).unwrap());
+        }
+    }
+
+    #[test]
+    fn parse_any_unknown_governance() {
+        // Unknown module.
+        let mut payload = [0u8; 40];
+        payload[28..32].copy_from_slice(b"Test");
+        let decoded = AnyPayload::decode(
+            Chain::Solana,
+            &GOVERNANCE_EMITTER,
+            &payload,
+            &EmitterRegistry::default(),
+        );
+        assert_eq!(AnyPayload::Unknown(payload.to_vec().into()), decoded);
+
+        // Known module, unknown action.
+        let mut payload = crate::token::MODULE.to_vec();
+        payload.extend_from_slice(&[0x7f, 0x00, 0x00]);
+        let decoded = AnyPayload::decode(
+            Chain::Solana,
+            &GOVERNANCE_EMITTER,
+            &payload,
+            &EmitterRegistry::default(),
+        );
+        assert_eq!(AnyPayload::Unknown(payload.into()), decoded);
+
+        // Governance module from a non-governance emitter.
+        let payload = serde_wormhole::to_vec(&crate::core::GovernancePacket {
+            chain: Chain::Any,
+            action: crate::core::Action::SetFee {
+                amount: crate::Amount([0x01; 32]),
+            },
+        })
+        .unwrap();
+        let decoded = AnyPayload::decode(
+            Chain::Ethereum,
+            &GOVERNANCE_EMITTER,
+            &payload,
+            &EmitterRegistry::default(),
+        );
+        assert_eq!(AnyPayload::Unknown(payload.into()), decoded);
+    }
+
+    #[test]
+    fn parse_any_emitters() {
+        let token_bridge = Address([0x7b; 32]);
+        let nft_bridge = Address([0x8c; 32]);
+
+        let mut emitters = EmitterRegistry::default();
+        let _ = emitters
+            .register(Chain::Ethereum, token_bridge, EmitterKind::TokenBridge)
+            .register(Chain::Ethereum, nft_bridge, EmitterKind::NftBridge);
+
+        let transfer = crate::token::Message::Transfer {
+            amount: crate::Amount([0x11; 32]),
+            token_address: Address([0x22; 32]),
+            token_chain: Chain::Solana,
+            recipient: Address([0x33; 32]),
+            recipient_chain: Chain::Bsc,
+            fee: crate::Amount([0x00; 32]),
+        };
+        let payload = serde_wormhole::to_vec(&transfer).unwrap();
+
+        assert_eq!(
+            AnyPayload::Token(transfer),
+            AnyPayload::decode(Chain::Ethereum, &token_bridge, &payload, &emitters)
+        );
+
+        // The same emitter address on a different chain is not registered.
+        assert_eq!(
+            AnyPayload::Unknown(payload.clone().into()),
+            AnyPayload::decode(Chain::Polygon, &token_bridge, &payload, &emitters)
+        );
+
+        // A token bridge payload from the NFT bridge doesn't decode.
+        assert_eq!(
+            AnyPayload::Unknown(payload.clone().into()),
+            AnyPayload::decode(Chain::Ethereum, &nft_bridge, &payload, &emitters)
+        );
+
+        let nft = crate::nft::Message::Transfer {
+            nft_address: Address([0x44; 32]),
+            nft_chain: Chain::Ethereum,
+            symbol: "TEST".into(),
+            name: "Test NFT".into(),
+            token_id: crate::nft::TokenId([0x55; 32]),
+            uri: "https://example.com/1".into(),
+            to: Address([0x66; 32]),
+            to_chain: Chain::Solana,
+        };
+        let (_, data) = VaaBuilder::new(Body {
+            timestamp: 1,
+            nonce: 2,
+            emitter_chain: Chain::Ethereum,
+            emitter_address: nft_bridge,
+            sequence: 3,
+            consistency_level: 1,
+            payload: nft.clone(),
+        })
+        .build_bytes()
+        .unwrap();
+
+        let vaa = parse_any(&data, &emitters).unwrap();
+        assert_eq!(AnyPayload::Nft(nft), vaa.payload);
+        assert_eq!(data, serde_wormhole::to_vec(&vaa).unwrap());
+    }
+
     #[test]
     fn digest_from_raw_parts() {
         let body = Body {
@@ -344,7 +1306,7 @@ mod test {
         let d1 = body.digest().unwrap();
 
         let data = serde_wormhole::to_vec(&body).unwrap();
-        let d2 = digest(&data).unwrap();
+        let d2 = body_digest(&data);
 
         assert_eq!(d1, d2);
 
@@ -374,7 +1336,7 @@ mod test {
             0xdc, 0x3f, 0x51, 0x1f,
         ];
 
-        assert_eq!(expected_digest, digest(&data).unwrap().secp256k_hash);
+        assert_eq!(expected_digest, body_digest(&data).secp256k_hash);
 
         let expected_body = Body {
             timestamp: 1,
@@ -0,0 +1,325 @@
+use core::{iter::FusedIterator, slice::ChunksExact};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+#[cfg(feature = "std")]
+use super::SignatureCache;
+use super::{body_digest, verify_signatures, Digest, Signature, VerifyError};
+use crate::{Address, Chain, GuardianAddress};
+
+// version (1) + guardian set index (4) + number of signatures (1)
+const HEADER_LEN: usize = 6;
+// guardian index (1) + signature (65)
+const SIGNATURE_LEN: usize = 66;
+// timestamp (4) + nonce (4) + emitter chain (2) + emitter address (32) + sequence (8) +
+// consistency level (1)
+const BODY_LEN: usize = 51;
+
+/// The reason a byte slice could not be viewed as a VAA.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum ViewError {
+    #[error("VAA is truncated: expected at least {expected} bytes, found {actual}")]
+    Truncated { expected: usize, actual: usize },
+}
+
+/// A borrowed, zero-copy view of a serialized VAA.
+///
+/// Unlike deserializing a [`Vaa`](super::Vaa), constructing a `VaaView` only checks that `data` is
+/// long enough to contain the header, the signatures, and the fixed-size fields of the body. All
+/// other accessors read directly from the underlying bytes without allocating.
+///
+/// # Examples
+///
+/// ```
+/// # fn example(data: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+/// use wormhole_sdk::vaa::VaaView;
+///
+/// let vaa = VaaView::new(data)?;
+/// let digest = vaa.digest();
+/// for sig in vaa.signatures() {
+///     println!("guardian {} signed {:?}", sig.index, digest.hash);
+/// }
+///
+/// let payload: &[u8] = vaa.payload();
+/// # let _ = payload;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VaaView<'a> {
+    data: &'a [u8],
+    body_offset: usize,
+}
+
+impl<'a> VaaView<'a> {
+    /// Creates a view of the VAA in `data`.
+    pub fn new(data: &'a [u8]) -> Result<Self, ViewError> {
+        let truncated = |expected| ViewError::Truncated {
+            expected,
+            actual: data.len(),
+        };
+
+        if data.len() < HEADER_LEN {
+            return Err(truncated(HEADER_LEN));
+        }
+
+        let body_offset = HEADER_LEN + usize::from(data[HEADER_LEN - 1]) * SIGNATURE_LEN;
+        if data.len() < body_offset + BODY_LEN {
+            return Err(truncated(body_offset + BODY_LEN));
+        }
+
+        Ok(Self { data, body_offset })
+    }
+
+    /// Returns the full serialized VAA.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    #[inline]
+    pub fn version(&self) -> u8 {
+        self.data[0]
+    }
+
+    #[inline]
+    pub fn guardian_set_index(&self) -> u32 {
+        u32::from_be_bytes(self.array(1))
+    }
+
+    /// Returns the number of signatures in the VAA.
+    #[inline]
+    pub fn num_signatures(&self) -> usize {
+        usize::from(self.data[HEADER_LEN - 1])
+    }
+
+    /// Returns an iterator over the signatures in the VAA.
+    #[inline]
+    pub fn signatures(&self) -> Signatures<'a> {
+        Signatures(self.data[HEADER_LEN..self.body_offset].chunks_exact(SIGNATURE_LEN))
+    }
+
+    /// Returns the serialized body of the VAA. This is the data that is signed by the guardians.
+    #[inline]
+    pub fn body(&self) -> &'a [u8] {
+        &self.data[self.body_offset..]
+    }
+
+    /// Seconds since UNIX epoch.
+    #[inline]
+    pub fn timestamp(&self) -> u32 {
+        u32::from_be_bytes(self.array(self.body_offset))
+    }
+
+    #[inline]
+    pub fn nonce(&self) -> u32 {
+        u32::from_be_bytes(self.array(self.body_offset + 4))
+    }
+
+    #[inline]
+    pub fn emitter_chain(&self) -> Chain {
+        u16::from_be_bytes(self.array(self.body_offset + 8)).into()
+    }
+
+    #[inline]
+    pub fn emitter_address(&self) -> Address {
+        Address(self.array(self.body_offset + 10))
+    }
+
+    #[inline]
+    pub fn sequence(&self) -> u64 {
+        u64::from_be_bytes(self.array(self.body_offset + 42))
+    }
+
+    #[inline]
+    pub fn consistency_level(&self) -> u8 {
+        self.data[self.body_offset + 50]
+    }
+
+    /// Returns the serialized payload of the VAA.
+    #[inline]
+    pub fn payload(&self) -> &'a [u8] {
+        &self.data[self.body_offset + BODY_LEN..]
+    }
+
+    /// Deserializes the payload of the VAA, borrowing from the underlying data where possible.
+    pub fn deserialize_payload<P: Deserialize<'a>>(&self) -> Result<P, serde_wormhole::Error> {
+        serde_wormhole::from_slice(self.payload())
+    }
+
+    /// Check if the VAA is a Governance VAA.
+    pub fn is_governance(&self) -> bool {
+        self.emitter_address() == crate::GOVERNANCE_EMITTER && self.emitter_chain() == Chain::Solana
+    }
+
+    /// Computes the digest of the VAA directly from the body bytes.
+    pub fn digest(&self) -> Digest {
+        body_digest(self.body())
+    }
+
+    /// Verifies that the signatures in this VAA were produced by a quorum of the guardians in
+    /// `addrs`. See [`Header::verify`](super::Header::verify) for details.
+    pub fn verify(&self, addrs: &[GuardianAddress]) -> Result<Digest, VerifyError> {
+        verify_signatures(self.signatures(), self.body(), addrs, Signature::recover)
+    }
+
+    /// Like [`VaaView::verify`] but memoizes recovered guardian addresses in `cache`.
+    #[cfg(feature = "std")]
+    pub fn verify_cached(
+        &self,
+        addrs: &[GuardianAddress],
+        cache: &SignatureCache,
+    ) -> Result<Digest, VerifyError> {
+        verify_signatures(self.signatures(), self.body(), addrs, |sig, d| {
+            cache.recover(sig, d)
+        })
+    }
+
+    fn array<const N: usize>(&self, offset: usize) -> [u8; N] {
+        let mut buf = [0u8; N];
+        buf.copy_from_slice(&self.data[offset..offset + N]);
+        buf
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for VaaView<'a> {
+    type Error = ViewError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::new(data)
+    }
+}
+
+/// An iterator over the signatures in a [`VaaView`].
+#[derive(Debug, Clone)]
+pub struct Signatures<'a>(ChunksExact<'a, u8>);
+
+impl Signatures<'_> {
+    fn parse(chunk: &[u8]) -> Signature {
+        let mut signature = [0u8; 65];
+        signature.copy_from_slice(&chunk[1..]);
+        Signature {
+            index: chunk[0],
+            signature,
+        }
+    }
+}
+
+impl Iterator for Signatures<'_> {
+    type Item = Signature;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(Self::parse)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Signatures<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(Self::parse)
+    }
+}
+
+impl ExactSizeIterator for Signatures<'_> {}
+
+impl FusedIterator for Signatures<'_> {}
+
+#[cfg(test)]
+mod test {
+    use serde_wormhole::RawMessage;
+
+    use super::*;
+    use crate::{
+        relayer,
+        vaa::{
+            test::{devnet_vaa, DEVNET_GUARDIAN},
+            Header, Vaa,
+        },
+    };
+
+    #[test]
+    fn matches_deserialized_vaa() {
+        let buf = devnet_vaa();
+        let view = VaaView::new(&buf).unwrap();
+        let vaa = serde_wormhole::from_slice::<Vaa<&RawMessage>>(&buf).unwrap();
+
+        assert_eq!(vaa.version, view.version());
+        assert_eq!(vaa.guardian_set_index, view.guardian_set_index());
+        assert_eq!(vaa.signatures.len(), view.num_signatures());
+        assert_eq!(vaa.signatures, view.signatures().collect::<Vec<_>>());
+        assert_eq!(vaa.timestamp, view.timestamp());
+        assert_eq!(vaa.nonce, view.nonce());
+        assert_eq!(vaa.emitter_chain, view.emitter_chain());
+        assert_eq!(vaa.emitter_address, view.emitter_address());
+        assert_eq!(vaa.sequence, view.sequence());
+        assert_eq!(vaa.consistency_level, view.consistency_level());
+        assert_eq!(vaa.payload.get(), view.payload());
+        assert_eq!(vaa.is_governance(), view.is_governance());
+
+        let (_, body) = <(Header, _)>::from(vaa);
+        assert_eq!(body.digest().unwrap(), view.digest());
+        assert_eq!(&serde_wormhole::to_vec(&body).unwrap()[..], view.body());
+
+        let packet: relayer::GovernancePacket = view.deserialize_payload().unwrap();
+        assert!(matches!(
+            packet.action,
+            relayer::Action::RegisterChain { .. }
+        ));
+    }
+
+    #[test]
+    fn verify() {
+        let buf = devnet_vaa();
+        let view = VaaView::try_from(&buf[..]).unwrap();
+
+        assert_eq!(view.digest(), view.verify(&[DEVNET_GUARDIAN]).unwrap());
+
+        #[cfg(feature = "std")]
+        {
+            let cache = SignatureCache::default();
+            assert_eq!(
+                view.digest(),
+                view.verify_cached(&[DEVNET_GUARDIAN], &cache).unwrap()
+            );
+            assert_eq!(1, cache.len());
+        }
+
+        let err = view
+            .verify(&[DEVNET_GUARDIAN, DEVNET_GUARDIAN])
+            .unwrap_err();
+        assert!(matches!(err, VerifyError::NoQuorum { .. }));
+    }
+
+    #[test]
+    fn truncated() {
+        let buf = devnet_vaa();
+
+        assert_eq!(
+            ViewError::Truncated {
+                expected: 6,
+                actual: 5
+            },
+            VaaView::new(&buf[..5]).unwrap_err()
+        );
+
+        // One signature plus the fixed-size body fields.
+        let min = 6 + 66 + 51;
+        assert_eq!(
+            ViewError::Truncated {
+                expected: min,
+                actual: min - 1
+            },
+            VaaView::new(&buf[..min - 1]).unwrap_err()
+        );
+
+        let view = VaaView::new(&buf[..min]).unwrap();
+        assert!(view.payload().is_empty());
+    }
+}
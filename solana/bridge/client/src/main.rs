@@ -310,7 +310,7 @@ fn main() {
                         .takes_value(true)
                         .index(3)
                         .required(true)
-                        .help("Consistency (Commitment) level at which the VAA should be produced <FINALIZED|CONFIRMED>"),
+                        .help("Consistency (Commitment) level at which the VAA should be produced <FINALIZED|CONFIRMED|INSTANT|SAFE>"),
                 )
                 .arg(
                     Arg::with_name("data")
@@ -403,6 +403,8 @@ fn main() {
                 match consistency_level.to_lowercase().as_str() {
                     "finalized" => bridge::types::ConsistencyLevel::Finalized,
                     "confirmed" => bridge::types::ConsistencyLevel::Confirmed,
+                    "instant" => bridge::types::ConsistencyLevel::Instant,
+                    "safe" => bridge::types::ConsistencyLevel::Safe,
                     _ => {
                         eprintln!("Invalid commitment level");
                         exit(1);
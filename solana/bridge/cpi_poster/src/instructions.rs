@@ -1,11 +1,29 @@
-use bridge::types::ConsistencyLevel;
+use bridge::{
+    accounts::{
+        Bridge,
+        EmitterFee,
+        EmitterFeeDerivationData,
+        FeeCollector,
+        Sequence,
+        SequenceDerivationData,
+    },
+    types::ConsistencyLevel,
+};
 use solana_program::{
     instruction::{
         AccountMeta,
         Instruction,
     },
     pubkey::Pubkey,
+    sysvar,
 };
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountState,
+    BorshSerialize,
+};
+
+use crate::PostMessageData;
 
 #[allow(clippy::too_many_arguments)]
 pub fn post_message(
@@ -18,17 +36,44 @@ pub fn post_message(
     payload: Vec<u8>,
     commitment: ConsistencyLevel,
 ) -> solitaire::Result<Instruction> {
-    let ix = bridge::instructions::post_message(
-        bridge_id, payer, emitter, message, nonce, payload, commitment,
-    )?;
-    let mut accounts = ix.accounts;
-    accounts.insert(7, AccountMeta::new_readonly(bridge_id, false));
-    let mut data = ix.data;
-    data[0] = 0;
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let fee_collector = FeeCollector::<'_>::key(None, &bridge_id);
+    let sequence = Sequence::<'_>::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter,
+        },
+        &bridge_id,
+    );
+    let emitter_fee = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData {
+            emitter_key: emitter,
+        },
+        &bridge_id,
+    );
 
     Ok(Instruction {
         program_id,
-        accounts,
-        data,
+        accounts: vec![
+            AccountMeta::new(bridge, false),
+            AccountMeta::new(message, true),
+            AccountMeta::new_readonly(emitter, true),
+            AccountMeta::new(sequence, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new(fee_collector, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(emitter_fee, false),
+        ],
+        data: (
+            crate::instruction::Instruction::PostMessage,
+            PostMessageData {
+                nonce,
+                payload,
+                consistency_level: commitment,
+            },
+        )
+            .try_to_vec()?,
     })
 }
@@ -1,5 +1,7 @@
 pub mod bridge;
 pub mod claim;
+pub mod close_age;
+pub mod emitter_fee;
 pub mod fee_collector;
 pub mod guardian_set;
 pub mod posted_message;
@@ -10,6 +12,8 @@ pub mod signature_set;
 pub use self::{
     bridge::*,
     claim::*,
+    close_age::*,
+    emitter_fee::*,
     fee_collector::*,
     guardian_set::*,
     posted_message::*,
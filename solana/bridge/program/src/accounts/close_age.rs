@@ -0,0 +1,33 @@
+//! The CloseAge account holds the minimum age a posted VAA must reach before it and its signature
+//! set can be closed without proof of consumption. It is set through governance, until then the
+//! default age applies.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solitaire::{
+    AccountOwner,
+    AccountState,
+    Data,
+    Derive,
+    Owned,
+};
+
+/// Minimum age, in seconds, of a posted VAA before it can be closed if governance never set one.
+pub const DEFAULT_CLOSE_AGE: u32 = 30 * 24 * 60 * 60;
+
+pub type CloseAge<'b, const State: AccountState> =
+    Derive<Data<'b, CloseAgeData, { State }>, "CloseAge">;
+
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize)]
+pub struct CloseAgeData {
+    /// Minimum age, in seconds, of a posted VAA before it can be closed.
+    pub age: u32,
+}
+
+impl Owned for CloseAgeData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
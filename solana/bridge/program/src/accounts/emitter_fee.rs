@@ -0,0 +1,45 @@
+//! Emitter fee accounts lower the global message fee for a single emitter. They are set through
+//! governance and are passed as an optional last account to instructions posting a message, an
+//! emitter that leaves its account out pays the global fee.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::pubkey::Pubkey;
+use solitaire::{
+    processors::seeded::Seeded,
+    AccountOwner,
+    AccountState,
+    Data,
+    Owned,
+};
+
+pub type EmitterFee<'b, const State: AccountState> = Data<'b, EmitterFeeData, { State }>;
+
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize)]
+pub struct EmitterFeeData {
+    /// Amount of lamports the emitter pays to post a message while the global fee is higher.
+    pub fee: u64,
+}
+
+pub struct EmitterFeeDerivationData {
+    pub emitter_key: Pubkey,
+}
+
+impl<'b, const State: AccountState> Seeded<&EmitterFeeDerivationData>
+    for EmitterFee<'b, { State }>
+{
+    fn seeds(data: &EmitterFeeDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            "EmitterFee".as_bytes().to_vec(),
+            data.emitter_key.to_bytes().to_vec(),
+        ]
+    }
+}
+
+impl Owned for EmitterFeeData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
@@ -3,6 +3,7 @@ use borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
+use solana_program::pubkey::Pubkey;
 use solitaire::{
     processors::seeded::Seeded,
     AccountOwner,
@@ -34,16 +35,26 @@ impl<'a, const State: AccountState> Seeded<&PostedVAADerivationData> for PostedV
     }
 }
 
-#[repr(transparent)]
 #[derive(Default)]
 pub struct PostedVAAData {
     pub message: MessageData,
+
+    /// Account that paid for posting the VAA. VAAs posted before the payer was recorded have no
+    /// room for it and read back the default key.
+    pub payer: Pubkey,
 }
 
 impl BorshSerialize for PostedVAAData {
     fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
         writer.write_all(b"vaa")?;
-        BorshSerialize::serialize(&self.message, writer)
+        BorshSerialize::serialize(&self.message, writer)?;
+
+        // Legacy accounts are too small to hold the payer, so don't write it back for them.
+        if self.payer != Pubkey::default() {
+            BorshSerialize::serialize(&self.payer, writer)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -59,10 +70,18 @@ impl BorshDeserialize for PostedVAAData {
         if !expected.contains(&magic) {
             return Err(Error::new(InvalidData, "Magic mismatch."));
         };
+        let is_vaa = magic == b"vaa";
         *buf = &buf[3..];
-        Ok(PostedVAAData {
-            message: <MessageData as BorshDeserialize>::deserialize(buf)?,
-        })
+        let message = <MessageData as BorshDeserialize>::deserialize(buf)?;
+
+        // Only posted VAAs record a payer, messages read through this type never do.
+        let payer = if is_vaa && !buf.is_empty() {
+            Pubkey::deserialize(buf)?
+        } else {
+            Pubkey::default()
+        };
+
+        Ok(PostedVAAData { message, payer })
     }
 }
 
@@ -84,6 +103,7 @@ impl Clone for PostedVAAData {
     fn clone(&self) -> Self {
         PostedVAAData {
             message: self.message.clone(),
+            payer: self.payer,
         }
     }
 }
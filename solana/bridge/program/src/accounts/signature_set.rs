@@ -4,16 +4,18 @@ use borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
+use solana_program::pubkey::Pubkey;
 use solitaire::{
     AccountOwner,
     AccountState,
     Data,
     Owned,
 };
+use std::io::Write;
 
 pub type SignatureSet<'b, const State: AccountState> = Data<'b, SignatureSetData, { State }>;
 
-#[derive(Default, BorshSerialize, BorshDeserialize)]
+#[derive(Default)]
 pub struct SignatureSetData {
     /// Signatures of validators
     pub signatures: Vec<bool>,
@@ -23,6 +25,45 @@ pub struct SignatureSetData {
 
     /// Index of the guardian set
     pub guardian_set_index: u32,
+
+    /// Account that paid for the creation of the signature set. Signature sets created before
+    /// the payer was recorded have no room for it and read back the default key.
+    pub payer: Pubkey,
+}
+
+impl BorshSerialize for SignatureSetData {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.signatures, writer)?;
+        BorshSerialize::serialize(&self.hash, writer)?;
+        BorshSerialize::serialize(&self.guardian_set_index, writer)?;
+
+        // Legacy accounts are too small to hold the payer, so don't write it back for them.
+        if self.payer != Pubkey::default() {
+            BorshSerialize::serialize(&self.payer, writer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for SignatureSetData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let signatures = <Vec<bool>>::deserialize(buf)?;
+        let hash = <[u8; 32]>::deserialize(buf)?;
+        let guardian_set_index = u32::deserialize(buf)?;
+        let payer = if buf.is_empty() {
+            Pubkey::default()
+        } else {
+            Pubkey::deserialize(buf)?
+        };
+
+        Ok(SignatureSetData {
+            signatures,
+            hash,
+            guardian_set_index,
+            payer,
+        })
+    }
 }
 
 impl Owned for SignatureSetData {
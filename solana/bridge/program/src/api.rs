@@ -1,9 +1,11 @@
+pub mod close_posted_vaa;
 pub mod governance;
 pub mod initialize;
 pub mod post_message;
 pub mod post_vaa;
 pub mod verify_signature;
 
+pub use close_posted_vaa::*;
 pub use governance::*;
 pub use initialize::*;
 pub use post_message::*;
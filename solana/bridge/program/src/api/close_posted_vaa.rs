@@ -0,0 +1,174 @@
+use solitaire::*;
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::{
+    pubkey::Pubkey,
+    system_program,
+    sysvar::clock::Clock,
+};
+
+use crate::{
+    accounts::{
+        Bridge,
+        Claim,
+        ClaimData,
+        ClaimDerivationData,
+        CloseAge,
+        FeeCollector,
+        PostedVAA,
+        PostedVAADerivationData,
+        SignatureSet,
+        DEFAULT_CLOSE_AGE,
+    },
+    error::Error::{
+        InvalidClaim,
+        MathOverflow,
+        PayerMismatch,
+        PostedVAANotClosable,
+        SignatureSetMismatch,
+    },
+    PostedVAAData,
+};
+use solitaire::processors::seeded::Seeded;
+
+/// Position of the optional claim account, passed after all accounts of `ClosePostedVAA`.
+pub const CLOSE_POSTED_VAA_CLAIM_INDEX: usize = 8;
+
+#[derive(FromAccounts)]
+pub struct ClosePostedVAA<'b> {
+    /// Account that paid for the posted VAA, receives its lamports. VAAs posted before the payer
+    /// was recorded are refunded to the fee collector instead.
+    pub posted_vaa_payer: Mut<Info<'b>>,
+
+    /// Account that paid for the signature set, receives its lamports. Signature sets created
+    /// before the payer was recorded are refunded to the fee collector instead.
+    pub signature_set_payer: Mut<Info<'b>>,
+
+    /// Posted VAA to close.
+    pub posted_vaa: Mut<PostedVAA<'b, { AccountState::Initialized }>>,
+
+    /// Signature set the VAA was posted with, closed along with it.
+    pub signature_set: Mut<SignatureSet<'b, { AccountState::Initialized }>>,
+
+    /// Minimum age of the VAA before it can be closed, if governance has set one.
+    pub close_age: CloseAge<'b, { AccountState::MaybeInitialized }>,
+
+    /// Bridge config, tracks lamports refunded to the fee collector.
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Account collecting tx fees, receives the lamports of legacy accounts.
+    pub fee_collector: Mut<FeeCollector<'b>>,
+
+    /// Clock used to determine the age of the VAA.
+    pub clock: Sysvar<'b, Clock>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct ClosePostedVAAData {}
+
+pub fn close_posted_vaa(
+    ctx: &ExecutionContext,
+    accs: &mut ClosePostedVAA,
+    _data: ClosePostedVAAData,
+) -> Result<()> {
+    accs.posted_vaa.verify_derivation(
+        ctx.program_id,
+        &PostedVAADerivationData {
+            payload_hash: accs.signature_set.hash.to_vec(),
+        },
+    )?;
+
+    if accs.posted_vaa.vaa_signature_account != *accs.signature_set.info().key {
+        return Err(SignatureSetMismatch.into());
+    }
+
+    // Lamports of accounts created before their payer was recorded go to the fee collector, as
+    // there is no way to know who they belong to.
+    let fee_collector = *accs.fee_collector.key;
+    let refund_to = |payer: Pubkey| match payer == Pubkey::default() {
+        true => fee_collector,
+        false => payer,
+    };
+    if *accs.posted_vaa_payer.key != refund_to(accs.posted_vaa.payer)
+        || *accs.signature_set_payer.key != refund_to(accs.signature_set.payer)
+    {
+        return Err(PayerMismatch.into());
+    }
+
+    // Once a program has consumed the VAA, whoever paid to post it may close it right away. It is
+    // left to them as closing it early could force them to post it again.
+    match ctx.accounts.get(CLOSE_POSTED_VAA_CLAIM_INDEX) {
+        Some(claim) => {
+            if accs.posted_vaa.payer == Pubkey::default() || !accs.posted_vaa_payer.is_signer {
+                return Err(PostedVAANotClosable.into());
+            }
+            verify_claim(claim, &accs.posted_vaa)?;
+        }
+        None => {
+            let close_age = match accs.close_age.is_initialized() {
+                true => accs.close_age.age,
+                false => DEFAULT_CLOSE_AGE,
+            };
+            if accs.clock.unix_timestamp - (accs.posted_vaa.vaa_time as i64) < close_age as i64 {
+                return Err(PostedVAANotClosable.into());
+            }
+        }
+    }
+
+    let accounts = [
+        (accs.posted_vaa.info(), &*accs.posted_vaa_payer),
+        (accs.signature_set.info(), &*accs.signature_set_payer),
+    ];
+    for (account, recipient) in accounts {
+        // Lamports moved into the fee collector are not message fees, so they must not be counted
+        // as paid by the next message posted.
+        if *recipient.key == fee_collector {
+            accs.bridge.last_lamports = accs
+                .bridge
+                .last_lamports
+                .checked_add(account.lamports())
+                .ok_or(MathOverflow)?;
+        }
+        close_account(account, recipient)?;
+    }
+
+    Ok(())
+}
+
+/// Fail unless `claim` is the initialized claim a program created when consuming the VAA.
+fn verify_claim(claim: &Info, vaa: &PostedVAAData) -> Result<()> {
+    let derived = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: vaa.emitter_address,
+            emitter_chain: vaa.emitter_chain,
+            sequence: vaa.sequence,
+        },
+        claim.owner,
+    );
+    if derived != *claim.key {
+        return Err(InvalidClaim.into());
+    }
+
+    match ClaimData::try_from_slice(&claim.data.borrow()) {
+        Ok(ClaimData { claimed: true }) => Ok(()),
+        _ => Err(InvalidClaim.into()),
+    }
+}
+
+/// Move all lamports of `account` to `recipient` and hand the emptied account back to the system
+/// program, which also keeps it from being persisted once the instruction completes.
+pub(crate) fn close_account(account: &Info, recipient: &Info) -> Result<()> {
+    let lamports = recipient
+        .lamports()
+        .checked_add(account.lamports())
+        .ok_or(MathOverflow)?;
+    **recipient.lamports.borrow_mut() = lamports;
+    **account.lamports.borrow_mut() = 0;
+    account.realloc(0, false)?;
+    account.assign(&system_program::id());
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use primitive_types::U256;
 use solana_program::{
     program::invoke_signed,
     pubkey::Pubkey,
@@ -19,9 +20,13 @@ use crate::{
             Claim,
         },
         Bridge,
+        CloseAge,
+        EmitterFee,
+        EmitterFeeDerivationData,
         GuardianSet,
         GuardianSetDerivationData,
     },
+    api::close_posted_vaa::close_account,
     error::Error::{
         InvalidFeeRecipient,
         InvalidGovernanceKey,
@@ -30,6 +35,8 @@ use crate::{
     },
     types::{
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetCloseAge,
+        GovernancePayloadSetEmitterFee,
         GovernancePayloadSetMessageFee,
         GovernancePayloadTransferFees,
         GovernancePayloadUpgrade,
@@ -223,6 +230,89 @@ pub fn set_fees(ctx: &ExecutionContext, accs: &mut SetFees, _data: SetFeesData)
     Ok(())
 }
 
+#[derive(FromAccounts)]
+pub struct SetEmitterFee<'b> {
+    /// Payer for account creation (vaa-claim, emitter fee)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetEmitterFee>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Fee override for the emitter named in the VAA.
+    pub emitter_fee: Mut<EmitterFee<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetEmitterFeeData {}
+
+pub fn set_emitter_fee(
+    ctx: &ExecutionContext,
+    accs: &mut SetEmitterFee,
+    _data: SetEmitterFeeData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let derivation_data = EmitterFeeDerivationData {
+        emitter_key: accs.vaa.emitter,
+    };
+    accs.emitter_fee
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // The maximum fee removes the override, returning the emitter to the global fee.
+    if accs.vaa.fee == U256::max_value() {
+        if accs.emitter_fee.is_initialized() {
+            close_account(accs.emitter_fee.info(), &accs.payer)?;
+        }
+        return Ok(());
+    }
+
+    if !accs.emitter_fee.is_initialized() {
+        accs.emitter_fee
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+    }
+
+    accs.emitter_fee.fee = accs.vaa.fee.as_u64();
+    Ok(())
+}
+
+#[derive(FromAccounts)]
+pub struct SetCloseAge<'b> {
+    /// Payer for account creation (vaa-claim, close age)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Governance VAA
+    pub vaa: PayloadMessage<'b, GovernancePayloadSetCloseAge>,
+
+    /// An Uninitialized Claim account to consume the VAA.
+    pub claim: Mut<Claim<'b>>,
+
+    /// Minimum age of posted VAAs before they can be closed.
+    pub close_age: Mut<CloseAge<'b, { AccountState::MaybeInitialized }>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct SetCloseAgeData {}
+
+pub fn set_close_age(
+    ctx: &ExecutionContext,
+    accs: &mut SetCloseAge,
+    _data: SetCloseAgeData,
+) -> Result<()> {
+    verify_governance(&accs.vaa)?;
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    if !accs.close_age.is_initialized() {
+        accs.close_age.create(ctx, accs.payer.key, Exempt)?;
+    }
+
+    accs.close_age.age = accs.vaa.age;
+    Ok(())
+}
+
 #[derive(FromAccounts)]
 pub struct TransferFees<'b> {
     /// Payer for account creation (vaa-claim)
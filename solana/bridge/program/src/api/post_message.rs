@@ -1,6 +1,9 @@
 use crate::{
     accounts::{
         Bridge,
+        EmitterFee,
+        EmitterFeeData,
+        EmitterFeeDerivationData,
         FeeCollector,
         PostedMessage,
         PostedMessageUnreliable,
@@ -20,8 +23,14 @@ use crate::{
 };
 use solana_program::{
     msg,
+    program::invoke,
     pubkey::Pubkey,
-    sysvar::clock::Clock,
+    rent::Rent,
+    system_instruction,
+    sysvar::{
+        clock::Clock,
+        Sysvar as SolanaSysvar,
+    },
 };
 use solitaire::{
     processors::seeded::Seeded,
@@ -78,6 +87,18 @@ pub struct PostMessageUnreliable<'b> {
     pub clock: Sysvar<'b, Clock>,
 }
 
+#[derive(FromAccounts)]
+pub struct ResizeMessageUnreliable<'b> {
+    /// Message account to resize
+    pub message: Mut<PostedMessageUnreliable<'b, { AccountState::Initialized }>>,
+
+    /// Emitter that owns the message account
+    pub emitter: Signer<MaybeMut<Info<'b>>>,
+
+    /// Payer covering the rent difference, receives the excess when shrinking
+    pub payer: Mut<Signer<Info<'b>>>,
+}
+
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct PostMessageData {
     /// Unique nonce for this message
@@ -134,7 +155,8 @@ pub fn post_message_unreliable(
     accs: &mut PostMessageUnreliable,
     data: PostMessageData,
 ) -> Result<()> {
-    // Accounts can't be resized so the payload sizes need to match
+    // Accounts are only resized through `resize_message_unreliable` so the payload sizes need to
+    // match
     if accs.message.is_initialized() && accs.message.payload.len() != data.payload.len() {
         return Err(InvalidPayloadLength.into());
     }
@@ -174,6 +196,54 @@ pub fn post_message_unreliable(
     Ok(())
 }
 
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ResizeMessageUnreliableData {
+    /// Payload length the message account should be able to hold
+    pub payload_len: u32,
+}
+
+/// Resize a message account used with `post_message_unreliable` so that it can hold payloads of a
+/// different length. The current payload is truncated or zero-padded to the new length, so the
+/// next message posted to the account must have exactly `payload_len` bytes. The payer tops up the
+/// account to stay rent exempt when it grows and is refunded the excess when it shrinks.
+pub fn resize_message_unreliable(
+    ctx: &ExecutionContext,
+    accs: &mut ResizeMessageUnreliable,
+    data: ResizeMessageUnreliableData,
+) -> Result<()> {
+    // Only the emitter may resize its message account
+    if accs.emitter.key.to_bytes() != accs.message.emitter_address {
+        return Err(EmitterChanged.into());
+    }
+
+    accs.message.payload.resize(data.payload_len as usize, 0);
+    let size = accs.message.size();
+    let target_rent = Rent::get()?.minimum_balance(size);
+    let lamports = accs.message.info().lamports();
+
+    if lamports < target_rent {
+        let transfer_ix = system_instruction::transfer(
+            accs.payer.key,
+            accs.message.info().key,
+            target_rent - lamports,
+        );
+        invoke(&transfer_ix, ctx.accounts)?;
+    } else {
+        let refund = accs
+            .payer
+            .lamports()
+            .checked_add(lamports - target_rent)
+            .ok_or(MathOverflow)?;
+        **accs.payer.lamports.borrow_mut() = refund;
+        **accs.message.info().lamports.borrow_mut() = target_rent;
+    }
+
+    // The resized payload is written back when the account is persisted.
+    accs.message.info().realloc(size, false)?;
+
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 fn post_message_internal<'b>(
     ctx: &ExecutionContext,
@@ -196,7 +266,23 @@ fn post_message_internal<'b>(
     };
     sequence.verify_derivation(ctx.program_id, &sequence_derivation)?;
 
-    let fee = bridge.config.fee;
+    // Emitters with a fee override set by governance pay that fee when it is lower than the global
+    // one. The override is an optional account passed after all others, so that instructions built
+    // before overrides existed keep working and pay the global fee.
+    let emitter_fee = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData {
+            emitter_key: *emitter.key,
+        },
+        ctx.program_id,
+    );
+    let fee = match ctx.accounts.iter().find(|a| *a.key == emitter_fee) {
+        Some(account) if account.owner == ctx.program_id && !account.data_is_empty() => {
+            let emitter_fee = EmitterFeeData::try_from_slice(&account.data.borrow())?;
+            emitter_fee.fee.min(bridge.config.fee)
+        }
+        _ => bridge.config.fee,
+    };
+
     // Fee handling, checking previously known balance allows us to not care who is the payer of
     // this submission.
     if fee_collector
@@ -232,10 +318,7 @@ fn post_message_internal<'b>(
     message.nonce = data.nonce;
     message.payload = data.payload;
     message.sequence = sequence.sequence;
-    message.consistency_level = match data.consistency_level {
-        ConsistencyLevel::Confirmed => 1,
-        ConsistencyLevel::Finalized => 32,
-    };
+    message.consistency_level = data.consistency_level.into();
 
     // Bump sequence number
     trace!("New Sequence: {}", sequence.sequence + 1);
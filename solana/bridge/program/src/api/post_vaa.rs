@@ -147,6 +147,7 @@ pub fn post_vaa(ctx: &ExecutionContext, accs: &mut PostVAA, vaa: PostVAAData) ->
     accs.message.vaa_version = vaa.version;
     accs.message.vaa_time = vaa.timestamp;
     accs.message.vaa_signature_account = *accs.signature_set.info().key;
+    accs.message.payer = *accs.payer.key;
     accs.message
         .create(&msg_derivation, ctx, accs.payer.key, Exempt)?;
 
@@ -172,6 +172,7 @@ pub fn verify_signatures(
         accs.signature_set.signatures = vec![false; accs.guardian_set.keys.len()];
         accs.signature_set.guardian_set_index = accs.guardian_set.index;
         accs.signature_set.hash = msg_hash;
+        accs.signature_set.payer = *accs.payer.key;
 
         let size = accs.signature_set.size();
         create_account(
@@ -26,6 +26,10 @@ pub enum Error {
     VAAInvalid,
     InvalidPayloadLength,
     EmitterChanged,
+    PayerMismatch,
+    SignatureSetMismatch,
+    PostedVAANotClosable,
+    InvalidClaim,
 }
 
 /// Errors thrown by the program will bubble up to the solitaire wrapper, which needs a way to
@@ -27,6 +27,9 @@ use crate::{
         Bridge,
         Claim,
         ClaimDerivationData,
+        CloseAge,
+        EmitterFee,
+        EmitterFeeDerivationData,
         FeeCollector,
         GuardianSet,
         GuardianSetDerivationData,
@@ -36,9 +39,13 @@ use crate::{
         SequenceDerivationData,
     },
     types::ConsistencyLevel,
+    ClosePostedVAAData,
     InitializeData,
     PostMessageData,
     PostVAAData,
+    ResizeMessageUnreliableData,
+    SetCloseAgeData,
+    SetEmitterFeeData,
     SetFeesData,
     TransferFeesData,
     UpgradeContractData,
@@ -101,6 +108,12 @@ pub fn post_message(
         },
         &program_id,
     );
+    let emitter_fee = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData {
+            emitter_key: emitter,
+        },
+        &program_id,
+    );
 
     Ok(Instruction {
         program_id,
@@ -114,6 +127,7 @@ pub fn post_message(
             AccountMeta::new_readonly(sysvar::clock::id(), false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(emitter_fee, false),
         ],
         data: (
             crate::instruction::Instruction::PostMessage,
@@ -144,6 +158,12 @@ pub fn post_message_unreliable(
         },
         &program_id,
     );
+    let emitter_fee = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData {
+            emitter_key: emitter,
+        },
+        &program_id,
+    );
 
     Ok(Instruction {
         program_id,
@@ -157,6 +177,7 @@ pub fn post_message_unreliable(
             AccountMeta::new_readonly(sysvar::clock::id(), false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(emitter_fee, false),
         ],
         data: (
             crate::instruction::Instruction::PostMessageUnreliable,
@@ -170,6 +191,29 @@ pub fn post_message_unreliable(
     })
 }
 
+pub fn resize_message_unreliable(
+    program_id: Pubkey,
+    payer: Pubkey,
+    emitter: Pubkey,
+    message: Pubkey,
+    payload_len: u32,
+) -> solitaire::Result<Instruction> {
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(message, false),
+            AccountMeta::new_readonly(emitter, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::ResizeMessageUnreliable,
+            ResizeMessageUnreliableData { payload_len },
+        )
+            .try_to_vec()?,
+    })
+}
+
 pub fn verify_signatures(
     program_id: Pubkey,
     payer: Pubkey,
@@ -380,6 +424,49 @@ pub fn set_fees(
     }
 }
 
+pub fn set_emitter_fee(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    fee_emitter: Pubkey,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let emitter_fee = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData {
+            emitter_key: fee_emitter,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(emitter_fee, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetEmitterFee,
+            SetEmitterFeeData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 pub fn transfer_fees(
     program_id: Pubkey,
     payer: Pubkey,
@@ -423,6 +510,84 @@ pub fn transfer_fees(
     }
 }
 
+/// Close a posted VAA and its signature set, refunding each to the account that paid for it. If
+/// `claim` is set the VAA may be closed before it reaches the close age, and `posted_vaa_payer`
+/// must sign.
+pub fn close_posted_vaa(
+    program_id: Pubkey,
+    posted_vaa_payer: Pubkey,
+    signature_set_payer: Pubkey,
+    posted_vaa: Pubkey,
+    signature_set: Pubkey,
+    claim: Option<Pubkey>,
+) -> Instruction {
+    let close_age = CloseAge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let fee_collector = FeeCollector::<'_>::key(None, &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(posted_vaa_payer, claim.is_some()),
+        AccountMeta::new(signature_set_payer, false),
+        AccountMeta::new(posted_vaa, false),
+        AccountMeta::new(signature_set, false),
+        AccountMeta::new_readonly(close_age, false),
+        AccountMeta::new(bridge, false),
+        AccountMeta::new(fee_collector, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    if let Some(claim) = claim {
+        accounts.push(AccountMeta::new_readonly(claim, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: (
+            crate::instruction::Instruction::ClosePostedVAA,
+            ClosePostedVAAData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_close_age(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+) -> Instruction {
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain: CHAIN_ID_GOVERANCE,
+            sequence,
+        },
+        &program_id,
+    );
+    let close_age = CloseAge::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(close_age, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetCloseAge,
+            SetCloseAgeData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 // Convert a full VAA structure into the serialization of its unique components, this structure is
 // what is hashed and verified by Guardians.
 pub fn serialize_vaa(vaa: &PostVAAData) -> Vec<u8> {
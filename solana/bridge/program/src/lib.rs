@@ -27,6 +27,11 @@ pub use accounts::{
     Claim,
     ClaimData,
     ClaimDerivationData,
+    CloseAge,
+    CloseAgeData,
+    EmitterFee,
+    EmitterFeeData,
+    EmitterFeeDerivationData,
     FeeCollector,
     GuardianSet,
     GuardianSetData,
@@ -48,15 +53,21 @@ pub use accounts::{
 pub mod api;
 
 pub use api::{
+    close_posted_vaa,
     initialize,
     post_message,
     post_message_unreliable,
     post_vaa,
+    resize_message_unreliable,
+    set_close_age,
+    set_emitter_fee,
     set_fees,
     transfer_fees,
     upgrade_contract,
     upgrade_guardian_set,
     verify_signatures,
+    ClosePostedVAA,
+    ClosePostedVAAData,
     Initialize,
     InitializeData,
     PostMessage,
@@ -64,6 +75,12 @@ pub use api::{
     PostMessageUnreliable,
     PostVAA,
     PostVAAData,
+    ResizeMessageUnreliable,
+    ResizeMessageUnreliableData,
+    SetCloseAge,
+    SetCloseAgeData,
+    SetEmitterFee,
+    SetEmitterFeeData,
     SetFees,
     SetFeesData,
     Signature,
@@ -100,4 +117,8 @@ solitaire! {
     UpgradeGuardianSet => upgrade_guardian_set,
     VerifySignatures   => verify_signatures,
     PostMessageUnreliable        => post_message_unreliable,
+    ClosePostedVAA               => close_posted_vaa,
+    ResizeMessageUnreliable      => resize_message_unreliable,
+    SetEmitterFee                => set_emitter_fee,
+    SetCloseAge                  => set_close_age,
 }
@@ -37,11 +37,35 @@ use std::{
 /// Type representing an Ethereum style public key for Guardians.
 pub type GuardianPublicKey = [u8; 20];
 
+/// Commitment level at which the guardians should observe a message. New levels must only ever be
+/// appended, as the Borsh encoding of a variant is its index and existing clients and the guardian
+/// watcher rely on it.
+///
+/// There is deliberately no level for a custom number of blocks. Guardians observe Solana messages
+/// at either confirmed or finalized commitment and have no way to wait for a number of slots, so
+/// such a level would be signed into VAAs without ever being honoured.
 #[repr(u8)]
 #[derive(BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize)]
 pub enum ConsistencyLevel {
     Confirmed,
     Finalized,
+    /// Publish the message as soon as possible, which guardians on Solana treat as `Confirmed`.
+    Instant,
+    /// Publish the message once its block is considered safe, which guardians on Solana treat as
+    /// `Confirmed`.
+    Safe,
+}
+
+/// The consistency level that is recorded in the posted message and signed into the VAA.
+impl From<ConsistencyLevel> for u8 {
+    fn from(level: ConsistencyLevel) -> u8 {
+        match level {
+            ConsistencyLevel::Confirmed => 1,
+            ConsistencyLevel::Finalized => 32,
+            ConsistencyLevel::Instant => 200,
+            ConsistencyLevel::Safe => 201,
+        }
+    }
 }
 
 pub struct GovernancePayloadUpgrade {
@@ -233,3 +257,95 @@ impl SerializeGovernancePayload for GovernancePayloadTransferFees {
 
 impl DeserializeGovernancePayload for GovernancePayloadTransferFees {
 }
+
+pub struct GovernancePayloadSetEmitterFee {
+    // Emitter the fee applies to
+    pub emitter: Pubkey,
+
+    // New fee in lamports, the maximum value removes the override
+    pub fee: U256,
+}
+
+impl SerializePayload for GovernancePayloadSetEmitterFee {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        let mut fee_data = [0u8; 32];
+        self.fee.to_big_endian(&mut fee_data);
+        v.write_all(&self.emitter.to_bytes())?;
+        v.write_all(&fee_data[..])?;
+
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetEmitterFee
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let mut emitter = [0u8; 32];
+        c.read_exact(&mut emitter)?;
+
+        let mut fee_data: [u8; 32] = [0; 32];
+        c.read_exact(&mut fee_data)?;
+        let fee = U256::from_big_endian(&fee_data);
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetEmitterFee {
+            emitter: Pubkey::new_from_array(emitter),
+            fee,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetEmitterFee {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 6;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetEmitterFee {
+}
+
+pub struct GovernancePayloadSetCloseAge {
+    // Minimum age in seconds before a posted VAA can be closed
+    pub age: u32,
+}
+
+impl SerializePayload for GovernancePayloadSetCloseAge {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        use byteorder::WriteBytesExt;
+        v.write_u32::<BigEndian>(self.age)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetCloseAge
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let age = c.read_u32::<BigEndian>()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetCloseAge { age })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetCloseAge {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 7;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetCloseAge {
+}
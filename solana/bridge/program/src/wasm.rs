@@ -55,6 +55,16 @@ use crate::{
 use byteorder::LittleEndian;
 use wasm_bindgen::prelude::*;
 
+fn parse_consistency_level(consistency: &str) -> Result<ConsistencyLevel, JsValue> {
+    match consistency {
+        "CONFIRMED" => Ok(ConsistencyLevel::Confirmed),
+        "FINALIZED" => Ok(ConsistencyLevel::Finalized),
+        "INSTANT" => Ok(ConsistencyLevel::Instant),
+        "SAFE" => Ok(ConsistencyLevel::Safe),
+        _ => Err(JsValue::from_str("invalid consistency level")),
+    }
+}
+
 #[wasm_bindgen]
 pub fn post_message_ix(
     program_id: String,
@@ -64,12 +74,8 @@ pub fn post_message_ix(
     nonce: u32,
     msg: Vec<u8>,
     consistency: String,
-) -> JsValue {
-    let consistency_level = match consistency.as_str() {
-        "CONFIRMED" => ConsistencyLevel::Confirmed,
-        "FINALIZED" => ConsistencyLevel::Finalized,
-        _ => panic!("invalid consistency level"),
-    };
+) -> Result<JsValue, JsValue> {
+    let consistency_level = parse_consistency_level(&consistency)?;
     let ix = post_message(
         Pubkey::from_str(program_id.as_str()).unwrap(),
         Pubkey::from_str(payer.as_str()).unwrap(),
@@ -80,7 +86,7 @@ pub fn post_message_ix(
         consistency_level,
     )
     .unwrap();
-    return JsValue::from_serde(&ix).unwrap();
+    Ok(JsValue::from_serde(&ix).unwrap())
 }
 
 #[wasm_bindgen]
@@ -92,12 +98,8 @@ pub fn post_message_unreliable_ix(
     nonce: u32,
     msg: Vec<u8>,
     consistency: String,
-) -> JsValue {
-    let consistency_level = match consistency.as_str() {
-        "CONFIRMED" => ConsistencyLevel::Confirmed,
-        "FINALIZED" => ConsistencyLevel::Finalized,
-        _ => panic!("invalid consistency level"),
-    };
+) -> Result<JsValue, JsValue> {
+    let consistency_level = parse_consistency_level(&consistency)?;
     let ix = post_message_unreliable(
         Pubkey::from_str(program_id.as_str()).unwrap(),
         Pubkey::from_str(payer.as_str()).unwrap(),
@@ -108,7 +110,7 @@ pub fn post_message_unreliable_ix(
         consistency_level,
     )
     .unwrap();
-    return JsValue::from_serde(&ix).unwrap();
+    Ok(JsValue::from_serde(&ix).unwrap())
 }
 
 #[wasm_bindgen]
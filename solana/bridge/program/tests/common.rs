@@ -20,6 +20,7 @@ use solana_program_test::{
     ProgramTest,
 };
 use solana_sdk::{
+    account::Account,
     commitment_config::CommitmentLevel,
     secp256k1_instruction::new_secp256k1_instruction,
     signature::{
@@ -75,17 +76,33 @@ mod helpers {
     /// Initialize the test environment, spins up a solana-test-validator in the background so that
     /// each test has a fresh environment to work within.
     pub async fn setup() -> (BanksClient, Keypair, Pubkey) {
-        let program = env::var("BRIDGE_PROGRAM")
-            .unwrap_or_else(|_| "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o".to_string())
-            .parse::<Pubkey>()
-            .unwrap();
-        let builder = ProgramTest::new("bridge", program, processor!(instruction::solitaire));
+        setup_with_accounts(&[]).await
+    }
+
+    /// Like `setup`, but with `accounts` already present when the environment starts, which
+    /// allows tests to provide accounts the program can no longer create.
+    pub async fn setup_with_accounts(
+        accounts: &[(Pubkey, Account)],
+    ) -> (BanksClient, Keypair, Pubkey) {
+        let program = program_id();
+        let mut builder = ProgramTest::new("bridge", program, processor!(instruction::solitaire));
+        for (key, account) in accounts {
+            builder.add_account(*key, account.clone());
+        }
 
         let (client, payer, _) = builder.start().await;
 
         (client, payer, program)
     }
 
+    /// Address of the bridge program under test.
+    pub fn program_id() -> Pubkey {
+        env::var("BRIDGE_PROGRAM")
+            .unwrap_or_else(|_| "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o".to_string())
+            .parse::<Pubkey>()
+            .unwrap()
+    }
+
     /// Fetch account data, the loop is there to re-attempt until data is available.
     pub async fn get_account_data<T: BorshDeserialize>(
         client: &mut BanksClient,
@@ -295,6 +312,31 @@ mod helpers {
         .await
     }
 
+    pub async fn resize_message_unreliable(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        emitter: &Keypair,
+        message: Pubkey,
+        payload_len: u32,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer, emitter],
+            &[instructions::resize_message_unreliable(
+                *program,
+                payer.pubkey(),
+                emitter.pubkey(),
+                message,
+                payload_len,
+            )
+            .unwrap()],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn verify_signatures(
         client: &mut BanksClient,
         program: &Pubkey,
@@ -356,6 +398,31 @@ mod helpers {
         .await
     }
 
+    pub async fn close_posted_vaa(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        posted_vaa: Pubkey,
+        signature_set: Pubkey,
+        claim: Option<Pubkey>,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::close_posted_vaa(
+                *program,
+                payer.pubkey(),
+                payer.pubkey(),
+                posted_vaa,
+                signature_set,
+                claim,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn upgrade_guardian_set(
         client: &mut BanksClient,
@@ -438,6 +505,56 @@ mod helpers {
         .await
     }
 
+    pub async fn set_emitter_fee(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+        fee_emitter: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_emitter_fee(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+                fee_emitter,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn set_close_age(
+        client: &mut BanksClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        sequence: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_close_age(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                sequence,
+            )],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn transfer_fees(
         client: &mut BanksClient,
         program: &Pubkey,
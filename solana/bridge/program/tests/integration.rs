@@ -1,6 +1,8 @@
+use borsh::BorshSerialize;
 use libsecp256k1::SecretKey;
 use rand::Rng;
 use solana_program::{
+    instruction::AccountMeta,
     pubkey::Pubkey,
     system_instruction,
 };
@@ -9,6 +11,7 @@ use solana_program_test::{
     BanksClient,
 };
 use solana_sdk::{
+    account::Account,
     commitment_config::CommitmentLevel,
     signature::{
         Keypair,
@@ -24,6 +27,13 @@ use bridge::{
     accounts::{
         Bridge,
         BridgeData,
+        Claim,
+        ClaimDerivationData,
+        CloseAge,
+        CloseAgeData,
+        EmitterFee,
+        EmitterFeeData,
+        EmitterFeeDerivationData,
         FeeCollector,
         GuardianSet,
         GuardianSetData,
@@ -32,15 +42,19 @@ use bridge::{
         PostedVAAData,
         PostedVAADerivationData,
         SignatureSetData,
+        DEFAULT_CLOSE_AGE,
     },
     instructions,
     types::{
         ConsistencyLevel,
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetCloseAge,
+        GovernancePayloadSetEmitterFee,
         GovernancePayloadSetMessageFee,
         GovernancePayloadTransferFees,
         GovernancePayloadUpgrade,
     },
+    PostVAAData,
     SerializeGovernancePayload,
 };
 use primitive_types::U256;
@@ -217,6 +231,7 @@ async fn bridge_messages() {
         // Verify on chain Signatures
         assert_eq!(signatures.hash, body);
         assert_eq!(signatures.guardian_set_index, 0);
+        assert_eq!(signatures.payer, payer.pubkey());
 
         for (signature, _secret_key) in signatures.signatures.iter().zip(context.secret.iter()) {
             assert!(*signature);
@@ -470,6 +485,228 @@ async fn test_bridge_messages_unreliable_do_not_override_reliable() {
     .is_err());
 }
 
+#[tokio::test]
+async fn test_bridge_messages_unreliable_resize() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+
+    let emitter = Keypair::new();
+    let message_key = Keypair::new();
+
+    let nonce = rand::thread_rng().gen();
+    let message: [u8; 32] = rand::thread_rng().gen();
+    common::post_message_unreliable(
+        client,
+        program,
+        payer,
+        &emitter,
+        &message_key,
+        nonce,
+        message.to_vec(),
+        10_000,
+    )
+    .await
+    .unwrap();
+    context.seq.next(emitter.pubkey().to_bytes());
+
+    // Only the emitter of the message may resize the account.
+    assert!(common::resize_message_unreliable(
+        client,
+        program,
+        payer,
+        &Keypair::new(),
+        message_key.pubkey(),
+        128,
+    )
+    .await
+    .is_err());
+
+    for len in [128usize, 16] {
+        common::resize_message_unreliable(
+            client,
+            program,
+            payer,
+            &emitter,
+            message_key.pubkey(),
+            len as u32,
+        )
+        .await
+        .unwrap();
+
+        // The account is rent exempt at its new size and the payload was padded or truncated.
+        let account = client
+            .get_account(message_key.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            account.lamports,
+            Rent::default().minimum_balance(account.data.len())
+        );
+        let posted_message: PostedVAAData =
+            common::get_account_data(client, message_key.pubkey()).await;
+        assert_eq!(posted_message.message.payload.len(), len);
+
+        // Messages of the new length can now be posted to the account.
+        let nonce = rand::thread_rng().gen();
+        let message = vec![0xa5u8; len];
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
+        common::post_message_unreliable(
+            client,
+            program,
+            payer,
+            &emitter,
+            &message_key,
+            nonce,
+            message.clone(),
+            10_000,
+        )
+        .await
+        .unwrap();
+
+        let posted_message: PostedVAAData =
+            common::get_account_data(client, message_key.pubkey()).await;
+        assert_eq!(posted_message.message.sequence, sequence);
+        assert_eq!(posted_message.message.payload, message);
+    }
+}
+
+#[tokio::test]
+async fn test_bridge_messages_unreliable_resize_reliable_fails() {
+    let (ref mut _context, ref mut client, ref payer, ref program) = initialize().await;
+
+    let emitter = Keypair::new();
+    let message_key = Keypair::new();
+
+    let nonce = rand::thread_rng().gen();
+    let message: [u8; 32] = rand::thread_rng().gen();
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        Some(&message_key),
+        nonce,
+        message.to_vec(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    // Reliable messages can never be resized.
+    assert!(common::resize_message_unreliable(
+        client,
+        program,
+        payer,
+        &emitter,
+        message_key.pubkey(),
+        128,
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn bridge_messages_consistency_levels() {
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::new();
+    let fee_collector = FeeCollector::key(None, program);
+
+    for (level, expected) in [
+        (ConsistencyLevel::Confirmed, 1),
+        (ConsistencyLevel::Finalized, 32),
+        (ConsistencyLevel::Instant, 200),
+        (ConsistencyLevel::Safe, 201),
+    ] {
+        let message_key = Keypair::new();
+        let nonce = rand::thread_rng().gen();
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
+        common::execute(
+            client,
+            payer,
+            &[payer, &emitter, &message_key],
+            &[
+                system_instruction::transfer(&payer.pubkey(), &fee_collector, 10_000),
+                instructions::post_message(
+                    *program,
+                    payer.pubkey(),
+                    emitter.pubkey(),
+                    message_key.pubkey(),
+                    nonce,
+                    b"consistency".to_vec(),
+                    level,
+                )
+                .unwrap(),
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await
+        .unwrap();
+
+        let posted_message: PostedVAAData =
+            common::get_account_data(client, message_key.pubkey()).await;
+        assert_eq!(posted_message.message.sequence, sequence);
+        assert_eq!(posted_message.message.consistency_level, expected);
+    }
+}
+
+#[test]
+fn consistency_level_legacy_encoding() {
+    use borsh::{
+        BorshDeserialize,
+        BorshSerialize,
+    };
+
+    // The consistency level and instruction data as understood by clients built before the
+    // additional levels were introduced.
+    #[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+    enum LegacyConsistencyLevel {
+        Confirmed,
+        Finalized,
+    }
+
+    #[derive(BorshSerialize, BorshDeserialize)]
+    struct LegacyPostMessageData {
+        nonce: u32,
+        payload: Vec<u8>,
+        consistency_level: LegacyConsistencyLevel,
+    }
+
+    for (level, legacy) in [
+        (
+            ConsistencyLevel::Confirmed,
+            LegacyConsistencyLevel::Confirmed,
+        ),
+        (
+            ConsistencyLevel::Finalized,
+            LegacyConsistencyLevel::Finalized,
+        ),
+    ] {
+        let data = bridge::PostMessageData {
+            nonce: 7,
+            payload: b"legacy".to_vec(),
+            consistency_level: level,
+        }
+        .try_to_vec()
+        .unwrap();
+
+        // Old clients decode the existing levels exactly as before.
+        let decoded = LegacyPostMessageData::try_from_slice(&data).unwrap();
+        assert_eq!(decoded.nonce, 7);
+        assert_eq!(decoded.payload, b"legacy");
+        assert_eq!(decoded.consistency_level, legacy);
+
+        // And data encoded by old clients is still accepted.
+        assert_eq!(decoded.try_to_vec().unwrap(), data);
+        bridge::PostMessageData::try_from_slice(&data).unwrap();
+    }
+
+    // Levels unknown to old clients fail to decode rather than being misinterpreted.
+    for level in [ConsistencyLevel::Instant, ConsistencyLevel::Safe] {
+        let data = level.try_to_vec().unwrap();
+        assert!(LegacyConsistencyLevel::try_from_slice(&data).is_err());
+    }
+}
+
 #[tokio::test]
 async fn bridge_works_after_transfer_fees() {
     // This test aims to ensure that the bridge remains operational after the
@@ -1100,15 +1337,17 @@ async fn set_fees_fails() {
 }
 
 #[tokio::test]
-async fn free_fees() {
+async fn set_emitter_fee() {
     // Initialize a wormhole bridge on Solana to test with.
     let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
     let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
 
-    // Set Fees to 0.
+    // Waive the fee for a single emitter.
+    let fee_emitter = Keypair::new();
     let nonce = rand::thread_rng().gen();
-    let message = GovernancePayloadSetMessageFee {
+    let message = GovernancePayloadSetEmitterFee {
+        emitter: fee_emitter.pubkey(),
         fee: U256::from(0u128),
     }
     .try_to_vec()
@@ -1135,34 +1374,86 @@ async fn free_fees() {
     common::post_vaa(client, program, payer, signature_set, vaa)
         .await
         .unwrap();
-    common::set_fees(
+    common::set_emitter_fee(
         client,
         program,
         payer,
         message_key,
         emitter.pubkey(),
         sequence,
+        fee_emitter.pubkey(),
     )
     .await
     .unwrap();
 
-    // Fetch Bridge to check on-state value.
-    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
-    let fee_collector = FeeCollector::key(None, program);
-    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
-    assert_eq!(bridge.config.fee, 0);
+    // Fetch the override to check on-state value.
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::Initialized }>::key(
+        &EmitterFeeDerivationData {
+            emitter_key: fee_emitter.pubkey(),
+        },
+        program,
+    );
+    let emitter_fee: EmitterFeeData = common::get_account_data(client, emitter_fee_key).await;
+    assert_eq!(emitter_fee.fee, 0);
 
-    // Check that posting a new message is free.
+    // The override only applies when passed along, as the last account.
+    let fee_collector = FeeCollector::key(None, program);
     let account_balance = common::get_account_balance(client, fee_collector).await;
-    let emitter = Keypair::new();
-    let sequence = context.seq.next(emitter.pubkey().to_bytes());
     let nonce = rand::thread_rng().gen();
     let message = [0u8; 32].to_vec();
-    let _message_key = common::post_message(
+    let message_pair = Keypair::new();
+    let mut instruction = instructions::post_message(
+        *program,
+        payer.pubkey(),
+        fee_emitter.pubkey(),
+        message_pair.pubkey(),
+        nonce,
+        message.clone(),
+        ConsistencyLevel::Confirmed,
+    )
+    .unwrap();
+    instruction.accounts.pop();
+    assert!(common::execute(
+        client,
+        payer,
+        &[payer, &fee_emitter, &message_pair],
+        &[instruction],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .is_err());
+
+    // Other emitters can't make use of the override.
+    let other_emitter = Keypair::new();
+    let message_pair = Keypair::new();
+    let mut instruction = instructions::post_message(
+        *program,
+        payer.pubkey(),
+        other_emitter.pubkey(),
+        message_pair.pubkey(),
+        nonce,
+        message.clone(),
+        ConsistencyLevel::Confirmed,
+    )
+    .unwrap();
+    *instruction.accounts.last_mut().unwrap() = AccountMeta::new_readonly(emitter_fee_key, false);
+    assert!(common::execute(
+        client,
+        payer,
+        &[payer, &other_emitter, &message_pair],
+        &[instruction],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .is_err());
+
+    // But the emitter it was set for posts for free.
+    let sequence = context.seq.next(fee_emitter.pubkey().to_bytes());
+    let message_key = common::post_message(
         client,
         program,
         payer,
-        &emitter,
+        &fee_emitter,
         None,
         nonce,
         message.clone(),
@@ -1171,80 +1462,39 @@ async fn free_fees() {
     .await
     .unwrap();
 
-    let (vaa, body, _body_hash) =
-        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
-    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
-        .await
-        .unwrap();
-    let vaa_time = vaa.timestamp;
-    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
-        &PostedVAADerivationData {
-            payload_hash: body.to_vec(),
-        },
-        program,
-    );
-    common::post_vaa(client, program, payer, signature_set, vaa)
-        .await
-        .unwrap();
-
-    // Verify that the fee collector was paid.
     assert_eq!(
         common::get_account_balance(client, fee_collector).await,
         account_balance,
     );
 
-    // And that the new message is on chain.
     let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
-    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
-
-    // Verify on chain Message
-    assert_eq!(posted_message.message.vaa_version, 0);
-    assert_eq!(
-        posted_message.message.consistency_level,
-        ConsistencyLevel::Confirmed as u8
-    );
-    assert_eq!(posted_message.message.vaa_time, vaa_time);
-    assert_eq!(posted_message.message.vaa_signature_account, signature_set);
-    assert_eq!(posted_message.message.nonce, nonce);
     assert_eq!(posted_message.message.sequence, sequence);
-    assert_eq!(posted_message.message.emitter_chain, 1);
-    assert_eq!(
-        &posted_message.message.emitter_address,
-        emitter.pubkey().as_ref()
-    );
     assert_eq!(posted_message.message.payload, message);
-    assert_eq!(
-        posted_message.message.emitter_address,
-        emitter.pubkey().to_bytes()
-    );
 
-    // Verify on chain Signatures
-    assert_eq!(signatures.hash, body);
-    assert_eq!(signatures.guardian_set_index, 0);
-
-    for (signature, _secret_key) in signatures.signatures.iter().zip(context.secret.iter()) {
-        assert!(*signature);
-    }
-}
+    // Emitters without an override still pay the global fee.
+    assert!(common::post_message(
+        client,
+        program,
+        payer,
+        &other_emitter,
+        None,
+        nonce,
+        message.clone(),
+        0,
+    )
+    .await
+    .is_err());
 
-#[tokio::test]
-async fn transfer_fees() {
-    // Initialize a wormhole bridge on Solana to test with.
-    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
-    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    // Setting the maximum fee removes the override again.
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
-
     let nonce = rand::thread_rng().gen();
-    let message = GovernancePayloadTransferFees {
-        amount: 100u128.into(),
-        to: payer.pubkey().to_bytes(),
+    let governance = GovernancePayloadSetEmitterFee {
+        emitter: fee_emitter.pubkey(),
+        fee: U256::max_value(),
     }
     .try_to_vec()
     .unwrap();
 
-    // Fetch accounts for chain state checking.
-    let fee_collector = FeeCollector::key(None, program);
-
     let message_key = common::post_message(
         client,
         program,
@@ -1252,36 +1502,281 @@ async fn transfer_fees() {
         &emitter,
         None,
         nonce,
-        message.clone(),
+        governance.clone(),
         10_000,
     )
     .await
     .unwrap();
 
     let (vaa, body, _body_hash) =
-        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+        common::generate_vaa(&emitter, governance.clone(), nonce, sequence, 0, 1);
     let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
         .await
         .unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa)
         .await
         .unwrap();
-
-    let previous_balance = common::get_account_balance(client, fee_collector).await;
-
-    common::transfer_fees(
+    common::set_emitter_fee(
         client,
         program,
         payer,
         message_key,
         emitter.pubkey(),
-        payer.pubkey(),
         sequence,
+        fee_emitter.pubkey(),
     )
     .await
     .unwrap();
-    assert_eq!(
-        common::get_account_balance(client, fee_collector).await,
+
+    assert!(client.get_account(emitter_fee_key).await.unwrap().is_none());
+    assert!(common::post_message(
+        client,
+        program,
+        payer,
+        &fee_emitter,
+        None,
+        nonce,
+        message.clone(),
+        0,
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn set_emitter_fee_fails() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+
+    // Use a random key to confirm only the governance key is respected.
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetEmitterFee {
+        emitter: emitter.pubkey(),
+        fee: U256::from(0u128),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    assert!(common::set_emitter_fee(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+        emitter.pubkey(),
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn free_fees() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Set Fees to 0.
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(0u128),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+    common::set_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+
+    // Fetch Bridge to check on-state value.
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let fee_collector = FeeCollector::key(None, program);
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.config.fee, 0);
+
+    // Check that posting a new message is free.
+    let account_balance = common::get_account_balance(client, fee_collector).await;
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let _message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    let vaa_time = vaa.timestamp;
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    // Verify that the fee collector was paid.
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        account_balance,
+    );
+
+    // And that the new message is on chain.
+    let posted_message: PostedVAAData = common::get_account_data(client, message_key).await;
+    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
+
+    // Verify on chain Message
+    assert_eq!(posted_message.message.vaa_version, 0);
+    assert_eq!(
+        posted_message.message.consistency_level,
+        ConsistencyLevel::Confirmed as u8
+    );
+    assert_eq!(posted_message.message.vaa_time, vaa_time);
+    assert_eq!(posted_message.message.vaa_signature_account, signature_set);
+    assert_eq!(posted_message.message.nonce, nonce);
+    assert_eq!(posted_message.message.sequence, sequence);
+    assert_eq!(posted_message.message.emitter_chain, 1);
+    assert_eq!(
+        &posted_message.message.emitter_address,
+        emitter.pubkey().as_ref()
+    );
+    assert_eq!(posted_message.message.payload, message);
+    assert_eq!(
+        posted_message.message.emitter_address,
+        emitter.pubkey().to_bytes()
+    );
+
+    // Verify on chain Signatures
+    assert_eq!(signatures.hash, body);
+    assert_eq!(signatures.guardian_set_index, 0);
+
+    for (signature, _secret_key) in signatures.signatures.iter().zip(context.secret.iter()) {
+        assert!(*signature);
+    }
+}
+
+#[tokio::test]
+async fn transfer_fees() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadTransferFees {
+        amount: 100u128.into(),
+        to: payer.pubkey().to_bytes(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    // Fetch accounts for chain state checking.
+    let fee_collector = FeeCollector::key(None, program);
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .await
+    .unwrap();
+
+    let (vaa, body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    let previous_balance = common::get_account_balance(client, fee_collector).await;
+
+    common::transfer_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        payer.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
         previous_balance - 100
     );
 }
@@ -1578,3 +2073,358 @@ async fn upgrade_contract() {
     .await
     .unwrap();
 }
+
+/// Verify and post a VAA, returning the signature set and posted VAA accounts.
+async fn post_vaa_accounts(
+    context: &Context,
+    client: &mut BanksClient,
+    payer: &Keypair,
+    program: &Pubkey,
+    vaa: PostVAAData,
+) -> (Pubkey, Pubkey) {
+    let body = instructions::hash_vaa(&vaa);
+    let signature_set = common::verify_signatures(client, program, payer, body, &context.secret, 0)
+        .await
+        .unwrap();
+    let posted_vaa = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+    common::post_vaa(client, program, payer, signature_set, vaa)
+        .await
+        .unwrap();
+
+    (signature_set, posted_vaa)
+}
+
+#[tokio::test]
+async fn close_posted_vaa_not_expired() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let (vaa, _body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let (signature_set, posted_vaa) = post_vaa_accounts(context, client, payer, program, vaa).await;
+
+    // The VAA is not old enough to be closed yet.
+    assert!(
+        common::close_posted_vaa(client, program, payer, posted_vaa, signature_set, None)
+            .await
+            .is_err()
+    );
+    assert!(client.get_account(posted_vaa).await.unwrap().is_some());
+    assert!(client.get_account(signature_set).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn close_posted_vaa_expired() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let (mut vaa, _body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+
+    // Backdate the VAA so that it is already past the close age.
+    vaa.timestamp -= DEFAULT_CLOSE_AGE + 60;
+    let (signature_set, posted_vaa) = post_vaa_accounts(context, client, payer, program, vaa).await;
+
+    let posted_message: PostedVAAData = common::get_account_data(client, posted_vaa).await;
+    assert_eq!(posted_message.payer, payer.pubkey());
+    let signatures: SignatureSetData = common::get_account_data(client, signature_set).await;
+    assert_eq!(signatures.payer, payer.pubkey());
+
+    // Only the payer of the accounts may receive their lamports.
+    let other = Keypair::new();
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            &other.pubkey(),
+            1_000_000_000,
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+    assert!(
+        common::close_posted_vaa(client, program, &other, posted_vaa, signature_set, None)
+            .await
+            .is_err()
+    );
+
+    let reclaimed = common::get_account_balance(client, posted_vaa).await
+        + common::get_account_balance(client, signature_set).await;
+    let payer_balance = common::get_account_balance(client, payer.pubkey()).await;
+
+    // Once the VAA is old enough anyone can close it, the payer does not need to sign.
+    common::execute(
+        client,
+        &other,
+        &[&other],
+        &[instructions::close_posted_vaa(
+            *program,
+            payer.pubkey(),
+            payer.pubkey(),
+            posted_vaa,
+            signature_set,
+            None,
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+
+    // Both accounts are gone and their lamports went back to the payer.
+    assert!(client.get_account(posted_vaa).await.unwrap().is_none());
+    assert!(client.get_account(signature_set).await.unwrap().is_none());
+    assert_eq!(
+        common::get_account_balance(client, payer.pubkey()).await,
+        payer_balance + reclaimed,
+    );
+}
+
+#[tokio::test]
+async fn close_posted_vaa_claimed() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(100u128),
+    }
+    .try_to_vec()
+    .unwrap();
+    let (vaa, _body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+    let (signature_set, posted_vaa) = post_vaa_accounts(context, client, payer, program, vaa).await;
+
+    let claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.pubkey().to_bytes(),
+            emitter_chain: 1,
+            sequence,
+        },
+        program,
+    );
+
+    // The VAA can't be closed early before it has been consumed.
+    assert!(common::close_posted_vaa(
+        client,
+        program,
+        payer,
+        posted_vaa,
+        signature_set,
+        Some(claim)
+    )
+    .await
+    .is_err());
+
+    common::set_fees(
+        client,
+        program,
+        payer,
+        posted_vaa,
+        emitter.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+
+    // Only the claim of this VAA counts as proof that it was consumed.
+    let other_claim = Claim::<'_>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.pubkey().to_bytes(),
+            emitter_chain: 1,
+            sequence: sequence + 1,
+        },
+        program,
+    );
+    assert!(common::close_posted_vaa(
+        client,
+        program,
+        payer,
+        posted_vaa,
+        signature_set,
+        Some(other_claim)
+    )
+    .await
+    .is_err());
+
+    common::close_posted_vaa(
+        client,
+        program,
+        payer,
+        posted_vaa,
+        signature_set,
+        Some(claim),
+    )
+    .await
+    .unwrap();
+    assert!(client.get_account(posted_vaa).await.unwrap().is_none());
+    assert!(client.get_account(signature_set).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn set_close_age() {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref mut context, ref mut client, ref payer, ref program) = initialize().await;
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let (mut vaa, _body, _body_hash) =
+        common::generate_vaa(&emitter, message.clone(), nonce, sequence, 0, 1);
+
+    // Backdate the VAA past the new close age, but not the default one.
+    vaa.timestamp -= 120;
+    let (signature_set, posted_vaa) = post_vaa_accounts(context, client, payer, program, vaa).await;
+    assert!(
+        common::close_posted_vaa(client, program, payer, posted_vaa, signature_set, None)
+            .await
+            .is_err()
+    );
+
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(governance.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetCloseAge { age: 60 }
+        .try_to_vec()
+        .unwrap();
+    let (vaa, _body, _body_hash) =
+        common::generate_vaa(&governance, message.clone(), nonce, sequence, 0, 1);
+    let (_, governance_vaa) = post_vaa_accounts(context, client, payer, program, vaa).await;
+    common::set_close_age(
+        client,
+        program,
+        payer,
+        governance_vaa,
+        governance.pubkey(),
+        sequence,
+    )
+    .await
+    .unwrap();
+
+    let close_age_key = CloseAge::<'_, { AccountState::Initialized }>::key(None, program);
+    let close_age: CloseAgeData = common::get_account_data(client, close_age_key).await;
+    assert_eq!(close_age.age, 60);
+
+    common::close_posted_vaa(client, program, payer, posted_vaa, signature_set, None)
+        .await
+        .unwrap();
+    assert!(client.get_account(posted_vaa).await.unwrap().is_none());
+    assert!(client.get_account(signature_set).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn close_legacy_posted_vaa() {
+    let program = common::program_id();
+    let (public_keys, _) = common::generate_keys(6);
+
+    // Accounts created before the payer was recorded, which the program can no longer create.
+    let hash = [7u8; 32];
+    let signature_set = Pubkey::new_unique();
+    let posted_vaa = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash.to_vec(),
+        },
+        &program,
+    );
+    let legacy_account = |data: Vec<u8>| Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: program,
+        executable: false,
+        rent_epoch: 0,
+    };
+    let mut posted_message = PostedVAAData::default();
+    posted_message.vaa_signature_account = signature_set;
+    let signatures = SignatureSetData {
+        signatures: vec![true; 6],
+        hash,
+        guardian_set_index: 0,
+        payer: Pubkey::default(),
+    };
+    let (ref mut client, ref payer, ref program) = common::setup_with_accounts(&[
+        (
+            posted_vaa,
+            legacy_account(posted_message.try_to_vec().unwrap()),
+        ),
+        (
+            signature_set,
+            legacy_account(signatures.try_to_vec().unwrap()),
+        ),
+    ])
+    .await;
+    common::initialize(client, *program, payer, &public_keys, 500)
+        .await
+        .unwrap();
+
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, program);
+    let fee_collector = FeeCollector::key(None, program);
+    let reclaimed = common::get_account_balance(client, posted_vaa).await
+        + common::get_account_balance(client, signature_set).await;
+    let collected = common::get_account_balance(client, fee_collector).await;
+
+    // Nobody can claim to have paid for the accounts, their lamports go to the fee collector.
+    assert!(
+        common::close_posted_vaa(client, program, payer, posted_vaa, signature_set, None)
+            .await
+            .is_err()
+    );
+    common::execute(
+        client,
+        payer,
+        &[payer],
+        &[instructions::close_posted_vaa(
+            *program,
+            fee_collector,
+            fee_collector,
+            posted_vaa,
+            signature_set,
+            None,
+        )],
+        CommitmentLevel::Processed,
+    )
+    .await
+    .unwrap();
+
+    assert!(client.get_account(posted_vaa).await.unwrap().is_none());
+    assert!(client.get_account(signature_set).await.unwrap().is_none());
+    assert_eq!(
+        common::get_account_balance(client, fee_collector).await,
+        collected + reclaimed,
+    );
+
+    // The refunded lamports are not counted as a fee for the next message.
+    let bridge: BridgeData = common::get_account_data(client, bridge_key).await;
+    assert_eq!(bridge.last_lamports, collected + reclaimed);
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    assert!(common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        None,
+        nonce,
+        [0u8; 32].to_vec(),
+        0,
+    )
+    .await
+    .is_err());
+}
@@ -3,11 +3,18 @@ use crate::{
     TokenBridgeError,
 };
 use bridge::{
-    accounts::BridgeData,
+    accounts::{
+        BridgeData,
+        EmitterFee,
+        EmitterFeeDerivationData,
+    },
     api::ForeignAddress,
 };
 use primitive_types::U256;
-use solana_program::pubkey::Pubkey;
+use solana_program::{
+    instruction::AccountMeta,
+    pubkey::Pubkey,
+};
 use solitaire::{
     processors::seeded::Seeded,
     *,
@@ -22,6 +29,25 @@ pub type CoreBridge<'a, const STATE: AccountState> = Data<'a, BridgeData, { STAT
 
 pub type EmitterAccount<'b> = Derive<Info<'b>, "emitter">;
 
+/// The core bridge fee override of the emitter, forwarded when posting a message if the caller
+/// passed it along. Without it the core bridge charges its global fee.
+pub fn emitter_fee_account(
+    ctx: &ExecutionContext,
+    bridge_id: &Pubkey,
+    emitter: &Pubkey,
+) -> Option<AccountMeta> {
+    let emitter_fee = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData {
+            emitter_key: *emitter,
+        },
+        bridge_id,
+    );
+    ctx.accounts
+        .iter()
+        .find(|account| *account.key == emitter_fee)
+        .map(|_| AccountMeta::new_readonly(emitter_fee, false))
+}
+
 pub type ConfigAccount<'b, const STATE: AccountState> =
     Derive<Data<'b, Config, { STATE }>, "config">;
 
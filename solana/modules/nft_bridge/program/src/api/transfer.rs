@@ -1,6 +1,7 @@
 use crate::{
     accounts::{
         deserialize_and_verify_metadata,
+        emitter_fee_account,
         AuthoritySigner,
         ConfigAccount,
         CoreBridge,
@@ -188,7 +189,7 @@ pub fn transfer_native(
         },
     );
 
-    let ix = Instruction::new_with_bytes(
+    let mut ix = Instruction::new_with_bytes(
         accs.config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
         vec![
@@ -203,6 +204,11 @@ pub fn transfer_native(
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
         ],
     );
+    ix.accounts.extend(emitter_fee_account(
+        ctx,
+        &accs.config.wormhole_bridge,
+        accs.emitter.key,
+    ));
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
 
     Ok(())
@@ -330,7 +336,7 @@ pub fn transfer_wrapped(
         },
     );
 
-    let ix = Instruction::new_with_bytes(
+    let mut ix = Instruction::new_with_bytes(
         accs.config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
         vec![
@@ -345,6 +351,11 @@ pub fn transfer_wrapped(
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
         ],
     );
+    ix.accounts.extend(emitter_fee_account(
+        ctx,
+        &accs.config.wormhole_bridge,
+        accs.emitter.key,
+    ));
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
 
     Ok(())
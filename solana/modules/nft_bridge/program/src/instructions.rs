@@ -38,6 +38,8 @@ use bridge::{
         Bridge,
         Claim,
         ClaimDerivationData,
+        EmitterFee,
+        EmitterFeeDerivationData,
         FeeCollector,
         Sequence,
         SequenceDerivationData,
@@ -347,6 +349,10 @@ pub fn transfer_native(
         &bridge_id,
     );
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::Uninitialized }>::key(
+        &EmitterFeeDerivationData { emitter_key },
+        &bridge_id,
+    );
 
     Ok(Instruction {
         program_id,
@@ -371,6 +377,8 @@ pub fn transfer_native(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            // Optional fee override of the emitter
+            AccountMeta::new_readonly(emitter_fee_key, false),
         ],
         data: (crate::instruction::Instruction::TransferNative, data).try_to_vec()?,
     })
@@ -426,6 +434,10 @@ pub fn transfer_wrapped(
         &bridge_id,
     );
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::Uninitialized }>::key(
+        &EmitterFeeDerivationData { emitter_key },
+        &bridge_id,
+    );
 
     Ok(Instruction {
         program_id,
@@ -450,6 +462,8 @@ pub fn transfer_wrapped(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            // Optional fee override of the emitter
+            AccountMeta::new_readonly(emitter_fee_key, false),
         ],
         data: (crate::instruction::Instruction::TransferWrapped, data).try_to_vec()?,
     })
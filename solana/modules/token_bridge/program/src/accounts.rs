@@ -1,12 +1,20 @@
 use crate::{
+    token_2022,
     types::*,
     TokenBridgeError,
 };
 use bridge::{
-    accounts::BridgeData,
+    accounts::{
+        BridgeData,
+        EmitterFee,
+        EmitterFeeDerivationData,
+    },
     api::ForeignAddress,
 };
-use solana_program::pubkey::Pubkey;
+use solana_program::{
+    instruction::AccountMeta,
+    pubkey::Pubkey,
+};
 use solitaire::{
     processors::seeded::Seeded,
     *,
@@ -21,6 +29,25 @@ pub type CoreBridge<'a, const STATE: AccountState> = Data<'a, BridgeData, { STAT
 
 pub type EmitterAccount<'b> = Derive<Info<'b>, "emitter">;
 
+/// The core bridge fee override of the emitter, forwarded when posting a message if the caller
+/// passed it along. Without it the core bridge charges its global fee.
+pub fn emitter_fee_account(
+    ctx: &ExecutionContext,
+    bridge_id: &Pubkey,
+    emitter: &Pubkey,
+) -> Option<AccountMeta> {
+    let emitter_fee = EmitterFee::<'_, { AccountState::MaybeInitialized }>::key(
+        &EmitterFeeDerivationData {
+            emitter_key: *emitter,
+        },
+        bridge_id,
+    );
+    ctx.accounts
+        .iter()
+        .find(|account| *account.key == emitter_fee)
+        .map(|_| AccountMeta::new_readonly(emitter_fee, false))
+}
+
 pub type ConfigAccount<'b, const STATE: AccountState> =
     Derive<Data<'b, Config, { STATE }>, "config">;
 
@@ -38,6 +65,19 @@ impl<'b, const STATE: AccountState> Seeded<&CustodyAccountDerivationData>
     }
 }
 
+/// Custody of native tokens owned by the Token-2022 program. Mints are unique across both token
+/// programs, so this shares its derivation with `CustodyAccount`.
+pub type Token2022CustodyAccount<'b, const STATE: AccountState> =
+    Data<'b, Token2022Account, { STATE }>;
+
+impl<'b, const STATE: AccountState> Seeded<&CustodyAccountDerivationData>
+    for Token2022CustodyAccount<'b, { STATE }>
+{
+    fn seeds(accs: &CustodyAccountDerivationData) -> Vec<Vec<u8>> {
+        vec![accs.mint.to_bytes().to_vec()]
+    }
+}
+
 pub type WrappedMint<'b, const STATE: AccountState> = Data<'b, SplMint, { STATE }>;
 
 pub struct WrappedDerivationData {
@@ -146,3 +186,34 @@ pub fn deserialize_and_verify_metadata(
         _ => Err(TokenBridgeError::InvalidMetadata.into()),
     }
 }
+
+/// Token-2022 mints can carry their own metadata in the TokenMetadata extension, in which case the
+/// MetadataPointer extension points at the mint itself. Returns `None` for mints storing their
+/// metadata anywhere else, including classic SPL mints, which are covered by
+/// `deserialize_and_verify_metadata` instead.
+pub fn deserialize_and_verify_token_2022_metadata(
+    mint: &Info,
+) -> Result<Option<token_2022::TokenMetadata>> {
+    if *mint.owner != token_2022::id() {
+        return Ok(None);
+    }
+
+    let data: &[u8] = &mint.data.borrow();
+
+    // Only trust the embedded metadata if the mint's metadata pointer refers to it.
+    if token_2022::metadata_pointer(data) != Some(*mint.key) {
+        return Ok(None);
+    }
+
+    match token_2022::token_metadata(data) {
+        Some(Ok(metadata)) => {
+            if metadata.mint == *mint.key {
+                Ok(Some(metadata))
+            } else {
+                Err(TokenBridgeError::InvalidMetadata.into())
+            }
+        }
+        Some(Err(e)) => Err(e),
+        None => Ok(None),
+    }
+}
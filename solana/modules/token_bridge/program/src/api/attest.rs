@@ -1,6 +1,8 @@
 use crate::{
     accounts::{
         deserialize_and_verify_metadata,
+        deserialize_and_verify_token_2022_metadata,
+        emitter_fee_account,
         ConfigAccount,
         CoreBridge,
         EmitterAccount,
@@ -10,7 +12,9 @@ use crate::{
         WrappedTokenMeta,
     },
     messages::PayloadAssetMeta,
+    token_2022,
     types::*,
+    TokenBridgeError::WrongAccountOwner,
 };
 use bridge::{
     api::PostMessageData,
@@ -25,6 +29,7 @@ use solana_program::{
         Instruction,
     },
     program::invoke,
+    program_pack::Pack,
     sysvar::clock::Clock,
 };
 use solitaire::{
@@ -34,6 +39,7 @@ use solitaire::{
     },
     *,
 };
+use spl_token::state::Mint;
 
 #[derive(FromAccounts)]
 pub struct AttestToken<'b> {
@@ -41,8 +47,8 @@ pub struct AttestToken<'b> {
 
     pub config: Mut<ConfigAccount<'b, { AccountState::Initialized }>>,
 
-    /// Mint to attest
-    pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    /// Mint to attest, owned by either the SPL Token or the Token-2022 program
+    pub mint: Info<'b>,
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Uninitialized }>,
 
     /// SPL Metadata for the associated Mint
@@ -69,7 +75,7 @@ pub struct AttestToken<'b> {
 impl<'a> From<&AttestToken<'a>> for WrappedMetaDerivationData {
     fn from(accs: &AttestToken<'a>) -> Self {
         WrappedMetaDerivationData {
-            mint_key: *accs.mint.info().key,
+            mint_key: *accs.mint.key,
         }
     }
 }
@@ -77,7 +83,7 @@ impl<'a> From<&AttestToken<'a>> for WrappedMetaDerivationData {
 impl<'a> From<&AttestToken<'a>> for SplTokenMetaDerivationData {
     fn from(accs: &AttestToken<'a>) -> Self {
         SplTokenMetaDerivationData {
-            mint: *accs.mint.info().key,
+            mint: *accs.mint.key,
         }
     }
 }
@@ -107,16 +113,21 @@ pub fn attest_token(
         .verify_derivation(ctx.program_id, &derivation_data)?;
 
     // Create Asset Metadata
+    let mint = unpack_mint(&accs.mint)?;
     let mut payload = PayloadAssetMeta {
-        token_address: accs.mint.info().key.to_bytes(),
+        token_address: accs.mint.key.to_bytes(),
         token_chain: CHAIN_ID_SOLANA,
-        decimals: accs.mint.decimals,
+        decimals: mint.decimals,
         symbol: "".to_string(),
         name: "".to_string(),
     };
 
-    // Assign metadata if an SPL Metadata account exists for the SPL token in question.
-    if !accs.spl_metadata.data_is_empty() {
+    // Assign metadata if the Token-2022 mint carries its own, or an SPL Metadata account exists for
+    // the SPL token in question.
+    if let Some(metadata) = deserialize_and_verify_token_2022_metadata(&accs.mint)? {
+        payload.name = metadata.name;
+        payload.symbol = metadata.symbol;
+    } else if !accs.spl_metadata.data_is_empty() {
         let metadata = deserialize_and_verify_metadata(&accs.spl_metadata, (&*accs).into())?;
         payload.name = metadata.data.name.clone();
         payload.symbol = metadata.data.symbol;
@@ -131,7 +142,7 @@ pub fn attest_token(
         },
     );
 
-    let ix = Instruction::new_with_bytes(
+    let mut ix = Instruction::new_with_bytes(
         accs.config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
         vec![
@@ -146,7 +157,25 @@ pub fn attest_token(
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
         ],
     );
+    ix.accounts.extend(emitter_fee_account(
+        ctx,
+        &accs.config.wormhole_bridge,
+        accs.emitter.key,
+    ));
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
 
     Ok(())
 }
+
+/// Unpacks the mint being attested, which may belong to either token program.
+fn unpack_mint(mint: &Info) -> Result<Mint> {
+    let data: &[u8] = &mint.data.borrow();
+    if *mint.owner == spl_token::id() {
+        Ok(Mint::unpack(data)?)
+    } else if *mint.owner == token_2022::id() {
+        token_2022::verify_mint_extensions(data)?;
+        Ok(token_2022::unpack(data, token_2022::ACCOUNT_TYPE_MINT)?)
+    } else {
+        Err(WrongAccountOwner.into())
+    }
+}
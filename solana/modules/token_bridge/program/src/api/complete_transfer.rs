@@ -7,12 +7,14 @@ use crate::{
         Endpoint,
         EndpointDerivationData,
         MintSigner,
+        Token2022CustodyAccount,
         WrappedDerivationData,
         WrappedMetaDerivationData,
         WrappedMint,
         WrappedTokenMeta,
     },
     messages::PayloadTransfer,
+    token_2022,
     types::*,
     TokenBridgeError::*,
     INVALID_VAAS,
@@ -158,6 +160,130 @@ pub fn complete_native(
     Ok(())
 }
 
+#[derive(FromAccounts)]
+pub struct CompleteNativeToken2022<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub vaa: PayloadMessage<'b, PayloadTransfer>,
+    pub claim: Mut<Claim<'b>>,
+    pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
+
+    pub to: Mut<Data<'b, Token2022Account, { AccountState::Initialized }>>,
+    pub to_fees: Mut<Data<'b, Token2022Account, { AccountState::Initialized }>>,
+    pub custody: Mut<Token2022CustodyAccount<'b, { AccountState::Initialized }>>,
+    pub mint: Data<'b, Token2022Mint, { AccountState::Initialized }>,
+
+    pub custody_signer: CustodySigner<'b>,
+}
+
+impl<'a> From<&CompleteNativeToken2022<'a>> for EndpointDerivationData {
+    fn from(accs: &CompleteNativeToken2022<'a>) -> Self {
+        EndpointDerivationData {
+            emitter_chain: accs.vaa.meta().emitter_chain,
+            emitter_address: accs.vaa.meta().emitter_address,
+        }
+    }
+}
+
+impl<'a> From<&CompleteNativeToken2022<'a>> for CustodyAccountDerivationData {
+    fn from(accs: &CompleteNativeToken2022<'a>) -> Self {
+        CustodyAccountDerivationData {
+            mint: *accs.mint.info().key,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct CompleteNativeToken2022Data {}
+
+pub fn complete_native_token_2022(
+    ctx: &ExecutionContext,
+    accs: &mut CompleteNativeToken2022,
+    _data: CompleteNativeToken2022Data,
+) -> Result<()> {
+    // Verify the chain registration
+    let derivation_data: EndpointDerivationData = (&*accs).into();
+    accs.chain_registration
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify that the custody account is derived correctly
+    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
+    accs.custody
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    // Verify mints
+    if *accs.mint.info().key != accs.to.mint {
+        return Err(InvalidMint.into());
+    }
+    if *accs.mint.info().key != accs.to_fees.mint {
+        return Err(InvalidMint.into());
+    }
+    if *accs.mint.info().key != accs.custody.mint {
+        return Err(InvalidMint.into());
+    }
+    if *accs.custody_signer.key != accs.custody.owner {
+        return Err(WrongAccountOwner.into());
+    }
+
+    // Verify VAA
+    if accs.vaa.token_address != accs.mint.info().key.to_bytes() {
+        return Err(InvalidMint.into());
+    }
+    if accs.vaa.token_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to_chain != CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+    if accs.vaa.to != accs.to.info().key.to_bytes() {
+        return Err(InvalidRecipient.into());
+    }
+    if INVALID_VAAS.contains(&&*accs.vaa.info().key.to_string()) {
+        return Err(InvalidVAA.into());
+    }
+
+    // Prevent vaa double signing
+    claim::consume(ctx, accs.payer.key, &mut accs.claim, &accs.vaa)?;
+
+    let mut amount = accs.vaa.amount.as_u64();
+    let mut fee = accs.vaa.fee.as_u64();
+
+    // Wormhole always caps transfers at 8 decimals; un-truncate if the local token has more
+    if accs.mint.decimals > 8 {
+        amount *= 10u64.pow((accs.mint.decimals - 8) as u32);
+        fee *= 10u64.pow((accs.mint.decimals - 8) as u32);
+    }
+
+    let token_amount = amount
+        .checked_sub(fee)
+        .ok_or(SolitaireError::InsufficientFunds)?;
+
+    // Transfer tokens, any transfer fee configured on the mint is withheld from the recipients.
+    let transfer_ix = token_2022::transfer_checked(
+        accs.custody.info().key,
+        accs.mint.info().key,
+        accs.to.info().key,
+        accs.custody_signer.key,
+        token_amount,
+        accs.mint.decimals,
+    )?;
+    invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
+
+    // Transfer fees
+    let transfer_ix = token_2022::transfer_checked(
+        accs.custody.info().key,
+        accs.mint.info().key,
+        accs.to_fees.info().key,
+        accs.custody_signer.key,
+        fee,
+        accs.mint.decimals,
+    )?;
+    invoke_seeded(&transfer_ix, ctx, &accs.custody_signer, None)?;
+
+    Ok(())
+}
+
 #[derive(FromAccounts)]
 pub struct CompleteWrapped<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
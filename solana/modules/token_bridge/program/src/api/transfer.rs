@@ -1,5 +1,6 @@
 use crate::{
     accounts::{
+        emitter_fee_account,
         AuthoritySigner,
         ConfigAccount,
         CoreBridge,
@@ -8,12 +9,14 @@ use crate::{
         CustodySigner,
         EmitterAccount,
         MintSigner,
+        Token2022CustodyAccount,
         WrappedDerivationData,
         WrappedMetaDerivationData,
         WrappedMint,
         WrappedTokenMeta,
     },
     messages::PayloadTransfer,
+    token_2022,
     types::*,
     TokenBridgeError,
     TokenBridgeError::{
@@ -50,6 +53,7 @@ use solitaire::{
     CreationLamports::Exempt,
     *,
 };
+use spl_token::state::Account;
 
 #[derive(FromAccounts)]
 pub struct TransferNative<'b> {
@@ -148,7 +152,7 @@ pub fn transfer_native(
         },
     );
 
-    let ix = Instruction::new_with_bytes(
+    let mut ix = Instruction::new_with_bytes(
         accs.config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
         vec![
@@ -163,6 +167,11 @@ pub fn transfer_native(
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
         ],
     );
+    ix.accounts.extend(emitter_fee_account(
+        ctx,
+        &accs.config.wormhole_bridge,
+        accs.emitter.key,
+    ));
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
 
     Ok(())
@@ -244,6 +253,237 @@ pub fn verify_and_execute_native_transfers(
     Ok((amount, fee))
 }
 
+#[derive(FromAccounts)]
+pub struct TransferNativeToken2022<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    pub from: Mut<Data<'b, Token2022Account, { AccountState::Initialized }>>,
+
+    pub mint: Mut<Data<'b, Token2022Mint, { AccountState::Initialized }>>,
+
+    pub custody: Mut<Token2022CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    // This could allow someone to race someone else's tx if they do the approval in a separate tx.
+    // Therefore the approval must be set in the same tx.
+    pub authority_signer: AuthoritySigner<'b>,
+
+    pub custody_signer: CustodySigner<'b>,
+
+    /// CPI Context
+    pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted message
+    pub message: Signer<Mut<Info<'b>>>,
+
+    /// Emitter of the VAA
+    pub emitter: EmitterAccount<'b>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Info<'b>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+impl<'a> From<&TransferNativeToken2022<'a>> for CustodyAccountDerivationData {
+    fn from(accs: &TransferNativeToken2022<'a>) -> Self {
+        CustodyAccountDerivationData {
+            mint: *accs.mint.info().key,
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct TransferNativeToken2022Data {
+    pub nonce: u32,
+    pub amount: u64,
+    pub fee: u64,
+    pub target_address: Address,
+    pub target_chain: ChainID,
+}
+
+pub fn transfer_native_token_2022(
+    ctx: &ExecutionContext,
+    accs: &mut TransferNativeToken2022,
+    data: TransferNativeToken2022Data,
+) -> Result<()> {
+    // Prevent transferring to the same chain.
+    if data.target_chain == CHAIN_ID_SOLANA {
+        return Err(InvalidChain.into());
+    }
+
+    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
+    let (amount, fee) = verify_and_execute_native_token_2022_transfers(
+        ctx,
+        &derivation_data,
+        &accs.payer,
+        &accs.from,
+        &accs.mint,
+        &accs.custody,
+        &accs.authority_signer,
+        &accs.custody_signer,
+        &accs.bridge,
+        &accs.fee_collector,
+        data.amount,
+        data.fee,
+    )?;
+
+    // Post message
+    let payload = PayloadTransfer {
+        amount: U256::from(amount),
+        token_address: accs.mint.info().key.to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: data.target_address,
+        to_chain: data.target_chain,
+        fee: U256::from(fee),
+    };
+    let params = (
+        bridge::instruction::Instruction::PostMessage,
+        PostMessageData {
+            nonce: data.nonce,
+            payload: payload.try_to_vec()?,
+            consistency_level: ConsistencyLevel::Finalized,
+        },
+    );
+
+    let mut ix = Instruction::new_with_bytes(
+        accs.config.wormhole_bridge,
+        params.try_to_vec()?.as_slice(),
+        vec![
+            AccountMeta::new(*accs.bridge.info().key, false),
+            AccountMeta::new(*accs.message.key, true),
+            AccountMeta::new_readonly(*accs.emitter.key, true),
+            AccountMeta::new(*accs.sequence.key, false),
+            AccountMeta::new(*accs.payer.key, true),
+            AccountMeta::new(*accs.fee_collector.key, false),
+            AccountMeta::new_readonly(*accs.clock.info().key, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+        ],
+    );
+    ix.accounts.extend(emitter_fee_account(
+        ctx,
+        &accs.config.wormhole_bridge,
+        accs.emitter.key,
+    ));
+    invoke_seeded(&ix, ctx, &accs.emitter, None)?;
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn verify_and_execute_native_token_2022_transfers(
+    ctx: &ExecutionContext,
+    derivation_data: &CustodyAccountDerivationData,
+    payer: &Mut<Signer<AccountInfo>>,
+    from: &Mut<Data<Token2022Account, { AccountState::Initialized }>>,
+    mint: &Mut<Data<Token2022Mint, { AccountState::Initialized }>>,
+    custody: &Mut<Token2022CustodyAccount<{ AccountState::MaybeInitialized }>>,
+    authority_signer: &AuthoritySigner,
+    custody_signer: &CustodySigner,
+    bridge: &Mut<CoreBridge<{ AccountState::Initialized }>>,
+    fee_collector: &Mut<Info>,
+    raw_amount: u64,
+    raw_fee: u64,
+) -> Result<(u64, u64)> {
+    // Verify that the custody account is derived correctly
+    custody.verify_derivation(ctx.program_id, derivation_data)?;
+
+    // Verify mints
+    if from.mint != *mint.info().key {
+        return Err(TokenBridgeError::InvalidMint.into());
+    }
+
+    // Fee must be less than amount
+    if raw_fee > raw_amount {
+        return Err(InvalidFee.into());
+    }
+
+    // Wrapped tokens are always SPL Token mints and don't make it past peeling the mint, so there
+    // is no need to check the mint authority here.
+
+    // Only mints whose extensions can't touch custody may be bridged.
+    token_2022::verify_mint_extensions(&mint.info().data.borrow())?;
+
+    if !custody.is_initialized() {
+        // Token-2022 accounts grow with the extensions their mint requires, such as the withheld
+        // amount of a transfer fee, so the program has to be asked for the size.
+        let size = token_2022::get_account_data_size(ctx.accounts, mint.info().key)?;
+        let seeds = custody.self_bumped_seeds(derivation_data, ctx.program_id);
+        let s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+        create_account(
+            ctx,
+            custody.info(),
+            payer.key,
+            Exempt,
+            size,
+            &token_2022::id(),
+            IsSigned::SignedWithSeeds(&[s.as_slice()]),
+        )?;
+
+        let init_ix = token_2022::initialize_account(
+            custody.info().key,
+            mint.info().key,
+            custody_signer.key,
+        )?;
+        invoke_signed(&init_ix, ctx.accounts, &[])?;
+    }
+
+    let trunc_divisor = 10u64.pow(8.max(mint.decimals as u32) - 8);
+    // Untruncate the amount to drop the remainder so we don't "burn" user's funds.
+    let amount_trunc: u64 = (raw_amount / trunc_divisor) * trunc_divisor;
+
+    // Transfer tokens
+    let balance = custody_balance(custody)?;
+    let transfer_ix = token_2022::transfer_checked(
+        from.info().key,
+        mint.info().key,
+        custody.info().key,
+        authority_signer.key,
+        amount_trunc,
+        mint.decimals,
+    )?;
+    invoke_seeded(&transfer_ix, ctx, authority_signer, None)?;
+
+    // A transfer fee configured on the mint is withheld from what custody receives, so only the
+    // amount that actually arrived can be bridged. What can't be represented in 8 decimals after
+    // the fee stays in custody.
+    let received = custody_balance(custody)?.saturating_sub(balance);
+
+    // Truncate to 8 decimals
+    let amount: u64 = received / trunc_divisor;
+    let fee: u64 = raw_fee / trunc_divisor;
+
+    // Fee must still be less than the amount received
+    if fee > amount {
+        return Err(InvalidFee.into());
+    }
+
+    // Pay fee
+    let transfer_ix = solana_program::system_instruction::transfer(
+        payer.key,
+        fee_collector.key,
+        bridge.config.fee,
+    );
+    invoke(&transfer_ix, ctx.accounts)?;
+
+    Ok((amount, fee))
+}
+
+/// Reads the current balance of a custody account, which changes underneath the peeled state
+/// during CPI.
+fn custody_balance(
+    custody: &Token2022CustodyAccount<{ AccountState::MaybeInitialized }>,
+) -> Result<u64> {
+    let data: &[u8] = &custody.info().data.borrow();
+    let account: Account = token_2022::unpack(data, token_2022::ACCOUNT_TYPE_ACCOUNT)?;
+    Ok(account.amount)
+}
+
 #[derive(FromAccounts)]
 pub struct TransferWrapped<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
@@ -344,7 +584,7 @@ pub fn transfer_wrapped(
         },
     );
 
-    let ix = Instruction::new_with_bytes(
+    let mut ix = Instruction::new_with_bytes(
         accs.config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
         vec![
@@ -359,6 +599,11 @@ pub fn transfer_wrapped(
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
         ],
     );
+    ix.accounts.extend(emitter_fee_account(
+        ctx,
+        &accs.config.wormhole_bridge,
+        accs.emitter.key,
+    ));
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
 
     Ok(())
@@ -1,5 +1,6 @@
 use crate::{
     accounts::{
+        emitter_fee_account,
         AuthoritySigner,
         ConfigAccount,
         CoreBridge,
@@ -222,7 +223,7 @@ pub fn transfer_native_with_payload(
         },
     );
 
-    let ix = Instruction::new_with_bytes(
+    let mut ix = Instruction::new_with_bytes(
         accs.config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
         vec![
@@ -237,6 +238,11 @@ pub fn transfer_native_with_payload(
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
         ],
     );
+    ix.accounts.extend(emitter_fee_account(
+        ctx,
+        &accs.config.wormhole_bridge,
+        accs.emitter.key,
+    ));
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
 
     Ok(())
@@ -351,7 +357,7 @@ pub fn transfer_wrapped_with_payload(
         },
     );
 
-    let ix = Instruction::new_with_bytes(
+    let mut ix = Instruction::new_with_bytes(
         accs.config.wormhole_bridge,
         params.try_to_vec()?.as_slice(),
         vec![
@@ -366,6 +372,11 @@ pub fn transfer_wrapped_with_payload(
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
         ],
     );
+    ix.accounts.extend(emitter_fee_account(
+        ctx,
+        &accs.config.wormhole_bridge,
+        accs.emitter.key,
+    ));
     invoke_seeded(&ix, ctx, &accs.emitter, None)?;
 
     Ok(())
@@ -19,6 +19,7 @@ use crate::{
     api::{
         complete_transfer::{
             CompleteNativeData,
+            CompleteNativeToken2022Data,
             CompleteWrappedData,
         },
         AttestTokenData,
@@ -26,6 +27,7 @@ use crate::{
         RegisterChainData,
         SenderAccount,
         TransferNativeData,
+        TransferNativeToken2022Data,
         TransferWrappedData,
         UpgradeContractData,
     },
@@ -35,6 +37,7 @@ use crate::{
         PayloadTransfer,
         PayloadTransferWithPayload,
     },
+    token_2022,
     CompleteNativeWithPayloadData,
     CompleteWrappedWithPayloadData,
     TransferNativeWithPayloadData,
@@ -46,6 +49,8 @@ use bridge::{
         Bridge,
         Claim,
         ClaimDerivationData,
+        EmitterFee,
+        EmitterFeeDerivationData,
         FeeCollector,
         Sequence,
         SequenceDerivationData,
@@ -140,6 +145,65 @@ pub fn complete_native(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn complete_native_token_2022(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    vaa: PostVAAData,
+    to: Pubkey,
+    fee_recipient: Option<Pubkey>,
+    mint: Pubkey,
+    data: CompleteNativeToken2022Data,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa.clone());
+    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        &program_id,
+    );
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            message_acc,
+            claim_acc,
+            AccountMeta::new_readonly(endpoint, false),
+            AccountMeta::new(to, false),
+            if let Some(fee_r) = fee_recipient {
+                AccountMeta::new(fee_r, false)
+            } else {
+                AccountMeta::new(to, false)
+            },
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(custody_signer_key, false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(token_2022::id(), false),
+        ],
+        data: (
+            crate::instruction::Instruction::CompleteNativeToken2022,
+            data,
+        )
+            .try_to_vec()?,
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn complete_native_with_payload(
     program_id: Pubkey,
@@ -468,6 +532,7 @@ fn claimable_vaa(
 /// | system_program   | system program                                                    | false  |
 /// | bridge_id        | bridge_id program                                                 | false  |
 /// | spl_token        | spl_token program                                                 | false  |
+/// | emitter_fee      | PDA(bridge_id,  \["EmitterFee", emitter\])                        | false  |
 pub fn transfer_native(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -496,6 +561,10 @@ pub fn transfer_native(
         &bridge_id,
     );
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::Uninitialized }>::key(
+        &EmitterFeeDerivationData { emitter_key },
+        &bridge_id,
+    );
 
     let instruction = crate::instruction::Instruction::TransferNative;
 
@@ -521,6 +590,93 @@ pub fn transfer_native(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            // Optional fee override of the emitter
+            AccountMeta::new_readonly(emitter_fee_key, false),
+        ],
+        data: (instruction, data).try_to_vec()?,
+    })
+}
+
+/// Required accounts
+///
+/// | name             | account                                                           | signer |
+/// |------------------+-------------------------------------------------------------------+--------|
+/// | payer            | Pubkey                                                            | true   |
+/// | config           | PDA(program_id, \["config"\])                                     | false  |
+/// | from             | Pubkey                                                            | false  |
+/// | mint             | Pubkey                                                            | false  |
+/// | custody          | PDA(program_id, \[mint\])                                         | false  |
+/// | authority_signer | PDA(program_id, \["authority_signer"\])                           | false  |
+/// | custody_signer   | PDA(program_id, \["custody_signer"\])                             | false  |
+/// | bridge_config    | PDA(bridge_id,  \["Bridge"\])                                     | false  |
+/// | message          | Pubkey                                                            | true   |
+/// | emitter          | PDA(program_id, \["emitter"\])                                    | false  |
+/// | sequence         | PDA(bridge_id,  \["Sequence", emitter\])                          | false  |
+/// | fee_collector    | PDA(bridge_id,  \["fee_collector"\])                              | false  |
+/// | rent             | rent sysvar                                                       | false  |
+/// | system_program   | system program                                                    | false  |
+/// | bridge_id        | bridge_id program                                                 | false  |
+/// | token_2022       | Token-2022 program                                                | false  |
+/// | emitter_fee      | PDA(bridge_id,  \["EmitterFee", emitter\])                        | false  |
+pub fn transfer_native_token_2022(
+    program_id: Pubkey,
+    bridge_id: Pubkey,
+    payer: Pubkey,
+    message_key: Pubkey,
+    from: Pubkey,
+    mint: Pubkey,
+    data: TransferNativeToken2022Data,
+) -> solitaire::Result<Instruction> {
+    let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData { mint },
+        &program_id,
+    );
+
+    let authority_signer_key = AuthoritySigner::key(None, &program_id);
+    let custody_signer_key = CustodySigner::key(None, &program_id);
+    let emitter_key = EmitterAccount::key(None, &program_id);
+
+    // Bridge keys
+    let bridge_config = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &bridge_id);
+    let sequence_key = Sequence::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter_key,
+        },
+        &bridge_id,
+    );
+    let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::Uninitialized }>::key(
+        &EmitterFeeDerivationData { emitter_key },
+        &bridge_id,
+    );
+
+    let instruction = crate::instruction::Instruction::TransferNativeToken2022;
+
+    Ok(Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new(from, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(custody_key, false),
+            AccountMeta::new_readonly(authority_signer_key, false),
+            AccountMeta::new_readonly(custody_signer_key, false),
+            AccountMeta::new(bridge_config, false),
+            AccountMeta::new(message_key, true),
+            AccountMeta::new_readonly(emitter_key, false),
+            AccountMeta::new(sequence_key, false),
+            AccountMeta::new(fee_collector_key, false),
+            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
+            // Dependencies
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            // Program
+            AccountMeta::new_readonly(bridge_id, false),
+            AccountMeta::new_readonly(token_2022::id(), false),
+            // Optional fee override of the emitter
+            AccountMeta::new_readonly(emitter_fee_key, false),
         ],
         data: (instruction, data).try_to_vec()?,
     })
@@ -548,6 +704,7 @@ pub fn transfer_native(
 /// | system_program   | system program                                                         | false  |
 /// | bridge_id        | bridge_id program                                                      | false  |
 /// | spl_token        | spl_token program                                                      | false  |
+/// | emitter_fee      | PDA(bridge_id,  \["EmitterFee", emitter\])                             | false  |
 pub fn transfer_native_with_payload(
     program_id: Pubkey,
     bridge_id: Pubkey,
@@ -576,6 +733,10 @@ pub fn transfer_native_with_payload(
         &bridge_id,
     );
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::Uninitialized }>::key(
+        &EmitterFeeDerivationData { emitter_key },
+        &bridge_id,
+    );
 
     let sender = match data.cpi_program_id {
         Some(cpi_program_id) => SenderAccount::key((), &cpi_program_id),
@@ -607,6 +768,8 @@ pub fn transfer_native_with_payload(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            // Optional fee override of the emitter
+            AccountMeta::new_readonly(emitter_fee_key, false),
         ],
         data: (instruction, data).try_to_vec()?,
     })
@@ -633,6 +796,7 @@ pub fn transfer_native_with_payload(
 /// | system_program   | system program                                                         | false  |
 /// | bridge_id        | bridge_id program                                                      | false  |
 /// | spl_token        | spl_token program                                                      | false  |
+/// | emitter_fee      | PDA(bridge_id,  \["EmitterFee", emitter\])                             | false  |
 #[allow(clippy::too_many_arguments)]
 pub fn transfer_wrapped(
     program_id: Pubkey,
@@ -673,6 +837,10 @@ pub fn transfer_wrapped(
         &bridge_id,
     );
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::Uninitialized }>::key(
+        &EmitterFeeDerivationData { emitter_key },
+        &bridge_id,
+    );
 
     let instruction = crate::instruction::Instruction::TransferWrapped;
 
@@ -698,6 +866,8 @@ pub fn transfer_wrapped(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            // Optional fee override of the emitter
+            AccountMeta::new_readonly(emitter_fee_key, false),
         ],
         data: (instruction, data).try_to_vec()?,
     })
@@ -725,6 +895,7 @@ pub fn transfer_wrapped(
 /// | system_program   | system program                                                         | false  |
 /// | bridge_id        | bridge_id program                                                      | false  |
 /// | spl_token        | spl_token program                                                      | false  |
+/// | emitter_fee      | PDA(bridge_id,  \["EmitterFee", emitter\])                             | false  |
 #[allow(clippy::too_many_arguments)]
 pub fn transfer_wrapped_with_payload(
     program_id: Pubkey,
@@ -765,6 +936,10 @@ pub fn transfer_wrapped_with_payload(
         &bridge_id,
     );
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::Uninitialized }>::key(
+        &EmitterFeeDerivationData { emitter_key },
+        &bridge_id,
+    );
 
     let sender = match data.cpi_program_id {
         Some(cpi_program_id) => SenderAccount::key((), &cpi_program_id),
@@ -796,6 +971,8 @@ pub fn transfer_wrapped_with_payload(
             // Program
             AccountMeta::new_readonly(bridge_id, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            // Optional fee override of the emitter
+            AccountMeta::new_readonly(emitter_fee_key, false),
         ],
         data: (instruction, data).try_to_vec()?,
     })
@@ -833,6 +1010,10 @@ pub fn attest(
         &bridge_id,
     );
     let fee_collector_key = FeeCollector::key(None, &bridge_id);
+    let emitter_fee_key = EmitterFee::<'_, { AccountState::Uninitialized }>::key(
+        &EmitterFeeDerivationData { emitter_key },
+        &bridge_id,
+    );
 
     Ok(Instruction {
         program_id,
@@ -854,6 +1035,8 @@ pub fn attest(
             AccountMeta::new(solana_program::system_program::id(), false),
             // Program
             AccountMeta::new_readonly(bridge_id, false),
+            // Optional fee override of the emitter
+            AccountMeta::new_readonly(emitter_fee_key, false),
         ],
         data: (
             crate::instruction::Instruction::AttestToken,
@@ -18,11 +18,13 @@ pub mod wasm;
 pub mod accounts;
 pub mod api;
 pub mod messages;
+pub mod token_2022;
 pub mod types;
 
 pub use api::{
     attest_token,
     complete_native,
+    complete_native_token_2022,
     complete_native_with_payload,
     complete_wrapped,
     complete_wrapped_with_payload,
@@ -30,6 +32,7 @@ pub use api::{
     initialize,
     register_chain,
     transfer_native,
+    transfer_native_token_2022,
     transfer_native_with_payload,
     transfer_wrapped,
     transfer_wrapped_with_payload,
@@ -38,6 +41,8 @@ pub use api::{
     AttestTokenData,
     CompleteNative,
     CompleteNativeData,
+    CompleteNativeToken2022,
+    CompleteNativeToken2022Data,
     CompleteNativeWithPayload,
     CompleteNativeWithPayloadData,
     CompleteWrapped,
@@ -52,6 +57,8 @@ pub use api::{
     RegisterChainData,
     TransferNative,
     TransferNativeData,
+    TransferNativeToken2022,
+    TransferNativeToken2022Data,
     TransferNativeWithPayload,
     TransferNativeWithPayloadData,
     TransferWrapped,
@@ -91,6 +98,7 @@ pub enum TokenBridgeError {
     InvalidVAA,
     NonexistentTokenMetadataAccount,
     NotMetadataV1Account,
+    UnsupportedMintExtension,
 }
 
 impl From<TokenBridgeError> for SolitaireError {
@@ -113,4 +121,6 @@ solitaire! {
     CompleteWrappedWithPayload => complete_wrapped_with_payload,
     TransferWrappedWithPayload => transfer_wrapped_with_payload,
     TransferNativeWithPayload => transfer_native_with_payload,
+    TransferNativeToken2022 => transfer_native_token_2022,
+    CompleteNativeToken2022 => complete_native_token_2022,
 }
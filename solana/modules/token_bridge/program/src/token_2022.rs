@@ -0,0 +1,327 @@
+//! Support for mints and token accounts owned by the SPL Token-2022 program.
+//!
+//! Token-2022 keeps the base state of the classic SPL Token program and appends extensions after
+//! it: the byte following the base `Account` layout marks the account type, followed by the
+//! extensions as type-length-value entries. Mints are padded to the length of an `Account` so both
+//! share the same offsets. Base instructions use the same encoding as the classic program, so the
+//! `spl_token` instruction builders are reused with the program id swapped.
+
+use crate::TokenBridgeError;
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{
+        AccountMeta,
+        Instruction,
+    },
+    program::{
+        get_return_data,
+        invoke,
+    },
+    program_error::ProgramError,
+    program_pack::{
+        IsInitialized,
+        Pack,
+    },
+    pubkey::Pubkey,
+};
+use solitaire::{
+    Result,
+    SolitaireError,
+};
+use spl_token::state::Account;
+
+solana_program::declare_id!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Offset of the account type marker, present once a mint or account carries extensions.
+const ACCOUNT_TYPE_OFFSET: usize = Account::LEN;
+
+pub const ACCOUNT_TYPE_MINT: u8 = 1;
+pub const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+const EXTENSION_TYPE_UNINITIALIZED: u16 = 0;
+const EXTENSION_TYPE_TRANSFER_FEE_CONFIG: u16 = 1;
+const EXTENSION_TYPE_DEFAULT_ACCOUNT_STATE: u16 = 6;
+const EXTENSION_TYPE_METADATA_POINTER: u16 = 18;
+const EXTENSION_TYPE_TOKEN_METADATA: u16 = 19;
+const EXTENSION_TYPE_GROUP_POINTER: u16 = 20;
+const EXTENSION_TYPE_TOKEN_GROUP: u16 = 21;
+const EXTENSION_TYPE_GROUP_MEMBER_POINTER: u16 = 22;
+const EXTENSION_TYPE_TOKEN_GROUP_MEMBER: u16 = 23;
+
+/// Mint extensions that can't affect tokens held in custody. Anything else, such as a permanent
+/// delegate, a transfer hook or pausing, would let the mint's authorities move or lock custody.
+const ALLOWED_MINT_EXTENSIONS: [u16; 7] = [
+    EXTENSION_TYPE_TRANSFER_FEE_CONFIG,
+    EXTENSION_TYPE_METADATA_POINTER,
+    EXTENSION_TYPE_TOKEN_METADATA,
+    EXTENSION_TYPE_GROUP_POINTER,
+    EXTENSION_TYPE_TOKEN_GROUP,
+    EXTENSION_TYPE_GROUP_MEMBER_POINTER,
+    EXTENSION_TYPE_TOKEN_GROUP_MEMBER,
+];
+
+/// Value of the DefaultAccountState extension for accounts that start out usable.
+const DEFAULT_ACCOUNT_STATE_INITIALIZED: u8 = 1;
+
+/// Token-2022 instruction returning the size of a token account for a given mint.
+const INSTRUCTION_GET_ACCOUNT_DATA_SIZE: u8 = 21;
+
+/// Metadata stored in a mint's TokenMetadata extension.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TokenMetadata {
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub additional_metadata: Vec<(String, String)>,
+}
+
+/// Unpacks the base state of a Token-2022 mint or account, ignoring any extensions.
+pub fn unpack<T: Pack + IsInitialized>(
+    data: &[u8],
+    account_type: u8,
+) -> std::result::Result<T, ProgramError> {
+    if data.len() < T::LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Without extensions the account is exactly as long as its base state, otherwise the marker
+    // must match the kind of state expected.
+    if data.len() != T::LEN && data.get(ACCOUNT_TYPE_OFFSET) != Some(&account_type) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    T::unpack(&data[..T::LEN])
+}
+
+/// Finds the value of an extension in the data of a Token-2022 mint or account.
+pub fn get_extension(data: &[u8], extension_type: u16) -> Option<&[u8]> {
+    let mut tlv = data.get(ACCOUNT_TYPE_OFFSET + 1..)?;
+    while tlv.len() >= 4 {
+        let kind = u16::from_le_bytes([tlv[0], tlv[1]]);
+        let len = u16::from_le_bytes([tlv[2], tlv[3]]) as usize;
+        if kind == EXTENSION_TYPE_UNINITIALIZED {
+            return None;
+        }
+
+        let value = tlv.get(4..4 + len)?;
+        if kind == extension_type {
+            return Some(value);
+        }
+        tlv = &tlv[4 + len..];
+    }
+
+    None
+}
+
+/// Checks that a Token-2022 mint only carries extensions the bridge supports. New accounts may
+/// default to initialized, but not to frozen as that would lock up custody.
+pub fn verify_mint_extensions(data: &[u8]) -> Result<()> {
+    let mut tlv = data.get(ACCOUNT_TYPE_OFFSET + 1..).unwrap_or_default();
+    while tlv.len() >= 4 {
+        let kind = u16::from_le_bytes([tlv[0], tlv[1]]);
+        let len = u16::from_le_bytes([tlv[2], tlv[3]]) as usize;
+        if kind == EXTENSION_TYPE_UNINITIALIZED {
+            break;
+        }
+
+        let value = tlv
+            .get(4..4 + len)
+            .ok_or(SolitaireError::from(ProgramError::InvalidAccountData))?;
+        let allowed = match kind {
+            EXTENSION_TYPE_DEFAULT_ACCOUNT_STATE => value == [DEFAULT_ACCOUNT_STATE_INITIALIZED],
+            kind => ALLOWED_MINT_EXTENSIONS.contains(&kind),
+        };
+        if !allowed {
+            return Err(TokenBridgeError::UnsupportedMintExtension.into());
+        }
+        tlv = &tlv[4 + len..];
+    }
+
+    Ok(())
+}
+
+/// Address the mint's MetadataPointer extension points to, if any.
+pub fn metadata_pointer(data: &[u8]) -> Option<Pubkey> {
+    // Authority followed by the metadata address, the default pubkey meaning unset.
+    let value = get_extension(data, EXTENSION_TYPE_METADATA_POINTER)?;
+    let address = Pubkey::new(value.get(32..64)?);
+    if address == Pubkey::default() {
+        None
+    } else {
+        Some(address)
+    }
+}
+
+/// Metadata embedded in the mint's TokenMetadata extension, if any.
+pub fn token_metadata(data: &[u8]) -> Option<Result<TokenMetadata>> {
+    let value = get_extension(data, EXTENSION_TYPE_TOKEN_METADATA)?;
+    Some(TokenMetadata::try_from_slice(value).map_err(|_| TokenBridgeError::InvalidMetadata.into()))
+}
+
+/// Asks the Token-2022 program how large a token account for `mint` has to be, accounting for the
+/// extensions the mint requires on its accounts.
+pub fn get_account_data_size(accounts: &[AccountInfo], mint: &Pubkey) -> Result<usize> {
+    let ix = Instruction::new_with_bytes(
+        id(),
+        &[INSTRUCTION_GET_ACCOUNT_DATA_SIZE],
+        vec![AccountMeta::new_readonly(*mint, false)],
+    );
+    invoke(&ix, accounts)?;
+
+    match get_return_data() {
+        Some((program, data)) if program == id() && data.len() == 8 => {
+            let mut size = [0u8; 8];
+            size.copy_from_slice(&data);
+            Ok(u64::from_le_bytes(size) as usize)
+        }
+        _ => Err(SolitaireError::from(ProgramError::InvalidAccountData)),
+    }
+}
+
+/// Builds a `TransferChecked` instruction for the Token-2022 program. Mints with a transfer fee
+/// reject plain transfers, so all transfers are checked.
+pub fn transfer_checked(
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Result<Instruction> {
+    let mut ix = spl_token::instruction::transfer_checked(
+        &spl_token::id(),
+        source,
+        mint,
+        destination,
+        authority,
+        &[],
+        amount,
+        decimals,
+    )?;
+    ix.program_id = id();
+    Ok(ix)
+}
+
+/// Builds an `InitializeAccount` instruction for the Token-2022 program.
+pub fn initialize_account(account: &Pubkey, mint: &Pubkey, owner: &Pubkey) -> Result<Instruction> {
+    let mut ix =
+        spl_token::instruction::initialize_account(&spl_token::id(), account, mint, owner)?;
+    ix.program_id = id();
+    Ok(ix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spl_token::state::Mint;
+
+    fn extension(kind: u16, value: &[u8]) -> Vec<u8> {
+        let mut tlv = kind.to_le_bytes().to_vec();
+        tlv.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        tlv.extend_from_slice(value);
+        tlv
+    }
+
+    fn mint_with_extensions(extensions: &[Vec<u8>]) -> Vec<u8> {
+        let mint = Mint {
+            decimals: 9,
+            is_initialized: true,
+            ..Mint::default()
+        };
+        let mut data = vec![0u8; ACCOUNT_TYPE_OFFSET];
+        mint.pack_into_slice(&mut data[..Mint::LEN]);
+        data.push(ACCOUNT_TYPE_MINT);
+        for extension in extensions {
+            data.extend_from_slice(extension);
+        }
+        data
+    }
+
+    #[test]
+    fn test_unpack() {
+        let data = mint_with_extensions(&[]);
+        let mint: Mint = unpack(&data, ACCOUNT_TYPE_MINT).unwrap();
+        assert_eq!(mint.decimals, 9);
+
+        // A mint is not an account.
+        assert!(unpack::<Mint>(&data, ACCOUNT_TYPE_ACCOUNT).is_err());
+
+        // Without extensions the layout matches the classic program.
+        let mint: Mint = unpack(&data[..Mint::LEN], ACCOUNT_TYPE_MINT).unwrap();
+        assert_eq!(mint.decimals, 9);
+    }
+
+    #[test]
+    fn test_metadata() {
+        let mint_key = Pubkey::new_unique();
+        let metadata = TokenMetadata {
+            update_authority: Pubkey::new_unique(),
+            mint: mint_key,
+            name: "Token".to_string(),
+            symbol: "TKN".to_string(),
+            uri: "".to_string(),
+            additional_metadata: vec![],
+        };
+        let mut pointer = Pubkey::new_unique().to_bytes().to_vec();
+        pointer.extend_from_slice(&mint_key.to_bytes());
+
+        let data = mint_with_extensions(&[
+            extension(EXTENSION_TYPE_METADATA_POINTER, &pointer),
+            extension(
+                EXTENSION_TYPE_TOKEN_METADATA,
+                &metadata.try_to_vec().unwrap(),
+            ),
+        ]);
+        assert_eq!(metadata_pointer(&data), Some(mint_key));
+
+        let parsed = token_metadata(&data).unwrap().ok().unwrap();
+        assert_eq!(parsed.mint, mint_key);
+        assert_eq!(parsed.name, "Token");
+        assert_eq!(parsed.symbol, "TKN");
+
+        // Mints without the extensions have no metadata.
+        let data = mint_with_extensions(&[]);
+        assert_eq!(metadata_pointer(&data), None);
+        assert!(token_metadata(&data).is_none());
+    }
+
+    #[test]
+    fn test_verify_mint_extensions() {
+        // Mints without extensions, in either layout, are supported.
+        let data = mint_with_extensions(&[]);
+        assert!(verify_mint_extensions(&data).is_ok());
+        assert!(verify_mint_extensions(&data[..Mint::LEN]).is_ok());
+
+        let data = mint_with_extensions(&[
+            extension(EXTENSION_TYPE_TRANSFER_FEE_CONFIG, &[0; 108]),
+            extension(EXTENSION_TYPE_METADATA_POINTER, &[0; 64]),
+            extension(EXTENSION_TYPE_DEFAULT_ACCOUNT_STATE, &[1]),
+        ]);
+        assert!(verify_mint_extensions(&data).is_ok());
+
+        // Frozen by default.
+        let data = mint_with_extensions(&[extension(EXTENSION_TYPE_DEFAULT_ACCOUNT_STATE, &[2])]);
+        assert!(verify_mint_extensions(&data).is_err());
+
+        // Mint close authority, permanent delegate, transfer hook and pausable.
+        for kind in [3, 12, 14, 26] {
+            let data = mint_with_extensions(&[
+                extension(EXTENSION_TYPE_METADATA_POINTER, &[0; 64]),
+                extension(kind, &[0; 64]),
+            ]);
+            assert!(verify_mint_extensions(&data).is_err());
+        }
+
+        // Truncated extension data.
+        let mut data =
+            mint_with_extensions(&[extension(EXTENSION_TYPE_METADATA_POINTER, &[0; 64])]);
+        data.truncate(data.len() - 1);
+        assert!(verify_mint_extensions(&data).is_err());
+    }
+}
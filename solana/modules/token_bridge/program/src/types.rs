@@ -1,3 +1,4 @@
+use crate::token_2022;
 use borsh::{
     BorshDeserialize,
     BorshSerialize,
@@ -87,3 +88,57 @@ impl Owned for WrappedMeta {
 
 pack_type!(SplMint, Mint, AccountOwner::Other(spl_token::id()));
 pack_type!(SplAccount, Account, AccountOwner::Other(spl_token::id()));
+
+/// Defines a wrapper around the base state of a Token-2022 mint or account. Unlike `pack_type`,
+/// the account data may be longer than the base state as extensions follow it.
+macro_rules! token_2022_type {
+    ($name:ident, $embed:ty, $account_type:expr) => {
+        #[repr(transparent)]
+        pub struct $name(pub $embed);
+
+        impl BorshDeserialize for $name {
+            fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+                let acc = $name(
+                    token_2022::unpack(buf, $account_type)
+                        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?,
+                );
+                // Extensions are read from the account data directly, mark everything as read.
+                *buf = &buf[..0];
+
+                Ok(acc)
+            }
+        }
+
+        impl BorshSerialize for $name {
+            fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                let mut data = [0u8; <$embed as solana_program::program_pack::Pack>::LEN];
+                solana_program::program_pack::Pack::pack_into_slice(&self.0, &mut data);
+                writer.write_all(&data)?;
+
+                Ok(())
+            }
+        }
+
+        impl Owned for $name {
+            fn owner(&self) -> AccountOwner {
+                AccountOwner::Other(token_2022::id())
+            }
+        }
+
+        impl std::ops::Deref for $name {
+            type Target = $embed;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl std::default::Default for $name {
+            fn default() -> Self {
+                $name(<$embed>::default())
+            }
+        }
+    };
+}
+
+token_2022_type!(Token2022Mint, Mint, token_2022::ACCOUNT_TYPE_MINT);
+token_2022_type!(Token2022Account, Account, token_2022::ACCOUNT_TYPE_ACCOUNT);
@@ -72,13 +72,19 @@ mod helpers {
         PostVAAData,
     };
     use solana_program_test::processor;
+    use spl_token_2022::extension::{
+        ExtensionType,
+        StateWithExtensions,
+    };
     use token_bridge::{
         CompleteNativeData,
+        CompleteNativeToken2022Data,
         CompleteNativeWithPayloadData,
         CompleteWrappedData,
         CreateWrappedData,
         RegisterChainData,
         TransferNativeData,
+        TransferNativeToken2022Data,
         TransferWrappedData,
     };
 
@@ -137,6 +143,11 @@ mod helpers {
             token_program,
             processor!(token_bridge::solitaire),
         );
+        builder.add_program(
+            "spl_token_2022",
+            token_bridge::token_2022::id(),
+            processor!(spl_token_2022::processor::Processor::process),
+        );
 
         // Some instructions go over the limit when tracing is enabled but we need that for better
         // logging.  We don't really care about the limit during these tests anyway.
@@ -331,6 +342,60 @@ mod helpers {
         .await
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub async fn transfer_native_token_2022(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        payer: &Keypair,
+        message: &Keypair,
+        from: &Keypair,
+        from_owner: &Keypair,
+        mint: Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::transfer_native_token_2022(
+            program,
+            bridge,
+            payer.pubkey(),
+            message.pubkey(),
+            from.pubkey(),
+            mint,
+            TransferNativeToken2022Data {
+                nonce: 0,
+                amount,
+                fee: 0,
+                target_address: [0u8; 32],
+                target_chain: 2,
+            },
+        )
+        .expect("Could not create Transfer Native Token-2022");
+
+        for account in instruction.accounts.iter().enumerate() {
+            println!("{}: {}", account.0, account.1.pubkey);
+        }
+
+        execute(
+            client,
+            payer,
+            &[payer, from_owner, message],
+            &[
+                spl_token_2022::instruction::approve(
+                    &spl_token_2022::id(),
+                    &from.pubkey(),
+                    &token_bridge::accounts::AuthoritySigner::key(None, &program),
+                    &from_owner.pubkey(),
+                    &[],
+                    amount,
+                )
+                .unwrap(),
+                instruction,
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn transfer_wrapped(
         client: &mut BanksClient,
@@ -458,6 +523,42 @@ mod helpers {
         .await
     }
 
+    pub async fn complete_native_token_2022(
+        client: &mut BanksClient,
+        program: Pubkey,
+        bridge: Pubkey,
+        message_acc: Pubkey,
+        vaa: PostVAAData,
+        payload: PayloadTransfer,
+        payer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction = instructions::complete_native_token_2022(
+            program,
+            bridge,
+            payer.pubkey(),
+            message_acc,
+            vaa,
+            Pubkey::new(&payload.to[..]),
+            None,
+            Pubkey::new(&payload.token_address[..]),
+            CompleteNativeToken2022Data {},
+        )
+        .expect("Could not create Complete Native Token-2022 instruction");
+
+        for account in instruction.accounts.iter().enumerate() {
+            println!("{}: {}", account.0, account.1.pubkey);
+        }
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
     pub async fn complete_transfer_wrapped(
         client: &mut BanksClient,
         program: Pubkey,
@@ -702,6 +803,123 @@ mod helpers {
         .await
     }
 
+    /// Create a Token-2022 mint carrying `extensions`, each initialized by one of
+    /// `extension_instructions` before the mint itself.
+    pub async fn create_mint_token_2022(
+        client: &mut BanksClient,
+        payer: &Keypair,
+        mint_authority: &Pubkey,
+        mint: &Keypair,
+        extensions: &[ExtensionType],
+        extension_instructions: Vec<Instruction>,
+    ) -> Result<(), BanksClientError> {
+        let mint_key = mint.pubkey();
+        let len = ExtensionType::get_account_len::<spl_token_2022::state::Mint>(extensions);
+
+        let mut instructions = vec![solana_sdk::system_instruction::create_account(
+            &payer.pubkey(),
+            &mint_key,
+            Rent::default().minimum_balance(len),
+            len as u64,
+            &spl_token_2022::id(),
+        )];
+        instructions.extend(extension_instructions);
+        instructions.push(
+            spl_token_2022::instruction::initialize_mint(
+                &spl_token_2022::id(),
+                &mint_key,
+                mint_authority,
+                Some(mint_authority),
+                0,
+            )
+            .unwrap(),
+        );
+
+        execute(
+            client,
+            payer,
+            &[payer, mint],
+            &instructions,
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    /// Create a Token-2022 account, `extensions` being those its mint requires on its accounts.
+    pub async fn create_token_account_token_2022(
+        client: &mut BanksClient,
+        payer: &Keypair,
+        token_acc: &Keypair,
+        token_authority: &Pubkey,
+        mint: &Pubkey,
+        extensions: &[ExtensionType],
+    ) -> Result<(), BanksClientError> {
+        let token_key = token_acc.pubkey();
+        let len = ExtensionType::get_account_len::<spl_token_2022::state::Account>(extensions);
+        execute(
+            client,
+            payer,
+            &[payer, token_acc],
+            &[
+                solana_sdk::system_instruction::create_account(
+                    &payer.pubkey(),
+                    &token_key,
+                    Rent::default().minimum_balance(len),
+                    len as u64,
+                    &spl_token_2022::id(),
+                ),
+                spl_token_2022::instruction::initialize_account(
+                    &spl_token_2022::id(),
+                    &token_key,
+                    mint,
+                    token_authority,
+                )
+                .unwrap(),
+            ],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    pub async fn mint_tokens_token_2022(
+        client: &mut BanksClient,
+        payer: &Keypair,
+        mint_authority: &Keypair,
+        mint: &Keypair,
+        token_account: &Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        execute(
+            client,
+            payer,
+            &[payer, mint_authority],
+            &[spl_token_2022::instruction::mint_to(
+                &spl_token_2022::id(),
+                &mint.pubkey(),
+                token_account,
+                &mint_authority.pubkey(),
+                &[],
+                amount,
+            )
+            .unwrap()],
+            CommitmentLevel::Processed,
+        )
+        .await
+    }
+
+    /// Fetch the balance of a Token-2022 account.
+    pub async fn get_token_2022_balance(client: &mut BanksClient, account: Pubkey) -> u64 {
+        let account = client
+            .get_account_with_commitment(account, CommitmentLevel::Processed)
+            .await
+            .unwrap()
+            .unwrap();
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+            .unwrap()
+            .base
+            .amount
+    }
+
     /// Utility function for generating VAA's from message data.
     pub fn generate_vaa<T: Into<Vec<u8>>>(
         emitter: Address,
@@ -4,6 +4,8 @@ use bridge::{
         PostedVAA,
         PostedVAADerivationData,
     },
+    DeserializePayload,
+    PostedMessageData,
     SerializePayload,
 };
 use libsecp256k1::SecretKey;
@@ -25,6 +27,14 @@ use solitaire::{
     processors::seeded::Seeded,
     AccountState,
 };
+use spl_token_2022::{
+    extension::{
+        default_account_state::instruction::initialize_default_account_state,
+        transfer_fee::instruction::initialize_transfer_fee_config,
+        ExtensionType,
+    },
+    instruction::initialize_mint_close_authority,
+};
 
 use std::{
     collections::HashMap,
@@ -33,6 +43,8 @@ use std::{
 use token_bridge::{
     accounts::{
         ConfigAccount,
+        CustodyAccount,
+        CustodyAccountDerivationData,
         WrappedDerivationData,
         WrappedMint,
     },
@@ -57,6 +69,9 @@ const GOVERNANCE_KEY: [u8; 64] = [
 const CHAIN_ID_SOLANA: u16 = 1;
 const CHAIN_ID_ETH: u16 = 2;
 
+/// Transfer fee charged by Token-2022 test mints, 1%.
+const TRANSFER_FEE_BASIS_POINTS: u16 = 100;
+
 struct Context {
     /// Guardian public keys.
     guardians: Vec<[u8; 20]>,
@@ -618,3 +633,350 @@ async fn transfer_native_with_payload_in() {
     .await
     .unwrap();
 }
+
+// Create a Token-2022 mint charging a transfer fee, along with a token account holding some of it.
+async fn create_transfer_fee_mint(
+    context: &mut Context,
+) -> Result<(Keypair, Keypair), TransportError> {
+    let mint = Keypair::new();
+    common::create_mint_token_2022(
+        &mut context.client,
+        &context.payer,
+        &context.mint_authority.pubkey(),
+        &mint,
+        &[ExtensionType::TransferFeeConfig],
+        vec![initialize_transfer_fee_config(
+            &spl_token_2022::id(),
+            &mint.pubkey(),
+            None,
+            None,
+            TRANSFER_FEE_BASIS_POINTS,
+            u64::MAX,
+        )
+        .unwrap()],
+    )
+    .await?;
+
+    let token_account = Keypair::new();
+    common::create_token_account_token_2022(
+        &mut context.client,
+        &context.payer,
+        &token_account,
+        &context.token_authority.pubkey(),
+        &mint.pubkey(),
+        &[ExtensionType::TransferFeeAmount],
+    )
+    .await?;
+
+    common::mint_tokens_token_2022(
+        &mut context.client,
+        &context.payer,
+        &context.mint_authority,
+        &mint,
+        &token_account.pubkey(),
+        1000,
+    )
+    .await?;
+
+    Ok((mint, token_account))
+}
+
+#[tokio::test]
+async fn attest_token_2022() {
+    let mut context = set_up().await.unwrap();
+    let (mint, _) = create_transfer_fee_mint(&mut context).await.unwrap();
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ..
+    } = context;
+
+    let message = &Keypair::new();
+
+    common::attest(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        mint.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    let message: PostedMessageData = common::get_account_data(client, message.pubkey())
+        .await
+        .unwrap();
+    let payload = PayloadAssetMeta::deserialize(&mut message.payload.as_slice()).unwrap();
+    assert_eq!(payload.token_address, mint.pubkey().to_bytes());
+    assert_eq!(payload.token_chain, CHAIN_ID_SOLANA);
+    assert_eq!(payload.decimals, 0);
+}
+
+#[tokio::test]
+async fn attest_token_2022_unsupported_extension() {
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint_authority,
+        ..
+    } = set_up().await.unwrap();
+
+    // A mint that can be closed, and one freezing new accounts such as custody.
+    let closable = Keypair::new();
+    let frozen = Keypair::new();
+    let mints = [
+        (
+            &closable,
+            ExtensionType::MintCloseAuthority,
+            initialize_mint_close_authority(
+                &spl_token_2022::id(),
+                &closable.pubkey(),
+                Some(&mint_authority.pubkey()),
+            )
+            .unwrap(),
+        ),
+        (
+            &frozen,
+            ExtensionType::DefaultAccountState,
+            initialize_default_account_state(
+                &spl_token_2022::id(),
+                &frozen.pubkey(),
+                &spl_token_2022::state::AccountState::Frozen,
+            )
+            .unwrap(),
+        ),
+    ];
+
+    for (mint, extension, instruction) in mints {
+        common::create_mint_token_2022(
+            client,
+            payer,
+            &mint_authority.pubkey(),
+            mint,
+            &[extension],
+            vec![instruction],
+        )
+        .await
+        .unwrap();
+
+        let message = &Keypair::new();
+        assert!(common::attest(
+            client,
+            token_bridge,
+            bridge,
+            payer,
+            message,
+            mint.pubkey(),
+            0,
+        )
+        .await
+        .is_err());
+    }
+}
+
+#[tokio::test]
+async fn transfer_native_token_2022() {
+    let mut context = set_up().await.unwrap();
+    let (mint, token_account) = create_transfer_fee_mint(&mut context).await.unwrap();
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref token_authority,
+        ..
+    } = context;
+
+    let message = &Keypair::new();
+    common::transfer_native_token_2022(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        &token_account,
+        token_authority,
+        mint.pubkey(),
+        500,
+    )
+    .await
+    .unwrap();
+
+    // The mint withholds its fee from what custody receives, only the remainder is bridged.
+    let custody = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData {
+            mint: mint.pubkey(),
+        },
+        &token_bridge,
+    );
+    assert_eq!(common::get_token_2022_balance(client, custody).await, 495);
+    assert_eq!(
+        common::get_token_2022_balance(client, token_account.pubkey()).await,
+        500
+    );
+
+    let message: PostedMessageData = common::get_account_data(client, message.pubkey())
+        .await
+        .unwrap();
+    let payload = PayloadTransfer::deserialize(&mut message.payload.as_slice()).unwrap();
+    assert_eq!(payload.amount, U256::from(495u128));
+    assert_eq!(payload.token_address, mint.pubkey().to_bytes());
+    assert_eq!(payload.token_chain, CHAIN_ID_SOLANA);
+}
+
+#[tokio::test]
+async fn transfer_native_token_2022_unsupported_extension() {
+    let mut context = set_up().await.unwrap();
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref mint_authority,
+        ref token_authority,
+        ..
+    } = context;
+
+    let mint = Keypair::new();
+    common::create_mint_token_2022(
+        client,
+        payer,
+        &mint_authority.pubkey(),
+        &mint,
+        &[ExtensionType::MintCloseAuthority],
+        vec![initialize_mint_close_authority(
+            &spl_token_2022::id(),
+            &mint.pubkey(),
+            Some(&mint_authority.pubkey()),
+        )
+        .unwrap()],
+    )
+    .await
+    .unwrap();
+
+    let token_account = Keypair::new();
+    common::create_token_account_token_2022(
+        client,
+        payer,
+        &token_account,
+        &token_authority.pubkey(),
+        &mint.pubkey(),
+        &[],
+    )
+    .await
+    .unwrap();
+    common::mint_tokens_token_2022(
+        client,
+        payer,
+        mint_authority,
+        &mint,
+        &token_account.pubkey(),
+        1000,
+    )
+    .await
+    .unwrap();
+
+    let message = &Keypair::new();
+    assert!(common::transfer_native_token_2022(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        &token_account,
+        token_authority,
+        mint.pubkey(),
+        500,
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn transfer_native_token_2022_in() {
+    let mut context = set_up().await.unwrap();
+    register_chain(&mut context).await;
+    let (mint, token_account) = create_transfer_fee_mint(&mut context).await.unwrap();
+    let Context {
+        ref payer,
+        ref mut client,
+        bridge,
+        token_bridge,
+        ref token_authority,
+        ref guardian_keys,
+        ..
+    } = context;
+
+    // Do an initial transfer so that custody holds some tokens, 495 after the transfer fee.
+    let message = &Keypair::new();
+    common::transfer_native_token_2022(
+        client,
+        token_bridge,
+        bridge,
+        payer,
+        message,
+        &token_account,
+        token_authority,
+        mint.pubkey(),
+        500,
+    )
+    .await
+    .unwrap();
+
+    let nonce = rand::thread_rng().gen();
+
+    let payload = PayloadTransfer {
+        amount: U256::from(100u128),
+        token_address: mint.pubkey().to_bytes(),
+        token_chain: CHAIN_ID_SOLANA,
+        to: token_account.pubkey().to_bytes(),
+        to_chain: CHAIN_ID_SOLANA,
+        fee: U256::from(0u128),
+    };
+    let message = payload.try_to_vec().unwrap();
+
+    let (vaa, body, _) = common::generate_vaa([0u8; 32], CHAIN_ID_ETH, message, nonce, 1);
+    let signature_set = common::verify_signatures(client, &bridge, payer, body, guardian_keys, 0)
+        .await
+        .unwrap();
+    common::post_vaa(client, bridge, payer, signature_set, vaa.clone())
+        .await
+        .unwrap();
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: body.to_vec(),
+    };
+    let message_key =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(msg_derivation_data, &bridge);
+
+    common::complete_native_token_2022(
+        client,
+        token_bridge,
+        bridge,
+        message_key,
+        vaa,
+        payload,
+        payer,
+    )
+    .await
+    .unwrap();
+
+    // Custody pays out the full amount with transfer_checked, as the mint rejects unchecked
+    // transfers, and the mint withholds its fee from the recipient.
+    let custody = CustodyAccount::<'_, { AccountState::Initialized }>::key(
+        &CustodyAccountDerivationData {
+            mint: mint.pubkey(),
+        },
+        &token_bridge,
+    );
+    assert_eq!(common::get_token_2022_balance(client, custody).await, 395);
+    assert_eq!(
+        common::get_token_2022_balance(client, token_account.pubkey()).await,
+        599
+    );
+}
@@ -167,6 +167,30 @@ impl<const SEED: &'static str, T: BorshSerialize + Owned + Default>
     }
 }
 
+impl<const SEED: &'static str, T: BorshSerialize + Owned + Default>
+    Derive<Data<'_, T, { AccountState::MaybeInitialized }>, SEED>
+{
+    pub fn create(
+        &self,
+        ctx: &ExecutionContext,
+        payer: &Pubkey,
+        lamports: CreationLamports,
+    ) -> Result<()> {
+        // Get serialized struct size
+        let size = self.0.try_to_vec().unwrap().len();
+        let (_, bump_seed) = Pubkey::find_program_address(&[SEED.as_bytes()][..], ctx.program_id);
+        create_account(
+            ctx,
+            self.info(),
+            payer,
+            lamports,
+            size,
+            ctx.program_id,
+            SignedWithSeeds(&[&[SEED.as_bytes(), &[bump_seed]]]),
+        )
+    }
+}
+
 /// Create an account.
 ///
 /// This proceeds in the following order: